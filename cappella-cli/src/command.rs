@@ -0,0 +1,138 @@
+use cappella_core::local::app_error::AppError;
+
+pub const USAGE: &str = "Usage: cappella-cli [--json] <command>
+
+Commands:
+  play <uri|path>...  Plays the URIs and the paths in order, until the end
+  volume <0.0-1.0>    Sets the saved volume
+
+While playing, these commands are read from the standard input, one per line:
+  play <uri|path>, pause, stop, next, seek <seconds>, volume <0.0-1.0>";
+
+const JSON_FLAG: &str = "--json";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Format {
+    #[default]
+    Human,
+    /// One JSON message per line, for the scripts.
+    Json,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Play(Vec<String>),
+    Pause,
+    Stop,
+    Next,
+    Seek(f64),
+    Volume(f64),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Options {
+    pub format: Format,
+    pub command: Command,
+}
+
+pub fn parse_args(args: &[String]) -> Result<Options, AppError> {
+    let (format, args) = match args.split_first() {
+        Some((flag, args)) if flag == JSON_FLAG => (Format::Json, args),
+        _ => (Format::Human, args),
+    };
+
+    let (name, args) = args
+        .split_first()
+        .ok_or_else(|| AppError::InvalidInput("A command is expected.".to_owned()))?;
+
+    Ok(Options {
+        format,
+        command: parse(name, args)?,
+    })
+}
+
+/// A line from the standard input, the play argument is the rest of the line.
+pub fn parse_line(line: &str) -> Result<Command, AppError> {
+    let line = line.trim();
+    let (name, arg) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    let arg = arg.trim();
+    let args = if arg.is_empty() {
+        Vec::new()
+    } else {
+        vec![arg.to_owned()]
+    };
+
+    parse(name, &args)
+}
+
+fn parse(name: &str, args: &[String]) -> Result<Command, AppError> {
+    match (name, args) {
+        ("play", []) => Err(AppError::InvalidInput(
+            "`play` expects at least one URI or path.".to_owned(),
+        )),
+        ("play", uris) => Ok(Command::Play(uris.to_vec())),
+        ("pause", []) => Ok(Command::Pause),
+        ("stop", []) => Ok(Command::Stop),
+        ("next", []) => Ok(Command::Next),
+        ("seek", [seconds]) => Ok(Command::Seek(parse_number(name, seconds)?)),
+        ("volume", [volume]) => Ok(Command::Volume(parse_number(name, volume)?)),
+        _ => Err(AppError::InvalidInput(format!(
+            "Unknown command or wrong arguments: `{name}`."
+        ))),
+    }
+}
+
+fn parse_number(name: &str, value: &str) -> Result<f64, AppError> {
+    value
+        .parse()
+        .map_err(|_| AppError::InvalidInput(format!("`{name}` expects a number, not `{value}`.")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_args, parse_line, Command, Format, Options};
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args_play() {
+        let options = parse_args(&args(&["play", "a.mp3", "http://radio/stream"])).unwrap();
+
+        assert_eq!(
+            options,
+            Options {
+                format: Format::Human,
+                command: Command::Play(args(&["a.mp3", "http://radio/stream"])),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_args_json() {
+        let options = parse_args(&args(&["--json", "volume", "0.5"])).unwrap();
+
+        assert_eq!(options.format, Format::Json);
+        assert_eq!(options.command, Command::Volume(0.5));
+    }
+
+    #[test]
+    fn test_parse_args_err() {
+        assert!(parse_args(&[]).is_err());
+        assert!(parse_args(&args(&["play"])).is_err());
+        assert!(parse_args(&args(&["seek", "later"])).is_err());
+        assert!(parse_args(&args(&["rewind"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_line() {
+        assert_eq!(parse_line("pause\n").unwrap(), Command::Pause);
+        assert_eq!(parse_line(" seek 12.5 ").unwrap(), Command::Seek(12.5));
+        assert_eq!(
+            parse_line("play /music/a b.flac").unwrap(),
+            Command::Play(args(&["/music/a b.flac"]))
+        );
+        assert!(parse_line("stop now").is_err());
+    }
+}
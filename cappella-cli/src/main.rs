@@ -0,0 +1,121 @@
+use std::{
+    env,
+    io::{self, BufRead},
+    path::PathBuf,
+    process,
+    sync::Arc,
+    thread,
+    time::Duration,
+};
+
+use cappella_core::{
+    frontend::message::Message,
+    local::{app_error::AppError, state::State, uri},
+};
+use command::{Command, Options};
+
+mod command;
+mod output;
+
+/// The same directories as the desktop application, to share the settings and the library.
+const APP_IDENTIFIER: &str = "me.tabinol";
+const RUNNING_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+
+    let options = command::parse_args(&args).unwrap_or_else(|err| {
+        eprintln!("{err}\n\n{}", command::USAGE);
+        process::exit(2);
+    });
+
+    if let Err(err) = run(options) {
+        eprintln!("{err}");
+        process::exit(1);
+    }
+}
+
+fn run(options: Options) -> Result<(), AppError> {
+    let is_play = match options.command {
+        Command::Play(_) => true,
+        Command::Volume(_) => false,
+        _ => {
+            return Err(AppError::InvalidInput(
+                "This command is read from the standard input while playing.".to_owned(),
+            ))
+        }
+    };
+
+    let state = Arc::new(cappella_core::init(
+        app_dir(dirs::config_dir())?,
+        app_dir(dirs::data_dir())?,
+        output::new_arc(options.format),
+    ));
+    let mut result = execute(&state, options.command);
+
+    if is_play && result.is_ok() {
+        result = wait_until_stopped(&state);
+    }
+    state.end();
+
+    result
+}
+
+fn app_dir(dir: Option<PathBuf>) -> Result<PathBuf, AppError> {
+    dir.map(|dir| dir.join(APP_IDENTIFIER))
+        .ok_or_else(|| AppError::InvalidPath("The user directories are unknown.".to_owned()))
+}
+
+fn execute(state: &State, command: Command) -> Result<(), AppError> {
+    let player_front = state.player_front();
+
+    match command {
+        Command::Play(values) => {
+            let uris = values
+                .iter()
+                .map(|value| uri::from_uri_or_path(value))
+                .collect::<Result<Vec<_>, _>>()?;
+            player_front.clear_queue()?;
+            player_front.enqueue(uris)?;
+            player_front.jump_to(0)
+        }
+        Command::Pause => player_front.pause(),
+        Command::Stop => player_front.stop(),
+        Command::Next => player_front.next(),
+        Command::Seek(seconds) => player_front.seek((seconds * 1_000_000_000.0) as i64),
+        Command::Volume(volume) => {
+            let volume = player_front.set_volume(volume)?;
+            state.frontend_pipe().send(Message::VolumeChanged {
+                volume: volume.volume,
+                muted: volume.muted,
+            });
+            Ok(())
+        }
+    }
+}
+
+/// The commands are read from the standard input until the end of the queue or a stop.
+fn wait_until_stopped(state: &Arc<State>) -> Result<(), AppError> {
+    let input_state = state.clone();
+
+    thread::Builder::new()
+        .name("input".to_owned())
+        .spawn(move || {
+            for line in io::stdin().lock().lines() {
+                let result = line
+                    .map_err(AppError::from)
+                    .and_then(|line| command::parse_line(&line))
+                    .and_then(|command| execute(&input_state, command));
+
+                if let Err(err) = result {
+                    eprintln!("{err}");
+                }
+            }
+        })?;
+
+    while state.player_front().is_running()? {
+        thread::sleep(RUNNING_CHECK_INTERVAL);
+    }
+
+    Ok(())
+}
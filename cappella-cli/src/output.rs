@@ -0,0 +1,179 @@
+use std::{fmt::Debug, sync::Arc};
+
+use cappella_core::{
+    frontend::{
+        message::{ErrorReport, Message},
+        pipe::Pipe,
+    },
+    local::mutex_lock_timeout::MutexLockTimeout,
+};
+use parking_lot::Mutex;
+
+use crate::command::Format;
+
+const NANOS_PER_SECOND: i64 = 1_000_000_000;
+
+/// Prints the events on the standard output, the errors on the standard error in human form.
+pub fn new_arc(format: Format) -> Arc<dyn Pipe> {
+    Arc::new(Pipe_ {
+        format,
+        last_second: Mutex::default(),
+    })
+}
+
+#[derive(Debug)]
+struct Pipe_ {
+    format: Format,
+    last_second: Mutex<Option<i64>>,
+}
+
+impl Pipe for Pipe_ {
+    fn send(&self, frontend_message: Message) {
+        match self.format {
+            Format::Json => match serde_json::to_string(&frontend_message) {
+                Ok(line) => println!("{line}"),
+                Err(err) => eprintln!("Unable to write the message {frontend_message:?}: {err}"),
+            },
+            Format::Human => {
+                if let Message::Position { position_ns, .. } = frontend_message {
+                    if !self.is_new_second(position_ns) {
+                        return;
+                    }
+                }
+
+                match human_line(&frontend_message) {
+                    Some(line) if is_error(&frontend_message) => eprintln!("{line}"),
+                    Some(line) => println!("{line}"),
+                    None => {}
+                }
+            }
+        }
+    }
+}
+
+impl Pipe_ {
+    /// The position is printed once per second in human form.
+    fn is_new_second(&self, position_ns: i64) -> bool {
+        let second = position_ns / NANOS_PER_SECOND;
+
+        match self.last_second.try_lock_default_duration() {
+            Ok(mut last_second) => last_second.replace(second) != Some(second),
+            Err(_) => false,
+        }
+    }
+}
+
+fn human_line(message: &Message) -> Option<String> {
+    match message {
+        Message::None | Message::LibraryScanProgress { .. } | Message::QueueChanged => None,
+        Message::Position {
+            position_ns,
+            duration_ns,
+        } if *duration_ns >= 0 => Some(format!(
+            "{} / {}",
+            format_time(*position_ns),
+            format_time(*duration_ns)
+        )),
+        Message::Position { position_ns, .. } => Some(format_time(*position_ns)),
+        Message::StateChanged(state) => Some(format!("{state:?}")),
+        Message::TrackStarted(uri) => Some(format!("Track started: {uri}")),
+        Message::TrackEnded(uri) => Some(format!("Track ended: {uri}")),
+        Message::NowPlaying(now_playing) => {
+            let title = now_playing.title.as_deref().unwrap_or(&now_playing.uri);
+            let details = [&now_playing.artist, &now_playing.album]
+                .into_iter()
+                .flatten()
+                .fold(title.to_owned(), |line, detail| {
+                    format!("{line} - {detail}")
+                });
+            Some(format!("Now playing: {details}"))
+        }
+        Message::VolumeChanged { volume, muted } => Some(format!(
+            "Volume: {:.0}%{}",
+            volume * 100.0,
+            if *muted { " (muted)" } else { "" }
+        )),
+        Message::Buffering { percent } => Some(format!("Buffering: {percent}%")),
+        Message::Reconnecting {
+            uri,
+            attempt,
+            delay_ms,
+        } => Some(format!(
+            "Reconnecting in {}s, attempt {attempt} ({uri})",
+            delay_ms / 1000
+        )),
+        Message::LibraryScanFinished { total } => Some(format!("Library scanned: {total} files")),
+        Message::PlaylistChanged { playlist_id } => {
+            Some(format!("Playlist changed: {playlist_id}"))
+        }
+        Message::Error(report) => Some(format!("Error: {}", with_uri(report))),
+        Message::Warning(report) => Some(format!("Warning: {}", with_uri(report))),
+        Message::MissingPlugin { uri, description } => {
+            Some(format!("Missing plugin: {description} ({uri})"))
+        }
+    }
+}
+
+fn is_error(message: &Message) -> bool {
+    matches!(
+        message,
+        Message::Error(_)
+            | Message::Warning(_)
+            | Message::MissingPlugin { .. }
+            | Message::Reconnecting { .. }
+    )
+}
+
+fn with_uri(report: &ErrorReport) -> String {
+    match &report.uri {
+        Some(uri) => format!("{} ({uri})", report.message),
+        None => report.message.clone(),
+    }
+}
+
+fn format_time(time_ns: i64) -> String {
+    let seconds = time_ns / NANOS_PER_SECOND;
+
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use cappella_core::{frontend::message::Message, streamer::now_playing::NowPlaying};
+
+    use super::human_line;
+
+    #[test]
+    fn test_human_position() {
+        let message = Message::Position {
+            position_ns: 75_500_000_000,
+            duration_ns: 200_000_000_000,
+        };
+
+        assert_eq!(human_line(&message).as_deref(), Some("1:15 / 3:20"));
+    }
+
+    #[test]
+    fn test_human_position_live() {
+        let message = Message::Position {
+            position_ns: 5_000_000_000,
+            duration_ns: -1,
+        };
+
+        assert_eq!(human_line(&message).as_deref(), Some("0:05"));
+    }
+
+    #[test]
+    fn test_human_now_playing() {
+        let message = Message::NowPlaying(NowPlaying {
+            title: Some("Fragments".to_owned()),
+            artist: Some("Nomyn".to_owned()),
+            ..NowPlaying::new("file:///a.mp3")
+        });
+
+        assert_eq!(
+            human_line(&message).as_deref(),
+            Some("Now playing: Fragments - Nomyn")
+        );
+    }
+}
@@ -0,0 +1,86 @@
+//! Serves an audio file in a loop as an endless Icecast-style stream, to try the network streams:
+//!
+//! ```sh
+//! cargo run -p cappella-core --example endless_stream -- src-tauri/tests-resources/Nomyn-Fragments.mp3
+//! cargo run -p cappella-cli -- play http://127.0.0.1:8000/stream
+//! ```
+//!
+//! With `--drop-after <seconds>`, each connection is closed after the delay, as on a network drop.
+
+use std::{
+    env, fs,
+    io::{BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    process, thread,
+    time::{Duration, Instant},
+};
+
+const ADDRESS: &str = "127.0.0.1:8000";
+const CHUNK_SIZE: usize = 4096;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (path, drop_after) = match args.as_slice() {
+        [path] => (path, None),
+        [path, option, seconds] if option == "--drop-after" => match seconds.parse() {
+            Ok(seconds) => (path, Some(Duration::from_secs(seconds))),
+            Err(err) => exit(&format!("Invalid seconds `{seconds}`: {err}")),
+        },
+        _ => exit("Usage: endless_stream <file> [--drop-after <seconds>]"),
+    };
+
+    let content = fs::read(path).unwrap_or_else(|err| exit(&format!("{path}: {err}")));
+    let listener = TcpListener::bind(ADDRESS).unwrap_or_else(|err| exit(&err.to_string()));
+    println!("Streaming `{path}` on http://{ADDRESS}/stream");
+
+    for stream in listener.incoming() {
+        let content = content.clone();
+
+        match stream {
+            Ok(stream) => {
+                thread::spawn(move || {
+                    if let Err(err) = serve(stream, &content, drop_after) {
+                        println!("Connection closed: {err}");
+                    }
+                });
+            }
+            Err(err) => eprintln!("Connection error: {err}"),
+        }
+    }
+}
+
+fn serve(
+    mut stream: TcpStream,
+    content: &[u8],
+    drop_after: Option<Duration>,
+) -> std::io::Result<()> {
+    // The request is read up to the empty line, its content is not used.
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut line = String::new();
+    while reader.read_line(&mut line)? > 2 {
+        line.clear();
+    }
+
+    write!(
+        stream,
+        "HTTP/1.0 200 OK\r\nContent-Type: audio/mpeg\r\nicy-name: Cappella endless stream\r\n\
+         Cache-Control: no-cache\r\n\r\n"
+    )?;
+
+    // Paced by the client, which stops reading once its buffer is full.
+    let start = Instant::now();
+    for chunk in content.chunks(CHUNK_SIZE).cycle() {
+        if drop_after.is_some_and(|drop_after| start.elapsed() >= drop_after) {
+            println!("Connection dropped");
+            return Ok(());
+        }
+        stream.write_all(chunk)?;
+    }
+
+    Ok(())
+}
+
+fn exit(message: &str) -> ! {
+    eprintln!("{message}");
+    process::exit(1);
+}
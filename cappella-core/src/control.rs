@@ -0,0 +1,6 @@
+#[cfg(unix)]
+pub mod client;
+pub mod request;
+#[cfg(unix)]
+pub mod server;
+pub mod subscribers;
@@ -0,0 +1,48 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    os::unix::net::UnixStream,
+    path::Path,
+};
+
+use crate::local::app_error::AppError;
+
+use super::request::Request;
+
+/// A connection to the control socket of a running instance.
+#[derive(Debug)]
+pub struct Client {
+    reader: BufReader<UnixStream>,
+    writer: UnixStream,
+}
+
+impl Client {
+    pub fn connect(socket_path: &Path) -> Result<Self, AppError> {
+        let writer = UnixStream::connect(socket_path)?;
+
+        Ok(Self {
+            reader: BufReader::new(writer.try_clone()?),
+            writer,
+        })
+    }
+
+    /// The response line, as sent by the server.
+    pub fn request(&mut self, request: &Request) -> Result<String, AppError> {
+        writeln!(self.writer, "{}", serde_json::to_string(request)?)?;
+        self.writer.flush()?;
+
+        self.read_line()?.ok_or_else(|| {
+            AppError::Channel("The connection is closed before the response.".to_owned())
+        })
+    }
+
+    /// The next line, as an event after a subscription. `None` when the connection is closed.
+    pub fn read_line(&mut self) -> Result<Option<String>, AppError> {
+        let mut line = String::new();
+
+        if self.reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(line.trim_end().to_owned()))
+    }
+}
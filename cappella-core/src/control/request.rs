@@ -0,0 +1,332 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use serde_json::Value;
+
+use crate::{
+    frontend::message::Message,
+    local::{app_error::AppError, state::State},
+    player::{front::Volume, order::Repeat},
+    playlist::{self, entry::Entry},
+    stations::station::Station,
+};
+
+/// A command for a running player, mirroring the Tauri commands.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum Request {
+    Play {
+        uri: String,
+    },
+    Pause,
+    Stop,
+    Seek {
+        position_ns: i64,
+    },
+    Next,
+    Previous,
+    JumpTo {
+        index: usize,
+    },
+    Enqueue {
+        uris: Vec<String>,
+    },
+    RemoveFromQueue {
+        index: usize,
+    },
+    MoveInQueue {
+        from: usize,
+        to: usize,
+    },
+    ClearQueue,
+    SetGapless {
+        gapless: bool,
+    },
+    SetRepeat {
+        repeat: Repeat,
+    },
+    SetShuffle {
+        shuffle: bool,
+        seed: Option<u64>,
+    },
+    SetVolume {
+        volume: f64,
+    },
+    SetMute {
+        muted: bool,
+    },
+    GetVolume,
+    GetStatus,
+    AddLibraryFolder {
+        folder: PathBuf,
+    },
+    RescanLibrary,
+    ListLibraryTracks,
+    ListAlbums,
+    ListArtists,
+    ListTracksByAlbum {
+        album_id: i64,
+    },
+    ListTracksByArtist {
+        artist_id: i64,
+    },
+    Search {
+        query: String,
+        offset: usize,
+        limit: usize,
+    },
+    ListStations,
+    AddStation {
+        station: Station,
+    },
+    RemoveStation {
+        name: String,
+    },
+    PlayStation {
+        name: String,
+    },
+    ImportStations {
+        path: PathBuf,
+    },
+    ExportStations {
+        path: PathBuf,
+    },
+    LoadPlaylist {
+        path: PathBuf,
+    },
+    SaveQueueAsPlaylist {
+        path: PathBuf,
+    },
+    ListPlaylists,
+    ListPlaylistTracks {
+        playlist_id: i64,
+    },
+    CreatePlaylist {
+        name: String,
+    },
+    RenamePlaylist {
+        playlist_id: i64,
+        name: String,
+    },
+    DeletePlaylist {
+        playlist_id: i64,
+    },
+    DuplicatePlaylist {
+        playlist_id: i64,
+        name: String,
+    },
+    AddToPlaylist {
+        playlist_id: i64,
+        uris: Vec<String>,
+    },
+    RemoveFromPlaylist {
+        playlist_id: i64,
+        index: usize,
+    },
+    MoveInPlaylist {
+        playlist_id: i64,
+        from: usize,
+        to: usize,
+    },
+    /// The player events are sent on the connection after the response.
+    Subscribe,
+}
+
+/// One line per response or event.
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Response {
+    Ok(Value),
+    Error(AppError),
+    Event(Message),
+}
+
+impl From<Result<Value, AppError>> for Response {
+    fn from(value: Result<Value, AppError>) -> Self {
+        match value {
+            Ok(value) => Response::Ok(value),
+            Err(err) => Response::Error(err),
+        }
+    }
+}
+
+/// The subscription is handled by the transport, it is only acknowledged here.
+pub fn execute(state: &State, request: Request) -> Result<Value, AppError> {
+    let player_front = state.player_front();
+    let library_front = state.library_front();
+    let stations_front = state.stations_front();
+
+    match request {
+        Request::Play { uri } => to_value(player_front.play(&uri)?),
+        Request::Pause => to_value(player_front.pause()?),
+        Request::Stop => to_value(player_front.stop()?),
+        Request::Seek { position_ns } => to_value(player_front.seek(position_ns)?),
+        Request::Next => to_value(player_front.next()?),
+        Request::Previous => to_value(player_front.previous()?),
+        Request::JumpTo { index } => to_value(player_front.jump_to(index)?),
+        Request::Enqueue { uris } => to_value(player_front.enqueue(uris)?),
+        Request::RemoveFromQueue { index } => to_value(player_front.remove_from_queue(index)?),
+        Request::MoveInQueue { from, to } => to_value(player_front.move_in_queue(from, to)?),
+        Request::ClearQueue => to_value(player_front.clear_queue()?),
+        Request::SetGapless { gapless } => {
+            player_front.set_gapless(gapless);
+            Ok(Value::Null)
+        }
+        Request::SetRepeat { repeat } => to_value(player_front.set_repeat(repeat)?),
+        Request::SetShuffle { shuffle, seed } => to_value(player_front.set_shuffle(shuffle, seed)?),
+        Request::SetVolume { volume } => {
+            to_value(send_volume_changed(state, player_front.set_volume(volume)?))
+        }
+        Request::SetMute { muted } => {
+            to_value(send_volume_changed(state, player_front.set_mute(muted)?))
+        }
+        Request::GetVolume => to_value(player_front.volume()?),
+        Request::GetStatus => to_value(player_front.status()?),
+        Request::AddLibraryFolder { folder } => to_value(library_front.add_library_folder(folder)?),
+        Request::RescanLibrary => to_value(library_front.rescan_library()?),
+        Request::ListLibraryTracks => to_value(library_front.tracks()?),
+        Request::ListAlbums => to_value(library_front.albums()?),
+        Request::ListArtists => to_value(library_front.artists()?),
+        Request::ListTracksByAlbum { album_id } => {
+            to_value(library_front.tracks_by_album(album_id)?)
+        }
+        Request::ListTracksByArtist { artist_id } => {
+            to_value(library_front.tracks_by_artist(artist_id)?)
+        }
+        Request::Search {
+            query,
+            offset,
+            limit,
+        } => to_value(library_front.search(&query, offset, limit)?),
+        Request::ListStations => to_value(stations_front.stations()?),
+        Request::AddStation { station } => to_value(stations_front.add_station(station)?),
+        Request::RemoveStation { name } => to_value(stations_front.remove_station(&name)?),
+        Request::PlayStation { name } => {
+            to_value(player_front.play(&stations_front.station(&name)?.url)?)
+        }
+        Request::ImportStations { path } => to_value(stations_front.import_stations(&path)?),
+        Request::ExportStations { path } => to_value(stations_front.export_stations(&path)?),
+        Request::LoadPlaylist { path } => to_value(load_playlist(state, &path)?),
+        Request::SaveQueueAsPlaylist { path } => to_value(save_queue_as_playlist(state, &path)?),
+        Request::ListPlaylists => to_value(library_front.playlists()?),
+        Request::ListPlaylistTracks { playlist_id } => {
+            to_value(library_front.playlist_tracks(playlist_id)?)
+        }
+        Request::CreatePlaylist { name } => to_value(library_front.create_playlist(&name)?),
+        Request::RenamePlaylist { playlist_id, name } => {
+            to_value(library_front.rename_playlist(playlist_id, &name)?)
+        }
+        Request::DeletePlaylist { playlist_id } => {
+            to_value(library_front.delete_playlist(playlist_id)?)
+        }
+        Request::DuplicatePlaylist { playlist_id, name } => {
+            to_value(library_front.duplicate_playlist(playlist_id, &name)?)
+        }
+        Request::AddToPlaylist { playlist_id, uris } => {
+            to_value(library_front.add_to_playlist(playlist_id, uris)?)
+        }
+        Request::RemoveFromPlaylist { playlist_id, index } => {
+            to_value(library_front.remove_from_playlist(playlist_id, index)?)
+        }
+        Request::MoveInPlaylist {
+            playlist_id,
+            from,
+            to,
+        } => to_value(library_front.move_in_playlist(playlist_id, from, to)?),
+        Request::Subscribe => Ok(Value::Null),
+    }
+}
+
+/// Replaces the queue and plays from the first URI.
+pub fn play_uris(uris: Vec<String>) -> Vec<Request> {
+    vec![
+        Request::ClearQueue,
+        Request::Enqueue { uris },
+        Request::JumpTo { index: 0 },
+    ]
+}
+
+/// Appends the playlist entries to the queue, returns their number.
+pub fn load_playlist(state: &State, path: &Path) -> Result<usize, AppError> {
+    let uris = playlist::file::load(path)?
+        .into_iter()
+        .map(|entry| entry.uri)
+        .collect::<Vec<_>>();
+    let count = uris.len();
+
+    state.player_front().enqueue(uris)?;
+
+    Ok(count)
+}
+
+/// The titles, the artists and the durations are from the library when known.
+pub fn save_queue_as_playlist(state: &State, path: &Path) -> Result<(), AppError> {
+    let tracks = state
+        .library_front()
+        .tracks()?
+        .into_iter()
+        .map(|track| (track.uri.clone(), track))
+        .collect::<HashMap<_, _>>();
+
+    let entries = state
+        .player_front()
+        .queue()?
+        .iter()
+        .map(|uri| tracks.get(uri).map_or_else(|| Entry::new(uri), Entry::from))
+        .collect::<Vec<_>>();
+
+    playlist::file::save(path, &entries)
+}
+
+/// Every frontend is informed, not only the one which has changed the volume.
+pub fn send_volume_changed(state: &State, volume: Volume) -> Volume {
+    state.frontend_pipe().send(Message::VolumeChanged {
+        volume: volume.volume,
+        muted: volume.muted,
+    });
+
+    volume
+}
+
+fn to_value<T: serde::Serialize>(value: T) -> Result<Value, AppError> {
+    Ok(serde_json::to_value(value)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::local::app_error::AppError;
+
+    use super::{Request, Response};
+
+    #[test]
+    fn test_deserialize() {
+        let request: Request = serde_json::from_str(r#"{"command":"pause"}"#).unwrap();
+        assert_eq!(request, Request::Pause);
+
+        let request: Request =
+            serde_json::from_str(r#"{"command":"move_in_queue","from":1,"to":0}"#).unwrap();
+        assert_eq!(request, Request::MoveInQueue { from: 1, to: 0 });
+    }
+
+    #[test]
+    fn test_deserialize_err() {
+        assert!(serde_json::from_str::<Request>(r#"{"command":"rewind"}"#).is_err());
+        assert!(serde_json::from_str::<Request>(r#"{"command":"play"}"#).is_err());
+    }
+
+    #[test]
+    fn test_serialize_response() {
+        assert_eq!(
+            serde_json::to_value(Response::Ok(json!(null))).unwrap(),
+            json!({ "ok": null })
+        );
+        assert_eq!(
+            serde_json::to_value(Response::Error(AppError::LockTimeout)).unwrap()["error"]["kind"],
+            "LockTimeout"
+        );
+    }
+}
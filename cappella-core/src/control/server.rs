@@ -0,0 +1,130 @@
+use std::{
+    env, fs,
+    io::{BufRead, BufReader},
+    os::unix::{
+        fs::PermissionsExt,
+        net::{UnixListener, UnixStream},
+    },
+    path::{Path, PathBuf},
+    sync::Arc,
+    thread,
+    time::Duration,
+};
+
+use parking_lot::Mutex;
+use serde_json::Value;
+
+use crate::local::{app_error::AppError, state::State};
+
+use super::{
+    request::{self, Request, Response},
+    subscribers::{self, Subscribers, Writer},
+};
+
+const THREAD_NAME: &str = "control";
+const SOCKET_FILE_NAME: &str = "cappella.sock";
+/// Only the user can connect to the socket.
+const SOCKET_MODE: u32 = 0o600;
+/// A connection which stops reading is closed after it.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// In the runtime directory of the user when there is one, else in the temporary directory.
+pub fn default_socket_path() -> PathBuf {
+    match env::var_os("XDG_RUNTIME_DIR") {
+        Some(runtime_dir) => PathBuf::from(runtime_dir).join(SOCKET_FILE_NAME),
+        None => env::temp_dir().join(format!(
+            "cappella-{}.sock",
+            env::var("USER").unwrap_or_default()
+        )),
+    }
+}
+
+/// Listens in background, one line-delimited JSON request per line.
+pub fn start(
+    socket_path: &Path,
+    state: State,
+    subscribers: Arc<Subscribers>,
+) -> Result<(), AppError> {
+    if socket_path.exists() {
+        if UnixStream::connect(socket_path).is_ok() {
+            return Err(AppError::InvalidState(format!(
+                "Another instance listens on `{}`.",
+                socket_path.display()
+            )));
+        }
+        // Left by an instance which has not ended properly.
+        fs::remove_file(socket_path)?;
+    }
+
+    let listener = UnixListener::bind(socket_path)?;
+    fs::set_permissions(socket_path, fs::Permissions::from_mode(SOCKET_MODE))?;
+
+    thread::Builder::new()
+        .name(THREAD_NAME.to_owned())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                let state = state.clone();
+                let subscribers = subscribers.clone();
+
+                let result = stream.map_err(AppError::from).and_then(|stream| {
+                    thread::Builder::new()
+                        .name(THREAD_NAME.to_owned())
+                        .spawn(move || {
+                            if let Err(err) = handle_connection(stream, &state, &subscribers) {
+                                eprintln!("Control connection error: {err}");
+                            }
+                        })
+                        .map_err(AppError::from)
+                });
+
+                if let Err(err) = result {
+                    eprintln!("Control connection error: {err}");
+                }
+            }
+        })?;
+
+    Ok(())
+}
+
+pub fn remove(socket_path: &Path) {
+    if let Err(err) = fs::remove_file(socket_path) {
+        eprintln!("Unable to remove the control socket: {err}");
+    }
+}
+
+fn handle_connection(
+    stream: UnixStream,
+    state: &State,
+    subscribers: &Subscribers,
+) -> Result<(), AppError> {
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    let reader = BufReader::new(stream.try_clone()?);
+    let writer: Writer = Arc::new(Mutex::new(stream));
+
+    for line in reader.lines() {
+        let line = line?;
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match serde_json::from_str::<Request>(&line) {
+            // Acknowledged before the first event.
+            Ok(Request::Subscribe) => {
+                write_response(&writer, Response::Ok(Value::Null))?;
+                subscribers.add(writer.clone())?;
+            }
+            Ok(request) => write_response(&writer, request::execute(state, request).into())?,
+            Err(err) => write_response(
+                &writer,
+                Response::Error(AppError::InvalidInput(format!("Invalid request: {err}"))),
+            )?,
+        }
+    }
+
+    Ok(())
+}
+
+fn write_response(writer: &Writer, response: Response) -> Result<(), AppError> {
+    subscribers::write_line(writer, &serde_json::to_string(&response)?)
+}
@@ -0,0 +1,189 @@
+use std::{
+    fmt::Debug,
+    io::Write,
+    sync::{mpsc, Arc},
+    thread,
+};
+
+use parking_lot::Mutex;
+
+use crate::{
+    frontend::{message::Message, pipe::Pipe},
+    local::{app_error::AppError, mutex_lock_timeout::MutexLockTimeout},
+};
+
+use super::request::Response;
+
+pub type Writer = Arc<Mutex<dyn Write + Send>>;
+
+const THREAD_NAME: &str = "subscriber";
+/// The events waiting for a slow connection, it is dropped when they are more.
+const QUEUE_LEN: usize = 256;
+
+/// Forwards the events to the frontend and to the subscribed connections.
+pub struct Subscribers {
+    frontend_pipe: Arc<dyn Pipe>,
+    senders: Mutex<Vec<mpsc::SyncSender<String>>>,
+}
+
+pub fn new_arc(frontend_pipe: Arc<dyn Pipe>) -> Arc<Subscribers> {
+    Arc::new(Subscribers {
+        frontend_pipe,
+        senders: Mutex::default(),
+    })
+}
+
+impl Subscribers {
+    /// Each connection is written by its own thread, a connection which stops reading does not
+    /// block the sender of the events.
+    pub fn add(&self, writer: Writer) -> Result<(), AppError> {
+        let (sender, receiver) = mpsc::sync_channel::<String>(QUEUE_LEN);
+
+        thread::Builder::new()
+            .name(THREAD_NAME.to_owned())
+            .spawn(move || {
+                for line in receiver {
+                    if write_line(&writer, &line).is_err() {
+                        break;
+                    }
+                }
+            })?;
+
+        self.senders.try_lock_default_duration()?.push(sender);
+
+        Ok(())
+    }
+}
+
+impl Debug for Subscribers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Subscribers")
+            .field("frontend_pipe", &self.frontend_pipe)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Pipe for Subscribers {
+    fn send(&self, frontend_message: Message) {
+        self.frontend_pipe.send(frontend_message.clone());
+
+        let line = match serde_json::to_string(&Response::Event(frontend_message)) {
+            Ok(line) => line,
+            Err(err) => {
+                eprintln!("Unable to serialize the event for the subscribers: {err}");
+                return;
+            }
+        };
+
+        match self.senders.try_lock_default_duration() {
+            // A closed or too slow connection is removed, its thread ends with the queue.
+            Ok(mut senders) => senders.retain(|sender| sender.try_send(line.clone()).is_ok()),
+            Err(err) => eprintln!("Unable to send the event to the subscribers: {err}"),
+        }
+    }
+}
+
+pub fn write_line(writer: &Writer, line: &str) -> Result<(), AppError> {
+    let mut writer = writer.try_lock_default_duration()?;
+    writeln!(writer, "{line}")?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{self, Write},
+        sync::{mpsc, Arc},
+        thread,
+        time::Duration,
+    };
+
+    use parking_lot::Mutex;
+
+    use crate::frontend::{
+        message::{Message, PlayerState},
+        pipe::Pipe,
+    };
+
+    use super::{new_arc, QUEUE_LEN};
+
+    #[derive(Debug, Default)]
+    struct MockPipe {
+        messages: Mutex<Vec<Message>>,
+    }
+
+    impl Pipe for MockPipe {
+        fn send(&self, frontend_message: Message) {
+            self.messages.lock().push(frontend_message);
+        }
+    }
+
+    struct ClosedWriter;
+
+    impl Write for ClosedWriter {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::BrokenPipe.into())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Blocked until the sender is dropped.
+    struct BlockedWriter(mpsc::Receiver<()>);
+
+    impl Write for BlockedWriter {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            self.0.recv().map_err(|_| io::ErrorKind::BrokenPipe)?;
+            Ok(0)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_send() {
+        let frontend_pipe = Arc::<MockPipe>::default();
+        let subscribers = new_arc(frontend_pipe.clone());
+        let buffer = Arc::new(Mutex::new(Vec::<u8>::new()));
+        subscribers.add(buffer.clone()).unwrap();
+        subscribers.add(Arc::new(Mutex::new(ClosedWriter))).unwrap();
+
+        subscribers.send(Message::StateChanged(PlayerState::Playing));
+
+        // The closed connection is removed on the event after its failed write.
+        while subscribers.senders.lock().len() > 1 || buffer.lock().is_empty() {
+            thread::sleep(Duration::from_millis(10));
+            subscribers.send(Message::None);
+        }
+
+        assert!(matches!(
+            frontend_pipe.messages.lock()[0],
+            Message::StateChanged(PlayerState::Playing)
+        ));
+        assert!(String::from_utf8(buffer.lock().clone())
+            .unwrap()
+            .starts_with("{\"event\":{\"StateChanged\":\"Playing\"}}\n"));
+    }
+
+    #[test]
+    fn test_send_queue_full() {
+        let subscribers = new_arc(Arc::<MockPipe>::default());
+        let (sender, receiver) = mpsc::channel();
+        subscribers
+            .add(Arc::new(Mutex::new(BlockedWriter(receiver))))
+            .unwrap();
+
+        for _ in 0..=QUEUE_LEN + 1 {
+            subscribers.send(Message::None);
+        }
+
+        assert!(subscribers.senders.lock().is_empty());
+        drop(sender);
+    }
+}
@@ -0,0 +1,87 @@
+use crate::{
+    local::app_error::AppError,
+    streamer::{now_playing::NowPlaying, sys::gst_error::GstError},
+};
+
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum PlayerState {
+    #[default]
+    Stopped,
+    Paused,
+    Playing,
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+pub enum Message {
+    #[default]
+    None,
+    Position {
+        position_ns: i64,
+        duration_ns: i64,
+    },
+    StateChanged(PlayerState),
+    TrackStarted(String),
+    TrackEnded(String),
+    NowPlaying(NowPlaying),
+    VolumeChanged {
+        volume: f64,
+        muted: bool,
+    },
+    /// The fill level of a stream buffer, the playback waits until 100%.
+    Buffering {
+        percent: i32,
+    },
+    /// The network stream is lost, it is opened again after the delay.
+    Reconnecting {
+        uri: String,
+        attempt: u32,
+        delay_ms: u64,
+    },
+    LibraryScanProgress {
+        scanned: usize,
+        total: usize,
+    },
+    LibraryScanFinished {
+        total: usize,
+    },
+    /// The queue is changed by any client, its views are refreshed.
+    QueueChanged,
+    /// A saved playlist is created, changed or deleted, its open views are refreshed.
+    PlaylistChanged {
+        playlist_id: i64,
+    },
+    Error(ErrorReport),
+    Warning(ErrorReport),
+    /// A plugin is needed to play the URI, the description names it for the user.
+    MissingPlugin {
+        uri: String,
+        description: String,
+    },
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+pub struct ErrorReport {
+    pub message: String,
+    pub uri: Option<String>,
+    pub gst_error: Option<GstError>,
+}
+
+impl ErrorReport {
+    pub fn from_gst_error(gst_error: GstError, uri: &str) -> Self {
+        Self {
+            message: gst_error.to_string(),
+            uri: Some(uri.to_owned()),
+            gst_error: Some(gst_error),
+        }
+    }
+}
+
+impl From<&AppError> for ErrorReport {
+    fn from(value: &AppError) -> Self {
+        Self {
+            message: value.to_string(),
+            uri: None,
+            gst_error: value.gst_error().cloned(),
+        }
+    }
+}
@@ -0,0 +1,8 @@
+use std::fmt::Debug;
+
+use super::message::Message;
+
+/// The event sink of the player, implemented by each user interface.
+pub trait Pipe: Debug + Send + Sync {
+    fn send(&self, frontend_message: Message);
+}
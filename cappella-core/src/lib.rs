@@ -0,0 +1,67 @@
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+pub mod control;
+pub mod frontend;
+pub mod library;
+pub mod local;
+#[cfg(feature = "mpd")]
+pub mod mpd;
+#[cfg(target_os = "linux")]
+pub mod mpris;
+pub mod player;
+pub mod playlist;
+#[cfg(feature = "remote")]
+pub mod remote;
+pub mod stations;
+pub mod streamer;
+
+const POSITION_EVENT_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Builds the player and the library, the events are sent to the frontend pipe.
+pub fn init(
+    config_dir: PathBuf,
+    data_dir: PathBuf,
+    frontend_pipe: Arc<dyn frontend::pipe::Pipe>,
+) -> local::state::State {
+    // Step 1 in alphabetical order
+    let library_store = library::store::new_arc(data_dir.join(library::store::LIBRARY_FILE_NAME));
+    let local_settings =
+        local::settings::new_arc(config_dir.join(local::settings::SETTINGS_FILE_NAME));
+    let player_queue = player::queue::new_arc();
+    let stations_store =
+        stations::store::new_arc(config_dir.join(stations::store::STATIONS_FILE_NAME));
+    let streamer_bus = streamer::bus::new_arc();
+    let streamer_status = streamer::status::new_arc();
+
+    // Step 2 in alphabetical order
+    let library_front = library::front::new_box(library_store, frontend_pipe.clone());
+    let stations_front = stations::front::new_box(stations_store);
+    let streamer_front = streamer::front::new_box(
+        streamer_bus.clone(),
+        frontend_pipe.clone(),
+        player_queue.clone(),
+        local_settings.clone(),
+        streamer_status.clone(),
+        POSITION_EVENT_INTERVAL,
+    );
+    let streamer_pipe = streamer::pipe::new_box(streamer_bus.clone());
+
+    // Step 3 in alphabetical order
+    let player_front = player::front::new_box(
+        streamer_front,
+        streamer_pipe,
+        streamer_status,
+        player_queue,
+        local_settings.clone(),
+        frontend_pipe.clone(),
+    );
+
+    // Step 4 return
+    local::state::State::new(
+        frontend_pipe,
+        library_front,
+        player_front,
+        local_settings,
+        stations_front,
+    )
+}
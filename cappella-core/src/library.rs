@@ -0,0 +1,5 @@
+pub mod front;
+pub mod scanner;
+pub mod search;
+pub mod store;
+pub mod track;
@@ -0,0 +1,348 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    path::{Path, PathBuf},
+    sync::{mpsc, Arc},
+    thread::{self, JoinHandle},
+};
+
+use parking_lot::Mutex;
+
+use crate::{
+    frontend::{
+        message::{ErrorReport, Message},
+        pipe::Pipe,
+    },
+    local::{app_error::AppError, mutex_lock_timeout::MutexLockTimeout},
+    playlist::cue::{self, CueTrack},
+    streamer::sys::discoverer::Discoverer,
+};
+
+use super::{
+    scanner::{self, DISCOVERER_TIMEOUT},
+    search::SearchPage,
+    store::Store,
+    track::{Album, Artist, Folder, Playlist, Track},
+};
+
+const THREAD_NAME: &str = "library";
+
+pub trait Front: Debug + Send + Sync {
+    fn add_library_folder(&self, folder: PathBuf) -> Result<(), AppError>;
+    fn rescan_library(&self) -> Result<(), AppError>;
+    fn library_folders(&self) -> Result<Vec<Folder>, AppError>;
+    fn tracks(&self) -> Result<Vec<Track>, AppError>;
+    fn albums(&self) -> Result<Vec<Album>, AppError>;
+    fn artists(&self) -> Result<Vec<Artist>, AppError>;
+    fn tracks_by_album(&self, album_id: i64) -> Result<Vec<Track>, AppError>;
+    fn tracks_by_artist(&self, artist_id: i64) -> Result<Vec<Track>, AppError>;
+    fn search(&self, query: &str, offset: usize, limit: usize) -> Result<SearchPage, AppError>;
+    fn playlists(&self) -> Result<Vec<Playlist>, AppError>;
+    fn playlist_tracks(&self, playlist_id: i64) -> Result<Vec<Track>, AppError>;
+    fn create_playlist(&self, name: &str) -> Result<Playlist, AppError>;
+    fn rename_playlist(&self, playlist_id: i64, name: &str) -> Result<(), AppError>;
+    fn delete_playlist(&self, playlist_id: i64) -> Result<(), AppError>;
+    fn duplicate_playlist(&self, playlist_id: i64, name: &str) -> Result<Playlist, AppError>;
+    fn add_to_playlist(&self, playlist_id: i64, uris: Vec<String>) -> Result<(), AppError>;
+    fn remove_from_playlist(&self, playlist_id: i64, index: usize) -> Result<(), AppError>;
+    fn move_in_playlist(&self, playlist_id: i64, from: usize, to: usize) -> Result<(), AppError>;
+    fn wait_until_end(&self);
+}
+
+pub fn new_box(store: Arc<dyn Store>, frontend_pipe: Arc<dyn Pipe>) -> Box<dyn Front> {
+    let (sender, receiver) = mpsc::channel::<()>();
+    let worker_store = store.clone();
+    let worker_frontend_pipe = frontend_pipe.clone();
+
+    let join_handle = thread::Builder::new()
+        .name(THREAD_NAME.to_owned())
+        .spawn(move || {
+            // One scan at a time, the requests received during a scan are run after.
+            for () in receiver {
+                if let Err(err) = scan(&*worker_store, &*worker_frontend_pipe) {
+                    eprintln!("Library scan error: {err}");
+                    worker_frontend_pipe.send(Message::Error(ErrorReport::from(&err)));
+                }
+            }
+        })
+        .map_err(|err| eprintln!("Unable to start the library thread: {err}"))
+        .ok();
+
+    Box::new(Front_ {
+        store,
+        frontend_pipe,
+        sender: Mutex::new(Some(sender)),
+        join_handle: Mutex::new(join_handle),
+    })
+}
+
+#[derive(Debug)]
+struct Front_ {
+    store: Arc<dyn Store>,
+    frontend_pipe: Arc<dyn Pipe>,
+    sender: Mutex<Option<mpsc::Sender<()>>>,
+    join_handle: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl Front for Front_ {
+    fn add_library_folder(&self, folder: PathBuf) -> Result<(), AppError> {
+        self.add_folder(folder)?;
+
+        self.rescan_library()
+    }
+
+    /// The scan runs in the background, its progress and errors are sent to the frontend.
+    fn rescan_library(&self) -> Result<(), AppError> {
+        match &*self.sender.try_lock_default_duration()? {
+            Some(sender) => sender.send(()).map_err(|err| {
+                AppError::Channel(format!("Unable to send to the library thread: {err}"))
+            }),
+            None => Err(AppError::InvalidState(
+                "The library thread is ended.".to_owned(),
+            )),
+        }
+    }
+
+    fn library_folders(&self) -> Result<Vec<Folder>, AppError> {
+        self.store.folders()
+    }
+
+    fn tracks(&self) -> Result<Vec<Track>, AppError> {
+        self.store.tracks()
+    }
+
+    fn albums(&self) -> Result<Vec<Album>, AppError> {
+        self.store.albums()
+    }
+
+    fn artists(&self) -> Result<Vec<Artist>, AppError> {
+        self.store.artists()
+    }
+
+    fn tracks_by_album(&self, album_id: i64) -> Result<Vec<Track>, AppError> {
+        self.store.tracks_by_album(album_id)
+    }
+
+    fn tracks_by_artist(&self, artist_id: i64) -> Result<Vec<Track>, AppError> {
+        self.store.tracks_by_artist(artist_id)
+    }
+
+    fn search(&self, query: &str, offset: usize, limit: usize) -> Result<SearchPage, AppError> {
+        self.store.search(query, offset, limit)
+    }
+
+    fn playlists(&self) -> Result<Vec<Playlist>, AppError> {
+        self.store.playlists()
+    }
+
+    fn playlist_tracks(&self, playlist_id: i64) -> Result<Vec<Track>, AppError> {
+        self.store.playlist_tracks(playlist_id)
+    }
+
+    fn create_playlist(&self, name: &str) -> Result<Playlist, AppError> {
+        let playlist = self.store.create_playlist(name)?;
+        self.send_playlist_changed(playlist.id);
+
+        Ok(playlist)
+    }
+
+    fn rename_playlist(&self, playlist_id: i64, name: &str) -> Result<(), AppError> {
+        self.store.rename_playlist(playlist_id, name)?;
+        self.send_playlist_changed(playlist_id);
+
+        Ok(())
+    }
+
+    fn delete_playlist(&self, playlist_id: i64) -> Result<(), AppError> {
+        self.store.delete_playlist(playlist_id)?;
+        self.send_playlist_changed(playlist_id);
+
+        Ok(())
+    }
+
+    fn duplicate_playlist(&self, playlist_id: i64, name: &str) -> Result<Playlist, AppError> {
+        let playlist = self.store.duplicate_playlist(playlist_id, name)?;
+        self.send_playlist_changed(playlist.id);
+
+        Ok(playlist)
+    }
+
+    fn add_to_playlist(&self, playlist_id: i64, uris: Vec<String>) -> Result<(), AppError> {
+        self.store.add_to_playlist(playlist_id, &uris)?;
+        self.send_playlist_changed(playlist_id);
+
+        Ok(())
+    }
+
+    fn remove_from_playlist(&self, playlist_id: i64, index: usize) -> Result<(), AppError> {
+        self.store.remove_from_playlist(playlist_id, index)?;
+        self.send_playlist_changed(playlist_id);
+
+        Ok(())
+    }
+
+    fn move_in_playlist(&self, playlist_id: i64, from: usize, to: usize) -> Result<(), AppError> {
+        self.store.move_in_playlist(playlist_id, from, to)?;
+        self.send_playlist_changed(playlist_id);
+
+        Ok(())
+    }
+
+    fn wait_until_end(&self) {
+        match self.sender.try_lock_default_duration() {
+            Ok(mut sender_lock) => drop(sender_lock.take()),
+            Err(err) => eprintln!("{err}"),
+        }
+
+        match self.join_handle.try_lock_default_duration() {
+            Ok(mut join_handle_lock) => {
+                if let Some(join_handle) = join_handle_lock.take() {
+                    if let Err(err) = join_handle.join() {
+                        eprintln!("Error on library thread join handle: {err:?}.");
+                    }
+                }
+            }
+            Err(err) => eprintln!("{err}"),
+        }
+    }
+}
+
+impl Front_ {
+    fn add_folder(&self, folder: PathBuf) -> Result<(), AppError> {
+        let folder = folder.canonicalize()?;
+
+        if !folder.is_dir() {
+            return Err(AppError::InvalidPath(format!(
+                "The library folder `{}` is not a directory.",
+                folder.display()
+            )));
+        }
+
+        self.store.add_folder(&folder)
+    }
+
+    /// Every frontend is informed, not only the one which has changed the playlist.
+    fn send_playlist_changed(&self, playlist_id: i64) {
+        self.frontend_pipe
+            .send(Message::PlaylistChanged { playlist_id });
+    }
+}
+
+/// The tracks of a folder which cannot be read are kept, as on an unmounted drive.
+fn scan(store: &dyn Store, frontend_pipe: &dyn Pipe) -> Result<(), AppError> {
+    let folder_paths = store
+        .folders()?
+        .into_iter()
+        .filter_map(|folder| match scanner::walk(&folder.path) {
+            Ok(paths) => Some((folder.id, paths)),
+            Err(err) => {
+                eprintln!("Library folder skipped: {err}");
+                frontend_pipe.send(Message::Warning(ErrorReport::from(&err)));
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+    let total = folder_paths.iter().map(|(_, paths)| paths.len()).sum();
+    let discoverer = Discoverer::new(DISCOVERER_TIMEOUT)?;
+    let mut scanned = 0;
+
+    frontend_pipe.send(Message::LibraryScanProgress { scanned, total });
+
+    let sheets = folder_paths
+        .iter()
+        .flat_map(|(_, paths)| paths)
+        .filter(|path| cue::is_cue_file(path))
+        .filter_map(|path| match cue::load(path) {
+            Ok(cue_tracks) => Some((path.clone(), cue_tracks)),
+            Err(err) => {
+                eprintln!("Unable to read the CUE sheet `{}`: {err}", path.display());
+                None
+            }
+        })
+        .collect::<HashMap<_, _>>();
+    // The files split by a sheet are listed as its tracks only.
+    let sheet_files = sheets
+        .values()
+        .flatten()
+        .map(|cue_track| cue_track.file.clone())
+        .collect::<HashSet<_>>();
+
+    for (folder_id, folder_paths) in &folder_paths {
+        let mut paths = Vec::new();
+
+        for path in folder_paths {
+            if let Some(cue_tracks) = sheets.get(path) {
+                if let Err(err) = scan_sheet(store, &discoverer, *folder_id, path, cue_tracks) {
+                    eprintln!("Unable to read `{}`: {err}", path.display());
+                }
+                paths.extend(
+                    cue_tracks
+                        .iter()
+                        .map(|cue_track| cue::track_path(path, cue_track.number)),
+                );
+            } else if !cue::is_cue_file(path) && !sheet_files.contains(path) {
+                if let Err(err) = scan_file(store, &discoverer, *folder_id, path) {
+                    eprintln!("Unable to read `{}`: {err}", path.display());
+                }
+                paths.push(path.clone());
+            }
+
+            scanned += 1;
+            frontend_pipe.send(Message::LibraryScanProgress { scanned, total });
+        }
+
+        store.retain(*folder_id, &paths)?;
+    }
+
+    frontend_pipe.send(Message::LibraryScanFinished { total });
+
+    Ok(())
+}
+
+/// Probes the file only if it is new or changed since the last scan.
+fn scan_file(
+    store: &dyn Store,
+    discoverer: &Discoverer,
+    folder_id: i64,
+    path: &Path,
+) -> Result<(), AppError> {
+    let stamp = scanner::stamp(path)?;
+
+    if store.stamp(path)? == Some(stamp) {
+        return Ok(());
+    }
+
+    let track = scanner::read_track(discoverer, path)?;
+
+    store.upsert(folder_id, &track, stamp)
+}
+
+/// Probes the files of the sheet only if the sheet or one of its files is new or changed since
+/// the last scan.
+fn scan_sheet(
+    store: &dyn Store,
+    discoverer: &Discoverer,
+    folder_id: i64,
+    sheet: &Path,
+    cue_tracks: &[CueTrack],
+) -> Result<(), AppError> {
+    let stamp = scanner::sheet_stamp(sheet, cue_tracks)?;
+    let mut is_unchanged = true;
+
+    for cue_track in cue_tracks {
+        if store.stamp(&cue::track_path(sheet, cue_track.number))? != Some(stamp) {
+            is_unchanged = false;
+            break;
+        }
+    }
+
+    if is_unchanged {
+        return Ok(());
+    }
+
+    for track in scanner::read_cue_tracks(discoverer, sheet, cue_tracks)? {
+        store.upsert(folder_id, &track, stamp)?;
+    }
+
+    Ok(())
+}
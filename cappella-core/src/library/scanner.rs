@@ -0,0 +1,225 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, UNIX_EPOCH},
+};
+
+use crate::{
+    local::{app_error::AppError, uri},
+    playlist::cue::{self, CueTrack},
+    streamer::sys::{
+        discoverer::Discoverer,
+        taglist::{
+            TAG_ALBUM, TAG_ALBUM_ARTIST, TAG_ARTIST, TAG_DISC_NUMBER, TAG_GENRE, TAG_TITLE,
+            TAG_TRACK_NUMBER,
+        },
+    },
+};
+
+use super::track::{FileStamp, Track};
+
+pub const DISCOVERER_TIMEOUT: Duration = Duration::from_secs(10);
+
+const AUDIO_EXTENSIONS: [&str; 12] = [
+    "aac", "aiff", "ape", "flac", "m4a", "mp3", "oga", "ogg", "opus", "wav", "wma", "wv",
+];
+
+pub fn is_audio_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| AUDIO_EXTENSIONS.contains(&extension.to_lowercase().as_str()))
+}
+
+/// Every audio file and CUE sheet under the folder, sorted. An error if a directory cannot be
+/// read, as an unmounted folder, its tracks are then not known to be missing.
+pub fn walk(folder: &Path) -> Result<Vec<PathBuf>, AppError> {
+    let mut paths = Vec::new();
+    let mut directories = vec![folder.to_owned()];
+
+    while let Some(directory) = directories.pop() {
+        let entries = fs::read_dir(&directory).map_err(|err| {
+            AppError::InvalidPath(format!(
+                "Unable to read the directory `{}`: {err}",
+                directory.display()
+            ))
+        })?;
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            match entry.file_type() {
+                Ok(file_type) if file_type.is_dir() => directories.push(path),
+                Ok(_) if is_audio_file(&path) || cue::is_cue_file(&path) => paths.push(path),
+                _ => {}
+            }
+        }
+    }
+
+    paths.sort();
+
+    Ok(paths)
+}
+
+pub fn stamp(path: &Path) -> Result<FileStamp, AppError> {
+    let metadata = fs::metadata(path)?;
+    let modified = metadata.modified()?.duration_since(UNIX_EPOCH)?;
+
+    Ok(FileStamp {
+        modified_ns: i64::try_from(modified.as_nanos())?,
+        size: i64::try_from(metadata.len())?,
+    })
+}
+
+/// Changes with the sheet and with each of its audio files: the latest modification and the
+/// total size.
+pub fn sheet_stamp(sheet: &Path, cue_tracks: &[CueTrack]) -> Result<FileStamp, AppError> {
+    let mut files = cue_tracks
+        .iter()
+        .map(|cue_track| cue_track.file.as_path())
+        .collect::<Vec<_>>();
+    files.dedup();
+
+    files
+        .into_iter()
+        .try_fold(stamp(sheet)?, |sheet_stamp, file| {
+            let file_stamp = stamp(file)?;
+
+            Ok(FileStamp {
+                modified_ns: sheet_stamp.modified_ns.max(file_stamp.modified_ns),
+                size: sheet_stamp.size + file_stamp.size,
+            })
+        })
+}
+
+pub fn read_track(discoverer: &Discoverer, path: &Path) -> Result<Track, AppError> {
+    let uri = uri::from_path(path)?;
+    let info = discoverer.discover_uri(&uri)?;
+    let mut track = Track {
+        uri,
+        path: path.to_owned(),
+        duration_ns: info
+            .duration()
+            .and_then(|duration| i64::try_from(duration.as_nanos()).ok()),
+        ..Track::default()
+    };
+
+    if let Some(tags) = info.tags() {
+        track.title = tags.get_string(TAG_TITLE)?;
+        track.artist = tags.get_string(TAG_ARTIST)?;
+        track.album = tags.get_string(TAG_ALBUM)?;
+        track.album_artist = tags.get_string(TAG_ALBUM_ARTIST)?;
+        track.genre = tags.get_string(TAG_GENRE)?;
+        track.track_number = tags.get_uint(TAG_TRACK_NUMBER)?;
+        track.disc_number = tags.get_uint(TAG_DISC_NUMBER)?;
+    }
+
+    if let Some(audio_stream) = info.audio_stream() {
+        track.codec = audio_stream.codec;
+        track.bitrate = audio_stream.bitrate;
+        track.sample_rate = audio_stream.sample_rate;
+        track.channels = audio_stream.channels;
+    }
+
+    Ok(track)
+}
+
+/// The virtual tracks of a sheet, with the stream values of their file.
+pub fn read_cue_tracks(
+    discoverer: &Discoverer,
+    sheet: &Path,
+    cue_tracks: &[CueTrack],
+) -> Result<Vec<Track>, AppError> {
+    let mut current_file: Option<Track> = None;
+    let mut tracks = Vec::with_capacity(cue_tracks.len());
+
+    // Most sheets have a single file, it is probed once.
+    for cue_track in cue_tracks {
+        let file_track = match &mut current_file {
+            Some(file_track) if file_track.path == cue_track.file => file_track,
+            current_file => current_file.insert(read_track(discoverer, &cue_track.file)?),
+        };
+        let end_ns = cue_track.end_ns.or(file_track.duration_ns);
+
+        tracks.push(Track {
+            uri: cue::track_uri(sheet, cue_track.number)?,
+            path: cue::track_path(sheet, cue_track.number),
+            title: cue_track.title.clone(),
+            artist: cue_track.performer.clone().or(file_track.artist.clone()),
+            album: cue_track.album.clone().or(file_track.album.clone()),
+            album_artist: cue_track
+                .album_performer
+                .clone()
+                .or(file_track.album_artist.clone()),
+            genre: cue_track.genre.clone().or(file_track.genre.clone()),
+            track_number: Some(cue_track.number),
+            duration_ns: end_ns.map(|end_ns| end_ns - cue_track.start_ns),
+            ..file_track.clone()
+        });
+    }
+
+    Ok(tracks)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::Path, process};
+
+    use crate::playlist::cue::CueTrack;
+
+    use super::{is_audio_file, sheet_stamp, walk};
+
+    #[test]
+    fn test_is_audio_file() {
+        assert!(is_audio_file(Path::new("/music/a.flac")));
+        assert!(is_audio_file(Path::new("/music/a.MP3")));
+        assert!(!is_audio_file(Path::new("/music/cover.jpg")));
+        assert!(!is_audio_file(Path::new("/music/flac")));
+    }
+
+    #[test]
+    fn test_walk() {
+        let root = env::temp_dir().join(format!("cappella-scanner-{}", process::id()));
+        fs::create_dir_all(root.join("album")).unwrap();
+        fs::write(root.join("album/02.ogg"), "").unwrap();
+        fs::write(root.join("album/01.ogg"), "").unwrap();
+        fs::write(root.join("album/cover.jpg"), "").unwrap();
+        fs::write(root.join("album/album.cue"), "").unwrap();
+        fs::write(root.join("single.mp3"), "").unwrap();
+
+        let paths = walk(&root).unwrap();
+
+        assert_eq!(
+            paths,
+            vec![
+                root.join("album/01.ogg"),
+                root.join("album/02.ogg"),
+                root.join("album/album.cue"),
+                root.join("single.mp3")
+            ]
+        );
+        assert!(walk(&root.join("missing")).is_err());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_sheet_stamp() {
+        let root = env::temp_dir().join(format!("cappella-scanner-sheet-{}", process::id()));
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("album.cue"), "FILE").unwrap();
+        fs::write(root.join("album.flac"), "audio").unwrap();
+        let cue_tracks = [1, 2].map(|number| CueTrack {
+            number,
+            file: root.join("album.flac"),
+            ..CueTrack::default()
+        });
+
+        let before = sheet_stamp(&root.join("album.cue"), &cue_tracks).unwrap();
+        fs::write(root.join("album.flac"), "retagged audio").unwrap();
+        let after = sheet_stamp(&root.join("album.cue"), &cue_tracks).unwrap();
+
+        assert_eq!(before.size, 9);
+        assert_ne!(before, after);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
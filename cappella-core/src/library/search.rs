@@ -0,0 +1,51 @@
+use super::track::Track;
+
+pub const MAX_PAGE_LIMIT: usize = 500;
+
+/// Column weights for the ranking, in the `tracks_fts` column order.
+pub const BM25_WEIGHTS: &str = "10.0, 5.0, 5.0, 3.0, 1.0, 0.5";
+
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize)]
+pub struct SearchPage {
+    pub tracks: Vec<Track>,
+    pub total: usize,
+    pub offset: usize,
+}
+
+/// Every word of the query must match the start of a word, in any column. `None` if the query
+/// has no word.
+pub fn match_expression(query: &str) -> Option<String> {
+    let terms = query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| format!("\"{word}\"*"))
+        .collect::<Vec<_>>();
+
+    if terms.is_empty() {
+        return None;
+    }
+
+    Some(terms.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::match_expression;
+
+    #[test]
+    fn test_match_expression() {
+        assert_eq!(
+            match_expression("Björk  hyper-ballad").as_deref(),
+            Some("\"Björk\"* \"hyper\"* \"ballad\"*")
+        );
+    }
+
+    #[test]
+    fn test_match_expression_escapes_syntax() {
+        assert_eq!(
+            match_expression("a\" OR b*").as_deref(),
+            Some("\"a\"* \"OR\"* \"b\"*")
+        );
+        assert_eq!(match_expression(" -\"* "), None);
+    }
+}
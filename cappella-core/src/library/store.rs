@@ -0,0 +1,1224 @@
+use std::{
+    collections::HashSet,
+    fmt::Debug,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use parking_lot::Mutex;
+use rusqlite::{params, Connection, OptionalExtension, Params, Row, Transaction};
+
+use crate::local::{app_error::AppError, mutex_lock_timeout::MutexLockTimeout, uri};
+
+use super::{
+    search::{self, SearchPage, BM25_WEIGHTS, MAX_PAGE_LIMIT},
+    track::{Album, Artist, FileStamp, Folder, Playlist, Track},
+};
+
+pub const LIBRARY_FILE_NAME: &str = "library.sqlite3";
+
+/// Applied in order, the index + 1 is the schema version stored in `user_version`.
+const MIGRATIONS: [&str; 3] = [
+    "
+    CREATE TABLE folders (
+        id INTEGER PRIMARY KEY,
+        path TEXT NOT NULL UNIQUE
+    );
+    CREATE TABLE artists (
+        id INTEGER PRIMARY KEY,
+        name TEXT NOT NULL UNIQUE
+    );
+    CREATE TABLE albums (
+        id INTEGER PRIMARY KEY,
+        title TEXT NOT NULL,
+        artist_id INTEGER REFERENCES artists(id)
+    );
+    CREATE INDEX albums_title ON albums(title);
+    CREATE TABLE tracks (
+        id INTEGER PRIMARY KEY,
+        path TEXT NOT NULL UNIQUE,
+        uri TEXT NOT NULL,
+        folder_id INTEGER NOT NULL REFERENCES folders(id) ON DELETE CASCADE,
+        modified_ns INTEGER NOT NULL,
+        size INTEGER NOT NULL,
+        title TEXT,
+        artist_id INTEGER REFERENCES artists(id),
+        album_id INTEGER REFERENCES albums(id),
+        album_artist_id INTEGER REFERENCES artists(id),
+        genre TEXT,
+        track_number INTEGER,
+        disc_number INTEGER,
+        duration_ns INTEGER,
+        codec TEXT,
+        bitrate INTEGER,
+        sample_rate INTEGER,
+        channels INTEGER
+    );
+    CREATE INDEX tracks_album_id ON tracks(album_id);
+    CREATE INDEX tracks_artist_id ON tracks(artist_id);
+",
+    "
+    CREATE VIRTUAL TABLE tracks_fts USING fts5(
+        title, artist, album, album_artist, genre, path,
+        tokenize = 'unicode61 remove_diacritics 2',
+        prefix = '1 2 3'
+    );
+    INSERT INTO tracks_fts (rowid, title, artist, album, album_artist, genre, path)
+    SELECT t.id, t.title, ar.name, al.title, aa.name, t.genre, t.path
+    FROM tracks t
+    LEFT JOIN artists ar ON ar.id = t.artist_id
+    LEFT JOIN albums al ON al.id = t.album_id
+    LEFT JOIN artists aa ON aa.id = t.album_artist_id;
+",
+    "
+    CREATE TABLE playlists (
+        id INTEGER PRIMARY KEY,
+        name TEXT NOT NULL UNIQUE
+    );
+    CREATE TABLE playlist_tracks (
+        id INTEGER PRIMARY KEY,
+        playlist_id INTEGER NOT NULL REFERENCES playlists(id) ON DELETE CASCADE,
+        position INTEGER NOT NULL,
+        uri TEXT NOT NULL,
+        title TEXT,
+        artist TEXT,
+        album TEXT,
+        duration_ns INTEGER
+    );
+    CREATE INDEX playlist_tracks_playlist_id ON playlist_tracks(playlist_id, position);
+",
+];
+
+const TRACK_SELECT: &str = "
+    SELECT t.uri, t.path, t.title, ar.name, al.title, aa.name, t.genre, t.track_number,
+        t.disc_number, t.duration_ns, t.codec, t.bitrate, t.sample_rate, t.channels
+    FROM tracks t
+    LEFT JOIN artists ar ON ar.id = t.artist_id
+    LEFT JOIN albums al ON al.id = t.album_id
+    LEFT JOIN artists aa ON aa.id = t.album_artist_id";
+
+const TRACK_ORDER: &str = "ORDER BY t.disc_number, t.track_number, t.path";
+
+/// A moved file is found again by its tags when its duration is this close.
+const DURATION_TOLERANCE_NS: i64 = 2_000_000_000;
+
+pub trait Store: Debug + Send + Sync {
+    fn add_folder(&self, path: &Path) -> Result<(), AppError>;
+    fn folders(&self) -> Result<Vec<Folder>, AppError>;
+    /// `None` if the file is not in the library.
+    fn stamp(&self, path: &Path) -> Result<Option<FileStamp>, AppError>;
+    fn upsert(&self, folder_id: i64, track: &Track, stamp: FileStamp) -> Result<(), AppError>;
+    /// Removes the tracks of the folder not in `paths` with their orphan albums and artists,
+    /// returns the number of removed tracks.
+    fn retain(&self, folder_id: i64, paths: &[PathBuf]) -> Result<usize, AppError>;
+    fn tracks(&self) -> Result<Vec<Track>, AppError>;
+    fn albums(&self) -> Result<Vec<Album>, AppError>;
+    fn artists(&self) -> Result<Vec<Artist>, AppError>;
+    fn tracks_by_album(&self, album_id: i64) -> Result<Vec<Track>, AppError>;
+    fn tracks_by_artist(&self, artist_id: i64) -> Result<Vec<Track>, AppError>;
+    /// Best matches first, see `search::match_expression`.
+    fn search(&self, query: &str, offset: usize, limit: usize) -> Result<SearchPage, AppError>;
+    fn playlists(&self) -> Result<Vec<Playlist>, AppError>;
+    /// The entries whose file has moved are found again by their tags and updated, the ones
+    /// not found are kept with their saved values.
+    fn playlist_tracks(&self, playlist_id: i64) -> Result<Vec<Track>, AppError>;
+    fn create_playlist(&self, name: &str) -> Result<Playlist, AppError>;
+    fn rename_playlist(&self, playlist_id: i64, name: &str) -> Result<(), AppError>;
+    fn delete_playlist(&self, playlist_id: i64) -> Result<(), AppError>;
+    /// A copy of the playlist with its tracks under the new name.
+    fn duplicate_playlist(&self, playlist_id: i64, name: &str) -> Result<Playlist, AppError>;
+    /// Appended, with the tags of the library when known.
+    fn add_to_playlist(&self, playlist_id: i64, uris: &[String]) -> Result<(), AppError>;
+    fn remove_from_playlist(&self, playlist_id: i64, index: usize) -> Result<(), AppError>;
+    fn move_in_playlist(&self, playlist_id: i64, from: usize, to: usize) -> Result<(), AppError>;
+}
+
+pub fn new_arc(path: PathBuf) -> Arc<dyn Store> {
+    let connection = open(&path).unwrap_or_else(|err| {
+        eprintln!("Unable to open the library, an in memory one is used: {err}");
+        open_in_memory().expect("Unable to open an in memory library.")
+    });
+
+    Arc::new(Store_(Mutex::new(connection)))
+}
+
+fn open(path: &Path) -> Result<Connection, AppError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut connection = Connection::open(path)?;
+    migrate(&mut connection)?;
+
+    Ok(connection)
+}
+
+fn open_in_memory() -> Result<Connection, AppError> {
+    let mut connection = Connection::open_in_memory()?;
+    migrate(&mut connection)?;
+
+    Ok(connection)
+}
+
+fn migrate(connection: &mut Connection) -> Result<(), AppError> {
+    connection.pragma_update(None, "foreign_keys", true)?;
+
+    let transaction = connection.transaction()?;
+    let version: usize = transaction.pragma_query_value(None, "user_version", |row| row.get(0))?;
+
+    if version > MIGRATIONS.len() {
+        return Err(AppError::InvalidState(format!(
+            "The library schema version `{version}` is newer than this application."
+        )));
+    }
+
+    for migration in &MIGRATIONS[version..] {
+        transaction.execute_batch(migration)?;
+    }
+
+    transaction.pragma_update(None, "user_version", MIGRATIONS.len())?;
+    transaction.commit()?;
+
+    Ok(())
+}
+
+#[derive(Debug)]
+struct Store_(Mutex<Connection>);
+
+impl Store for Store_ {
+    fn add_folder(&self, path: &Path) -> Result<(), AppError> {
+        self.0.try_lock_default_duration()?.execute(
+            "INSERT OR IGNORE INTO folders (path) VALUES (?1)",
+            params![path_to_str(path)?],
+        )?;
+
+        Ok(())
+    }
+
+    fn folders(&self) -> Result<Vec<Folder>, AppError> {
+        let connection = self.0.try_lock_default_duration()?;
+        let mut statement = connection.prepare("SELECT id, path FROM folders ORDER BY path")?;
+        let folders = statement
+            .query_map([], |row| {
+                Ok(Folder {
+                    id: row.get(0)?,
+                    path: PathBuf::from(row.get::<_, String>(1)?),
+                })
+            })?
+            .collect::<Result<_, _>>()?;
+
+        Ok(folders)
+    }
+
+    fn stamp(&self, path: &Path) -> Result<Option<FileStamp>, AppError> {
+        let stamp = self
+            .0
+            .try_lock_default_duration()?
+            .query_row(
+                "SELECT modified_ns, size FROM tracks WHERE path = ?1",
+                params![path_to_str(path)?],
+                |row| {
+                    Ok(FileStamp {
+                        modified_ns: row.get(0)?,
+                        size: row.get(1)?,
+                    })
+                },
+            )
+            .optional()?;
+
+        Ok(stamp)
+    }
+
+    fn upsert(&self, folder_id: i64, track: &Track, stamp: FileStamp) -> Result<(), AppError> {
+        let mut connection = self.0.try_lock_default_duration()?;
+        let transaction = connection.transaction()?;
+
+        let artist_id = insert_artist(&transaction, track.artist.as_deref())?;
+        let album_artist_id = insert_artist(&transaction, track.album_artist.as_deref())?;
+        let album_id = insert_album(
+            &transaction,
+            track.album.as_deref(),
+            album_artist_id.or(artist_id),
+        )?;
+
+        transaction.execute(
+            "INSERT INTO tracks (path, uri, folder_id, modified_ns, size, title, artist_id,
+                album_id, album_artist_id, genre, track_number, disc_number, duration_ns, codec,
+                bitrate, sample_rate, channels)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)
+            ON CONFLICT (path) DO UPDATE SET uri = ?2, folder_id = ?3, modified_ns = ?4,
+                size = ?5, title = ?6, artist_id = ?7, album_id = ?8, album_artist_id = ?9,
+                genre = ?10, track_number = ?11, disc_number = ?12, duration_ns = ?13,
+                codec = ?14, bitrate = ?15, sample_rate = ?16, channels = ?17",
+            params![
+                path_to_str(&track.path)?,
+                track.uri,
+                folder_id,
+                stamp.modified_ns,
+                stamp.size,
+                track.title,
+                artist_id,
+                album_id,
+                album_artist_id,
+                track.genre,
+                track.track_number,
+                track.disc_number,
+                track.duration_ns,
+                track.codec,
+                track.bitrate,
+                track.sample_rate,
+                track.channels,
+            ],
+        )?;
+
+        let track_id: i64 = transaction.query_row(
+            "SELECT id FROM tracks WHERE path = ?1",
+            params![path_to_str(&track.path)?],
+            |row| row.get(0),
+        )?;
+        transaction.execute("DELETE FROM tracks_fts WHERE rowid = ?1", params![track_id])?;
+        transaction.execute(
+            "INSERT INTO tracks_fts (rowid, title, artist, album, album_artist, genre, path)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                track_id,
+                track.title,
+                track.artist,
+                track.album,
+                track.album_artist,
+                track.genre,
+                path_to_str(&track.path)?,
+            ],
+        )?;
+
+        transaction.commit()?;
+
+        Ok(())
+    }
+
+    fn retain(&self, folder_id: i64, paths: &[PathBuf]) -> Result<usize, AppError> {
+        let mut connection = self.0.try_lock_default_duration()?;
+        let transaction = connection.transaction()?;
+        let paths = paths
+            .iter()
+            .map(|path| path_to_str(path))
+            .collect::<Result<HashSet<_>, _>>()?;
+
+        let missing_ids = {
+            let mut statement =
+                transaction.prepare("SELECT id, path FROM tracks WHERE folder_id = ?1")?;
+            let rows = statement
+                .query_map(params![folder_id], |row| {
+                    Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+
+            rows.into_iter()
+                .filter(|(_, path)| !paths.contains(path.as_str()))
+                .map(|(id, _)| id)
+                .collect::<Vec<_>>()
+        };
+
+        for id in &missing_ids {
+            transaction.execute("DELETE FROM tracks WHERE id = ?1", params![id])?;
+            transaction.execute("DELETE FROM tracks_fts WHERE rowid = ?1", params![id])?;
+        }
+
+        delete_orphans(&transaction)?;
+        transaction.commit()?;
+
+        Ok(missing_ids.len())
+    }
+
+    fn tracks(&self) -> Result<Vec<Track>, AppError> {
+        self.query_tracks(&format!("{TRACK_SELECT} ORDER BY t.path"), [])
+    }
+
+    fn albums(&self) -> Result<Vec<Album>, AppError> {
+        let connection = self.0.try_lock_default_duration()?;
+        let mut statement = connection.prepare(
+            "SELECT al.id, al.title, ar.name, COUNT(t.id), SUM(t.duration_ns)
+            FROM albums al
+            LEFT JOIN artists ar ON ar.id = al.artist_id
+            JOIN tracks t ON t.album_id = al.id
+            GROUP BY al.id
+            ORDER BY al.title COLLATE NOCASE, ar.name COLLATE NOCASE",
+        )?;
+        let albums = statement
+            .query_map([], |row| {
+                Ok(Album {
+                    id: row.get(0)?,
+                    title: row.get(1)?,
+                    artist: row.get(2)?,
+                    track_count: row.get(3)?,
+                    duration_ns: row.get(4)?,
+                })
+            })?
+            .collect::<Result<_, _>>()?;
+
+        Ok(albums)
+    }
+
+    fn artists(&self) -> Result<Vec<Artist>, AppError> {
+        let connection = self.0.try_lock_default_duration()?;
+        let mut statement = connection.prepare(
+            "SELECT ar.id, ar.name, COUNT(t.id)
+            FROM artists ar
+            JOIN tracks t ON t.artist_id = ar.id
+            GROUP BY ar.id
+            ORDER BY ar.name COLLATE NOCASE",
+        )?;
+        let artists = statement
+            .query_map([], |row| {
+                Ok(Artist {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    track_count: row.get(2)?,
+                })
+            })?
+            .collect::<Result<_, _>>()?;
+
+        Ok(artists)
+    }
+
+    fn tracks_by_album(&self, album_id: i64) -> Result<Vec<Track>, AppError> {
+        self.query_tracks(
+            &format!("{TRACK_SELECT} WHERE t.album_id = ?1 {TRACK_ORDER}"),
+            params![album_id],
+        )
+    }
+
+    fn tracks_by_artist(&self, artist_id: i64) -> Result<Vec<Track>, AppError> {
+        self.query_tracks(
+            &format!(
+                "{TRACK_SELECT} WHERE t.artist_id = ?1 OR t.album_artist_id = ?1
+                ORDER BY al.title COLLATE NOCASE, t.disc_number, t.track_number, t.path"
+            ),
+            params![artist_id],
+        )
+    }
+
+    fn search(&self, query: &str, offset: usize, limit: usize) -> Result<SearchPage, AppError> {
+        let limit = limit.min(MAX_PAGE_LIMIT);
+        let Some(match_expression) = search::match_expression(query) else {
+            return Ok(SearchPage {
+                offset,
+                ..SearchPage::default()
+            });
+        };
+
+        let total = self.0.try_lock_default_duration()?.query_row(
+            "SELECT COUNT(*) FROM tracks_fts WHERE tracks_fts MATCH ?1",
+            params![match_expression],
+            |row| row.get(0),
+        )?;
+        let tracks = self.query_tracks(
+            &format!(
+                "{TRACK_SELECT}
+                JOIN (
+                    SELECT rowid AS id, bm25(tracks_fts, {BM25_WEIGHTS}) AS rank
+                    FROM tracks_fts
+                    WHERE tracks_fts MATCH ?1
+                    ORDER BY rank
+                    LIMIT ?2 OFFSET ?3
+                ) f ON f.id = t.id
+                ORDER BY f.rank"
+            ),
+            params![match_expression, limit, offset],
+        )?;
+
+        Ok(SearchPage {
+            tracks,
+            total,
+            offset,
+        })
+    }
+
+    fn playlists(&self) -> Result<Vec<Playlist>, AppError> {
+        let connection = self.0.try_lock_default_duration()?;
+        let mut statement = connection.prepare(
+            "SELECT p.id, p.name, COUNT(pt.id)
+            FROM playlists p
+            LEFT JOIN playlist_tracks pt ON pt.playlist_id = p.id
+            GROUP BY p.id
+            ORDER BY p.name COLLATE NOCASE",
+        )?;
+        let playlists = statement
+            .query_map([], playlist_from_row)?
+            .collect::<Result<_, _>>()?;
+
+        Ok(playlists)
+    }
+
+    fn playlist_tracks(&self, playlist_id: i64) -> Result<Vec<Track>, AppError> {
+        let mut connection = self.0.try_lock_default_duration()?;
+        let transaction = connection.transaction()?;
+        check_playlist(&transaction, playlist_id)?;
+
+        let entries = {
+            let mut statement = transaction.prepare(
+                "SELECT id, uri, title, artist, album, duration_ns FROM playlist_tracks
+                WHERE playlist_id = ?1 ORDER BY position",
+            )?;
+            let rows = statement
+                .query_map(params![playlist_id], |row| {
+                    Ok((
+                        row.get::<_, i64>(0)?,
+                        Track {
+                            uri: row.get(1)?,
+                            title: row.get(2)?,
+                            artist: row.get(3)?,
+                            album: row.get(4)?,
+                            duration_ns: row.get(5)?,
+                            ..Track::default()
+                        },
+                    ))
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+            rows
+        };
+
+        let mut tracks = Vec::with_capacity(entries.len());
+
+        for (id, entry) in entries {
+            if let Some(track) = track_by_uri(&transaction, &entry.uri)? {
+                tracks.push(track);
+            } else if let Some(track) = track_by_tags(&transaction, &entry)? {
+                transaction.execute(
+                    "UPDATE playlist_tracks SET uri = ?2 WHERE id = ?1",
+                    params![id, track.uri],
+                )?;
+                tracks.push(track);
+            } else {
+                tracks.push(Track {
+                    path: uri::to_path(&entry.uri).unwrap_or_default(),
+                    ..entry
+                });
+            }
+        }
+
+        transaction.commit()?;
+
+        Ok(tracks)
+    }
+
+    fn create_playlist(&self, name: &str) -> Result<Playlist, AppError> {
+        let connection = self.0.try_lock_default_duration()?;
+        let name = check_playlist_name(&connection, name, None)?;
+
+        connection.execute("INSERT INTO playlists (name) VALUES (?1)", params![name])?;
+
+        Ok(Playlist {
+            id: connection.last_insert_rowid(),
+            name: name.to_owned(),
+            track_count: 0,
+        })
+    }
+
+    fn rename_playlist(&self, playlist_id: i64, name: &str) -> Result<(), AppError> {
+        let connection = self.0.try_lock_default_duration()?;
+        check_playlist(&connection, playlist_id)?;
+        let name = check_playlist_name(&connection, name, Some(playlist_id))?;
+
+        connection.execute(
+            "UPDATE playlists SET name = ?2 WHERE id = ?1",
+            params![playlist_id, name],
+        )?;
+
+        Ok(())
+    }
+
+    fn delete_playlist(&self, playlist_id: i64) -> Result<(), AppError> {
+        let connection = self.0.try_lock_default_duration()?;
+        check_playlist(&connection, playlist_id)?;
+
+        connection.execute("DELETE FROM playlists WHERE id = ?1", params![playlist_id])?;
+
+        Ok(())
+    }
+
+    fn duplicate_playlist(&self, playlist_id: i64, name: &str) -> Result<Playlist, AppError> {
+        let mut connection = self.0.try_lock_default_duration()?;
+        let transaction = connection.transaction()?;
+        check_playlist(&transaction, playlist_id)?;
+        let name = check_playlist_name(&transaction, name, None)?;
+
+        transaction.execute("INSERT INTO playlists (name) VALUES (?1)", params![name])?;
+        let id = transaction.last_insert_rowid();
+        let track_count = transaction.execute(
+            "INSERT INTO playlist_tracks (playlist_id, position, uri, title, artist, album,
+                duration_ns)
+            SELECT ?2, position, uri, title, artist, album, duration_ns FROM playlist_tracks
+            WHERE playlist_id = ?1",
+            params![playlist_id, id],
+        )?;
+        transaction.commit()?;
+
+        Ok(Playlist {
+            id,
+            name: name.to_owned(),
+            track_count,
+        })
+    }
+
+    fn add_to_playlist(&self, playlist_id: i64, uris: &[String]) -> Result<(), AppError> {
+        let mut connection = self.0.try_lock_default_duration()?;
+        let transaction = connection.transaction()?;
+        check_playlist(&transaction, playlist_id)?;
+        let mut position = playlist_entry_ids(&transaction, playlist_id)?.len();
+
+        for uri in uris {
+            let track = track_by_uri(&transaction, uri)?.unwrap_or_else(|| Track {
+                uri: uri.clone(),
+                ..Track::default()
+            });
+
+            transaction.execute(
+                "INSERT INTO playlist_tracks (playlist_id, position, uri, title, artist, album,
+                    duration_ns)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    playlist_id,
+                    position,
+                    track.uri,
+                    track.title,
+                    track.artist,
+                    track.album,
+                    track.duration_ns,
+                ],
+            )?;
+            position += 1;
+        }
+
+        transaction.commit()?;
+
+        Ok(())
+    }
+
+    fn remove_from_playlist(&self, playlist_id: i64, index: usize) -> Result<(), AppError> {
+        let mut connection = self.0.try_lock_default_duration()?;
+        let transaction = connection.transaction()?;
+        check_playlist(&transaction, playlist_id)?;
+        let mut ids = playlist_entry_ids(&transaction, playlist_id)?;
+        check_index(index, ids.len())?;
+
+        let id = ids.remove(index);
+        transaction.execute("DELETE FROM playlist_tracks WHERE id = ?1", params![id])?;
+        set_positions(&transaction, &ids)?;
+        transaction.commit()?;
+
+        Ok(())
+    }
+
+    fn move_in_playlist(&self, playlist_id: i64, from: usize, to: usize) -> Result<(), AppError> {
+        let mut connection = self.0.try_lock_default_duration()?;
+        let transaction = connection.transaction()?;
+        check_playlist(&transaction, playlist_id)?;
+        let mut ids = playlist_entry_ids(&transaction, playlist_id)?;
+        check_index(from, ids.len())?;
+        check_index(to, ids.len())?;
+
+        let id = ids.remove(from);
+        ids.insert(to, id);
+        set_positions(&transaction, &ids)?;
+        transaction.commit()?;
+
+        Ok(())
+    }
+}
+
+impl Store_ {
+    fn query_tracks(&self, sql: &str, params: impl Params) -> Result<Vec<Track>, AppError> {
+        let connection = self.0.try_lock_default_duration()?;
+        let mut statement = connection.prepare(sql)?;
+        let tracks = statement
+            .query_map(params, track_from_row)?
+            .collect::<Result<_, _>>()?;
+
+        Ok(tracks)
+    }
+}
+
+fn track_from_row(row: &Row) -> rusqlite::Result<Track> {
+    Ok(Track {
+        uri: row.get(0)?,
+        path: PathBuf::from(row.get::<_, String>(1)?),
+        title: row.get(2)?,
+        artist: row.get(3)?,
+        album: row.get(4)?,
+        album_artist: row.get(5)?,
+        genre: row.get(6)?,
+        track_number: row.get(7)?,
+        disc_number: row.get(8)?,
+        duration_ns: row.get(9)?,
+        codec: row.get(10)?,
+        bitrate: row.get(11)?,
+        sample_rate: row.get(12)?,
+        channels: row.get(13)?,
+    })
+}
+
+fn insert_artist(transaction: &Transaction, name: Option<&str>) -> Result<Option<i64>, AppError> {
+    let Some(name) = name else {
+        return Ok(None);
+    };
+
+    transaction.execute(
+        "INSERT OR IGNORE INTO artists (name) VALUES (?1)",
+        params![name],
+    )?;
+    let id = transaction.query_row(
+        "SELECT id FROM artists WHERE name = ?1",
+        params![name],
+        |row| row.get(0),
+    )?;
+
+    Ok(Some(id))
+}
+
+fn insert_album(
+    transaction: &Transaction,
+    title: Option<&str>,
+    artist_id: Option<i64>,
+) -> Result<Option<i64>, AppError> {
+    let Some(title) = title else {
+        return Ok(None);
+    };
+
+    // `IS` matches the albums without artist too.
+    let id = transaction
+        .query_row(
+            "SELECT id FROM albums WHERE title = ?1 AND artist_id IS ?2",
+            params![title, artist_id],
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    if let Some(id) = id {
+        return Ok(Some(id));
+    }
+
+    transaction.execute(
+        "INSERT INTO albums (title, artist_id) VALUES (?1, ?2)",
+        params![title, artist_id],
+    )?;
+
+    Ok(Some(transaction.last_insert_rowid()))
+}
+
+fn delete_orphans(transaction: &Transaction) -> Result<(), AppError> {
+    transaction.execute_batch(
+        "DELETE FROM albums WHERE id NOT IN (
+            SELECT album_id FROM tracks WHERE album_id IS NOT NULL
+        );
+        DELETE FROM artists WHERE id NOT IN (
+            SELECT artist_id FROM tracks WHERE artist_id IS NOT NULL
+            UNION SELECT album_artist_id FROM tracks WHERE album_artist_id IS NOT NULL
+            UNION SELECT artist_id FROM albums WHERE artist_id IS NOT NULL
+        );",
+    )?;
+
+    Ok(())
+}
+
+fn playlist_from_row(row: &Row) -> rusqlite::Result<Playlist> {
+    Ok(Playlist {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        track_count: row.get(2)?,
+    })
+}
+
+fn track_by_uri(connection: &Connection, uri: &str) -> Result<Option<Track>, AppError> {
+    let track = connection
+        .query_row(
+            &format!("{TRACK_SELECT} WHERE t.uri = ?1"),
+            params![uri],
+            track_from_row,
+        )
+        .optional()?;
+
+    Ok(track)
+}
+
+/// Only a single match is taken, the title is needed.
+fn track_by_tags(connection: &Connection, entry: &Track) -> Result<Option<Track>, AppError> {
+    let Some(title) = &entry.title else {
+        return Ok(None);
+    };
+
+    let mut statement = connection.prepare(&format!(
+        "{TRACK_SELECT} WHERE t.title = ?1 AND ar.name IS ?2 AND al.title IS ?3"
+    ))?;
+    let mut tracks = statement
+        .query_map(params![title, entry.artist, entry.album], track_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    tracks.retain(|track| match (track.duration_ns, entry.duration_ns) {
+        (Some(duration_ns), Some(entry_duration_ns)) => {
+            (duration_ns - entry_duration_ns).abs() <= DURATION_TOLERANCE_NS
+        }
+        _ => true,
+    });
+
+    Ok(match tracks.len() {
+        1 => tracks.pop(),
+        _ => None,
+    })
+}
+
+fn check_playlist(connection: &Connection, playlist_id: i64) -> Result<(), AppError> {
+    connection
+        .query_row(
+            "SELECT id FROM playlists WHERE id = ?1",
+            params![playlist_id],
+            |row| row.get::<_, i64>(0),
+        )
+        .optional()?
+        .map(|_| ())
+        .ok_or_else(|| AppError::InvalidInput(format!("Unknown playlist `{playlist_id}`.")))
+}
+
+/// Trimmed, not empty and not used by another playlist.
+fn check_playlist_name<'a>(
+    connection: &Connection,
+    name: &'a str,
+    playlist_id: Option<i64>,
+) -> Result<&'a str, AppError> {
+    let name = name.trim();
+
+    if name.is_empty() {
+        return Err(AppError::InvalidInput(
+            "The playlist name is empty.".to_owned(),
+        ));
+    }
+
+    let other_id = connection
+        .query_row(
+            "SELECT id FROM playlists WHERE name = ?1",
+            params![name],
+            |row| row.get::<_, i64>(0),
+        )
+        .optional()?;
+
+    match other_id {
+        Some(other_id) if Some(other_id) != playlist_id => Err(AppError::InvalidInput(format!(
+            "The playlist `{name}` already exists."
+        ))),
+        _ => Ok(name),
+    }
+}
+
+fn check_index(index: usize, len: usize) -> Result<(), AppError> {
+    if index >= len {
+        return Err(AppError::OutOfRange { index, len });
+    }
+
+    Ok(())
+}
+
+fn playlist_entry_ids(connection: &Connection, playlist_id: i64) -> Result<Vec<i64>, AppError> {
+    let mut statement = connection
+        .prepare("SELECT id FROM playlist_tracks WHERE playlist_id = ?1 ORDER BY position")?;
+    let ids = statement
+        .query_map(params![playlist_id], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+
+    Ok(ids)
+}
+
+fn set_positions(transaction: &Transaction, ids: &[i64]) -> Result<(), AppError> {
+    for (position, id) in ids.iter().enumerate() {
+        transaction.execute(
+            "UPDATE playlist_tracks SET position = ?2 WHERE id = ?1",
+            params![id, position],
+        )?;
+    }
+
+    Ok(())
+}
+
+fn path_to_str(path: &Path) -> Result<&str, AppError> {
+    path.to_str().ok_or_else(|| {
+        AppError::InvalidPath(format!("The path `{}` is not valid UTF-8.", path.display()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        path::{Path, PathBuf},
+        sync::Arc,
+    };
+
+    use parking_lot::Mutex;
+
+    use crate::library::track::{FileStamp, Track};
+
+    use super::{migrate, open_in_memory, Store, Store_, MIGRATIONS};
+
+    const STAMP: FileStamp = FileStamp {
+        modified_ns: 1,
+        size: 2,
+    };
+
+    fn new_store() -> Arc<dyn Store> {
+        let store: Arc<dyn Store> = Arc::new(Store_(Mutex::new(open_in_memory().unwrap())));
+        store.add_folder(Path::new("/music")).unwrap();
+        store
+    }
+
+    fn folder_id(store: &Arc<dyn Store>) -> i64 {
+        store.folders().unwrap()[0].id
+    }
+
+    fn track(path: &str, album: &str, artist: &str, track_number: u32) -> Track {
+        Track {
+            path: PathBuf::from(path),
+            uri: format!("file://{path}"),
+            album: Some(album.to_owned()),
+            artist: Some(artist.to_owned()),
+            track_number: Some(track_number),
+            ..Track::default()
+        }
+    }
+
+    #[test]
+    fn test_migrate_twice() {
+        let mut connection = open_in_memory().unwrap();
+
+        migrate(&mut connection).unwrap();
+
+        let version: usize = connection
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .unwrap();
+        assert_eq!(version, MIGRATIONS.len());
+    }
+
+    #[test]
+    fn test_stamp() {
+        let store = new_store();
+        let folder_id = folder_id(&store);
+
+        assert_eq!(store.stamp(Path::new("/music/a.flac")).unwrap(), None);
+
+        store
+            .upsert(folder_id, &track("/music/a.flac", "A", "X", 1), STAMP)
+            .unwrap();
+
+        assert_eq!(
+            store.stamp(Path::new("/music/a.flac")).unwrap(),
+            Some(STAMP)
+        );
+    }
+
+    #[test]
+    fn test_upsert_replaces() {
+        let store = new_store();
+        let folder_id = folder_id(&store);
+
+        store
+            .upsert(folder_id, &track("/music/a.flac", "A", "X", 1), STAMP)
+            .unwrap();
+        store
+            .upsert(folder_id, &track("/music/a.flac", "B", "X", 1), STAMP)
+            .unwrap();
+
+        let albums = store.albums().unwrap();
+        assert_eq!(store.tracks().unwrap().len(), 1);
+        assert_eq!(albums.len(), 1);
+        assert_eq!(albums[0].title, "B");
+    }
+
+    #[test]
+    fn test_tracks_by_album() {
+        let store = new_store();
+        let folder_id = folder_id(&store);
+        store
+            .upsert(folder_id, &track("/music/2.flac", "A", "X", 2), STAMP)
+            .unwrap();
+        store
+            .upsert(folder_id, &track("/music/1.flac", "A", "X", 1), STAMP)
+            .unwrap();
+        store
+            .upsert(folder_id, &track("/music/3.flac", "B", "X", 1), STAMP)
+            .unwrap();
+
+        let album = store
+            .albums()
+            .unwrap()
+            .into_iter()
+            .find(|album| album.title == "A")
+            .unwrap();
+        let tracks = store.tracks_by_album(album.id).unwrap();
+
+        assert_eq!(album.track_count, 2);
+        assert_eq!(tracks[0].path, PathBuf::from("/music/1.flac"));
+        assert_eq!(tracks[1].path, PathBuf::from("/music/2.flac"));
+    }
+
+    #[test]
+    fn test_tracks_by_artist() {
+        let store = new_store();
+        let folder_id = folder_id(&store);
+        store
+            .upsert(folder_id, &track("/music/1.flac", "A", "X", 1), STAMP)
+            .unwrap();
+        store
+            .upsert(folder_id, &track("/music/2.flac", "B", "Y", 1), STAMP)
+            .unwrap();
+
+        let artist = store
+            .artists()
+            .unwrap()
+            .into_iter()
+            .find(|artist| artist.name == "Y")
+            .unwrap();
+        let tracks = store.tracks_by_artist(artist.id).unwrap();
+
+        assert_eq!(tracks.len(), 1);
+        assert_eq!(tracks[0].album.as_deref(), Some("B"));
+    }
+
+    #[test]
+    fn test_retain_deletes_orphans() {
+        let store = new_store();
+        let folder_id = folder_id(&store);
+        store
+            .upsert(folder_id, &track("/music/1.flac", "A", "X", 1), STAMP)
+            .unwrap();
+        store
+            .upsert(folder_id, &track("/music/2.flac", "B", "Y", 1), STAMP)
+            .unwrap();
+
+        let removed = store
+            .retain(folder_id, &[PathBuf::from("/music/1.flac")])
+            .unwrap();
+
+        assert_eq!(removed, 1);
+        assert_eq!(store.albums().unwrap().len(), 1);
+        assert_eq!(store.artists().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_retain_other_folder() {
+        let store = new_store();
+        let folder_id = folder_id(&store);
+        store.add_folder(Path::new("/offline")).unwrap();
+        let offline_id = store
+            .folders()
+            .unwrap()
+            .into_iter()
+            .find(|folder| folder.path == Path::new("/offline"))
+            .unwrap()
+            .id;
+        store
+            .upsert(offline_id, &track("/offline/1.flac", "A", "X", 1), STAMP)
+            .unwrap();
+
+        store.retain(folder_id, &[]).unwrap();
+
+        assert_eq!(store.tracks().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_search_prefix_without_diacritics() {
+        let store = new_store();
+        let folder_id = folder_id(&store);
+        store
+            .upsert(
+                folder_id,
+                &track("/music/1.flac", "Homogenic", "Björk", 1),
+                STAMP,
+            )
+            .unwrap();
+        store
+            .upsert(
+                folder_id,
+                &track("/music/2.flac", "Debut", "Other", 1),
+                STAMP,
+            )
+            .unwrap();
+
+        let page = store.search("bjo homo", 0, 10).unwrap();
+
+        assert_eq!(page.total, 1);
+        assert_eq!(page.tracks[0].artist.as_deref(), Some("Björk"));
+    }
+
+    #[test]
+    fn test_search_ranks_title_first() {
+        let store = new_store();
+        let folder_id = folder_id(&store);
+        store
+            .upsert(folder_id, &track("/music/star/1.flac", "A", "X", 1), STAMP)
+            .unwrap();
+        store
+            .upsert(
+                folder_id,
+                &Track {
+                    title: Some("Star".to_owned()),
+                    ..track("/music/2.flac", "B", "Y", 1)
+                },
+                STAMP,
+            )
+            .unwrap();
+
+        let page = store.search("star", 0, 10).unwrap();
+
+        assert_eq!(page.total, 2);
+        assert_eq!(page.tracks[0].title.as_deref(), Some("Star"));
+    }
+
+    #[test]
+    fn test_search_ranks_every_match() {
+        let store = new_store();
+        let folder_id = folder_id(&store);
+        for i in 0..1_100 {
+            store
+                .upsert(
+                    folder_id,
+                    &track(&format!("/music/star/{i:04}.flac"), "A", "X", i),
+                    STAMP,
+                )
+                .unwrap();
+        }
+        store
+            .upsert(
+                folder_id,
+                &Track {
+                    title: Some("Star".to_owned()),
+                    ..track("/music/last.flac", "B", "Y", 1)
+                },
+                STAMP,
+            )
+            .unwrap();
+
+        let page = store.search("star", 0, 1).unwrap();
+
+        assert_eq!(page.total, 1_101);
+        assert_eq!(page.tracks[0].title.as_deref(), Some("Star"));
+    }
+
+    #[test]
+    fn test_search_paged() {
+        let store = new_store();
+        let folder_id = folder_id(&store);
+        for i in 0..5 {
+            store
+                .upsert(
+                    folder_id,
+                    &track(&format!("/music/{i}.flac"), "A", "X", i),
+                    STAMP,
+                )
+                .unwrap();
+        }
+
+        let page = store.search("x", 4, 2).unwrap();
+
+        assert_eq!(page.total, 5);
+        assert_eq!(page.offset, 4);
+        assert_eq!(page.tracks.len(), 1);
+    }
+
+    #[test]
+    fn test_search_after_retain() {
+        let store = new_store();
+        let folder_id = folder_id(&store);
+        store
+            .upsert(folder_id, &track("/music/1.flac", "A", "X", 1), STAMP)
+            .unwrap();
+
+        store.retain(folder_id, &[]).unwrap();
+
+        assert_eq!(store.search("x", 0, 10).unwrap().total, 0);
+    }
+
+    #[test]
+    fn test_playlist_name_taken() {
+        let store = new_store();
+        store.create_playlist("Mix").unwrap();
+        let other = store.create_playlist("Other").unwrap();
+
+        assert!(store.create_playlist(" Mix ").is_err());
+        assert!(store.create_playlist("").is_err());
+        assert!(store.rename_playlist(other.id, "Mix").is_err());
+        store.rename_playlist(other.id, "Other").unwrap();
+    }
+
+    #[test]
+    fn test_playlist_tracks_ordered() {
+        let store = new_store();
+        let folder_id = folder_id(&store);
+        store
+            .upsert(folder_id, &track("/music/1.flac", "A", "X", 1), STAMP)
+            .unwrap();
+        let playlist = store.create_playlist("Mix").unwrap();
+        let uris = [
+            "file:///music/1.flac",
+            "http://radio/stream",
+            "file:///music/2.flac",
+        ]
+        .map(str::to_owned);
+
+        store.add_to_playlist(playlist.id, &uris).unwrap();
+        store.move_in_playlist(playlist.id, 2, 0).unwrap();
+        store.remove_from_playlist(playlist.id, 2).unwrap();
+
+        let tracks = store.playlist_tracks(playlist.id).unwrap();
+        assert_eq!(tracks.len(), 2);
+        assert_eq!(tracks[0].path, PathBuf::from("/music/2.flac"));
+        assert_eq!(tracks[1].album.as_deref(), Some("A"));
+        assert!(store.remove_from_playlist(playlist.id, 2).is_err());
+    }
+
+    #[test]
+    fn test_duplicate_playlist() {
+        let store = new_store();
+        let playlist = store.create_playlist("Mix").unwrap();
+        store
+            .add_to_playlist(playlist.id, &["http://radio/stream".to_owned()])
+            .unwrap();
+
+        let copy = store.duplicate_playlist(playlist.id, "Copy").unwrap();
+        store.delete_playlist(playlist.id).unwrap();
+
+        assert_eq!(copy.track_count, 1);
+        assert_eq!(store.playlists().unwrap(), vec![copy.clone()]);
+        assert_eq!(store.playlist_tracks(copy.id).unwrap().len(), 1);
+        assert!(store.playlist_tracks(playlist.id).is_err());
+    }
+
+    #[test]
+    fn test_playlist_track_moved() {
+        let store = new_store();
+        let folder_id = folder_id(&store);
+        let moved = Track {
+            title: Some("Song".to_owned()),
+            duration_ns: Some(180_000_000_000),
+            ..track("/music/old/1.flac", "A", "X", 1)
+        };
+        store.upsert(folder_id, &moved, STAMP).unwrap();
+        let playlist = store.create_playlist("Mix").unwrap();
+        store
+            .add_to_playlist(playlist.id, &[moved.uri.clone()])
+            .unwrap();
+
+        store
+            .upsert(
+                folder_id,
+                &Track {
+                    title: Some("Song".to_owned()),
+                    duration_ns: Some(180_500_000_000),
+                    ..track("/music/new/1.flac", "A", "X", 1)
+                },
+                STAMP,
+            )
+            .unwrap();
+        store
+            .retain(folder_id, &[PathBuf::from("/music/new/1.flac")])
+            .unwrap();
+
+        let tracks = store.playlist_tracks(playlist.id).unwrap();
+        assert_eq!(tracks[0].uri, "file:///music/new/1.flac");
+    }
+}
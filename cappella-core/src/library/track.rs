@@ -0,0 +1,55 @@
+use std::path::PathBuf;
+
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Track {
+    pub uri: String,
+    pub path: PathBuf,
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub album_artist: Option<String>,
+    pub genre: Option<String>,
+    pub track_number: Option<u32>,
+    pub disc_number: Option<u32>,
+    pub duration_ns: Option<i64>,
+    pub codec: Option<String>,
+    pub bitrate: Option<u32>,
+    pub sample_rate: Option<u32>,
+    pub channels: Option<u32>,
+}
+
+/// Compared on rescan, the file is probed again only when it differs.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FileStamp {
+    pub modified_ns: i64,
+    pub size: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct Folder {
+    pub id: i64,
+    pub path: PathBuf,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct Album {
+    pub id: i64,
+    pub title: String,
+    pub artist: Option<String>,
+    pub track_count: usize,
+    pub duration_ns: Option<i64>,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct Artist {
+    pub id: i64,
+    pub name: String,
+    pub track_count: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct Playlist {
+    pub id: i64,
+    pub name: String,
+    pub track_count: usize,
+}
@@ -1,3 +1,5 @@
 pub mod app_error;
 pub mod mutex_lock_timeout;
+pub mod settings;
 pub mod state;
+pub mod uri;
@@ -0,0 +1,242 @@
+use std::{
+    error::Error, ffi::NulError, fmt::Display, io, num::TryFromIntError, str::Utf8Error,
+    sync::mpsc::RecvTimeoutError, time::SystemTimeError,
+};
+
+use serde::ser::SerializeStruct;
+
+use crate::streamer::sys::gst_error::GstError;
+
+#[derive(Debug)]
+pub enum AppError {
+    LockTimeout,
+    /// Posted on the bus by a GStreamer element.
+    GStreamer(Box<GstError>),
+    /// A GStreamer or GLib call has failed or returned a null pointer.
+    Ffi(String),
+    /// A streamer message or structure is unexpected or incomplete.
+    InvalidMessage(String),
+    /// The component is not in a state allowing the call.
+    InvalidState(String),
+    OutOfRange {
+        index: usize,
+        len: usize,
+    },
+    InvalidPath(String),
+    InvalidUri(String),
+    /// A command from the user, as on a command line, is not valid.
+    InvalidInput(String),
+    Channel(String),
+    ChannelTimeout(RecvTimeoutError),
+    Io(io::Error),
+    Json(serde_json::Error),
+    Database(rusqlite::Error),
+    #[cfg(target_os = "linux")]
+    DBus(zbus::Error),
+    CString(NulError),
+    Utf8(Utf8Error),
+    IntConversion(TryFromIntError),
+    Time(SystemTimeError),
+}
+
+impl AppError {
+    /// The variant name, for the frontend and the logs.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AppError::LockTimeout => "LockTimeout",
+            AppError::GStreamer(_) => "GStreamer",
+            AppError::Ffi(_) => "Ffi",
+            AppError::InvalidMessage(_) => "InvalidMessage",
+            AppError::InvalidState(_) => "InvalidState",
+            AppError::OutOfRange { .. } => "OutOfRange",
+            AppError::InvalidPath(_) => "InvalidPath",
+            AppError::InvalidUri(_) => "InvalidUri",
+            AppError::InvalidInput(_) => "InvalidInput",
+            AppError::Channel(_) | AppError::ChannelTimeout(_) => "Channel",
+            AppError::Io(_) => "Io",
+            AppError::Json(_) => "Json",
+            AppError::Database(_) => "Database",
+            #[cfg(target_os = "linux")]
+            AppError::DBus(_) => "DBus",
+            AppError::CString(_) | AppError::Utf8(_) | AppError::IntConversion(_) => "Conversion",
+            AppError::Time(_) => "Time",
+        }
+    }
+
+    /// The details when the error comes from a GStreamer element.
+    pub fn gst_error(&self) -> Option<&GstError> {
+        match self {
+            AppError::GStreamer(gst_error) => Some(gst_error),
+            _ => None,
+        }
+    }
+}
+
+impl Display for AppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AppError::LockTimeout => write!(f, "Lock timeout."),
+            AppError::GStreamer(gst_error) => write!(f, "{gst_error}"),
+            AppError::Ffi(message)
+            | AppError::InvalidMessage(message)
+            | AppError::InvalidState(message)
+            | AppError::InvalidPath(message)
+            | AppError::InvalidUri(message)
+            | AppError::InvalidInput(message)
+            | AppError::Channel(message) => write!(f, "{message}"),
+            AppError::OutOfRange { index, len } => {
+                write!(f, "The index `{index}` is out of range (length: {len}).")
+            }
+            AppError::ChannelTimeout(err) => write!(f, "Channel error: {err}"),
+            AppError::Io(err) => write!(f, "I/O error: {err}"),
+            AppError::Json(err) => write!(f, "JSON error: {err}"),
+            AppError::Database(err) => write!(f, "Database error: {err}"),
+            #[cfg(target_os = "linux")]
+            AppError::DBus(err) => write!(f, "D-Bus error: {err}"),
+            AppError::CString(err) => write!(f, "C string conversion error: {err}"),
+            AppError::Utf8(err) => write!(f, "UTF-8 conversion error: {err}"),
+            AppError::IntConversion(err) => write!(f, "Integer conversion error: {err}"),
+            AppError::Time(err) => write!(f, "Time error: {err}"),
+        }
+    }
+}
+
+impl Error for AppError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AppError::ChannelTimeout(err) => Some(err),
+            AppError::Io(err) => Some(err),
+            AppError::Json(err) => Some(err),
+            AppError::Database(err) => Some(err),
+            #[cfg(target_os = "linux")]
+            AppError::DBus(err) => Some(err),
+            AppError::CString(err) => Some(err),
+            AppError::Utf8(err) => Some(err),
+            AppError::IntConversion(err) => Some(err),
+            AppError::Time(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Serialized as `{ kind, message, gst_error }` for the Tauri commands.
+impl serde::Serialize for AppError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("AppError", 3)?;
+        state.serialize_field("kind", self.kind())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("gst_error", &self.gst_error())?;
+        state.end()
+    }
+}
+
+impl From<GstError> for AppError {
+    fn from(value: GstError) -> Self {
+        AppError::GStreamer(Box::new(value))
+    }
+}
+
+impl From<RecvTimeoutError> for AppError {
+    fn from(value: RecvTimeoutError) -> Self {
+        AppError::ChannelTimeout(value)
+    }
+}
+
+impl From<io::Error> for AppError {
+    fn from(value: io::Error) -> Self {
+        AppError::Io(value)
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(value: serde_json::Error) -> Self {
+        AppError::Json(value)
+    }
+}
+
+impl From<rusqlite::Error> for AppError {
+    fn from(value: rusqlite::Error) -> Self {
+        AppError::Database(value)
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl From<zbus::Error> for AppError {
+    fn from(value: zbus::Error) -> Self {
+        AppError::DBus(value)
+    }
+}
+
+impl From<NulError> for AppError {
+    fn from(value: NulError) -> Self {
+        AppError::CString(value)
+    }
+}
+
+impl From<Utf8Error> for AppError {
+    fn from(value: Utf8Error) -> Self {
+        AppError::Utf8(value)
+    }
+}
+
+impl From<TryFromIntError> for AppError {
+    fn from(value: TryFromIntError) -> Self {
+        AppError::IntConversion(value)
+    }
+}
+
+impl From<SystemTimeError> for AppError {
+    fn from(value: SystemTimeError) -> Self {
+        AppError::Time(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, io};
+
+    use crate::streamer::sys::gst_error::GstError;
+
+    use super::AppError;
+
+    #[test]
+    fn test_source() {
+        let error = AppError::from(io::Error::new(io::ErrorKind::NotFound, "missing"));
+
+        assert_eq!(error.kind(), "Io");
+        assert_eq!(error.source().unwrap().to_string(), "missing");
+        assert!(AppError::LockTimeout.source().is_none());
+    }
+
+    #[test]
+    fn test_serialize() {
+        let error = AppError::OutOfRange { index: 2, len: 1 };
+
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            serde_json::json!({
+                "kind": "OutOfRange",
+                "message": "The index `2` is out of range (length: 1).",
+                "gst_error": null,
+            })
+        );
+    }
+
+    #[test]
+    fn test_serialize_gst_error() {
+        let error = AppError::from(GstError {
+            domain: "gst-stream-error-quark".to_owned(),
+            code: 6,
+            message: "No decoder.".to_owned(),
+            ..GstError::default()
+        });
+
+        let value = serde_json::to_value(&error).unwrap();
+
+        assert_eq!(value["kind"], "GStreamer");
+        assert_eq!(value["gst_error"]["code"], 6);
+    }
+}
@@ -5,21 +5,19 @@ use parking_lot::{Mutex, MutexGuard};
 use super::app_error::AppError;
 
 const LOCK_DEFAULT_TIMEOUT_DURATION: Duration = Duration::from_secs(5);
-const TIMEOUT_ERROR_MESSAGE: &str = "Lock timeout.";
 
-pub trait MutexLockTimeout<T> {
+pub trait MutexLockTimeout<T: ?Sized> {
     fn try_lock_for(&self, duration: Duration) -> Result<MutexGuard<T>, AppError>;
     fn try_lock_default_duration(&self) -> Result<MutexGuard<T>, AppError>;
 }
 
-impl<T> MutexLockTimeout<T> for Mutex<T> {
+impl<T: ?Sized> MutexLockTimeout<T> for Mutex<T> {
     fn try_lock_default_duration(&self) -> Result<MutexGuard<T>, AppError> {
         MutexLockTimeout::try_lock_for(self, LOCK_DEFAULT_TIMEOUT_DURATION)
     }
 
     fn try_lock_for(&self, duration: Duration) -> Result<MutexGuard<T>, AppError> {
-        self.try_lock_for(duration)
-            .ok_or_else(|| AppError::new(TIMEOUT_ERROR_MESSAGE.to_owned()))
+        self.try_lock_for(duration).ok_or(AppError::LockTimeout)
     }
 }
 
@@ -29,7 +27,7 @@ mod test {
 
     use parking_lot::Mutex;
 
-    use crate::local::mutex_lock_timeout::MutexLockTimeout;
+    use crate::local::{app_error::AppError, mutex_lock_timeout::MutexLockTimeout};
 
     #[test]
     fn test_no_timeout() {
@@ -49,6 +47,6 @@ mod test {
 
         let result_res = MutexLockTimeout::try_lock_for(&element, duration);
 
-        assert!(result_res.is_err());
+        assert!(matches!(result_res, Err(AppError::LockTimeout)));
     }
 }
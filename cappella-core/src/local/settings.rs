@@ -0,0 +1,225 @@
+use std::{
+    fmt::Debug,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use parking_lot::Mutex;
+
+use super::{app_error::AppError, mutex_lock_timeout::MutexLockTimeout};
+
+pub const SETTINGS_FILE_NAME: &str = "settings.json";
+/// Appended to the name of an unreadable settings file, kept for the user.
+const BACKUP_EXTENSION: &str = "invalid";
+
+const MPD_ADDRESS: &str = "127.0.0.1:6600";
+const REMOTE_ADDRESS: &str = "0.0.0.0:7700";
+
+pub trait Settings: Debug + Send + Sync {
+    fn get(&self) -> Result<Values, AppError>;
+    fn update(&self, update: &dyn Fn(&mut Values)) -> Result<(), AppError>;
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Values {
+    pub volume: f64,
+    pub muted: bool,
+    pub mpd: Mpd,
+    pub remote: Remote,
+}
+
+impl Default for Values {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            muted: false,
+            mpd: Mpd::default(),
+            remote: Remote::default(),
+        }
+    }
+}
+
+/// The MPD protocol server, when built with the `mpd` feature.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Mpd {
+    pub enabled: bool,
+    /// Only on the local host by default.
+    pub address: String,
+}
+
+impl Default for Mpd {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            address: MPD_ADDRESS.to_owned(),
+        }
+    }
+}
+
+/// The HTTP remote control, when built with the `remote` feature.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Remote {
+    pub enabled: bool,
+    /// On every interface by default, for the phones on the local network.
+    pub address: String,
+    /// Required, the server is not started without it.
+    pub token: String,
+}
+
+impl Default for Remote {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            address: REMOTE_ADDRESS.to_owned(),
+            token: String::new(),
+        }
+    }
+}
+
+/// An unreadable file is renamed before the defaults are used, it is never overwritten. When it
+/// cannot be renamed, the settings are not saved.
+pub fn new_arc(path: PathBuf) -> Arc<dyn Settings> {
+    let (values, is_writable) = match load(&path) {
+        Ok(values) => (values, true),
+        Err(err) => {
+            eprintln!("Unable to load the settings, defaults are used: {err}");
+            let is_writable = match backup(&path) {
+                Ok(backup_path) => {
+                    eprintln!(
+                        "The unreadable settings are kept in `{}`.",
+                        backup_path.display()
+                    );
+                    true
+                }
+                Err(err) => {
+                    eprintln!("Unable to back up the settings, they are not saved: {err}");
+                    false
+                }
+            };
+            (Values::default(), is_writable)
+        }
+    };
+
+    Arc::new(Settings_ {
+        path,
+        values: Mutex::new(values),
+        is_writable,
+    })
+}
+
+#[derive(Debug)]
+struct Settings_ {
+    path: PathBuf,
+    values: Mutex<Values>,
+    is_writable: bool,
+}
+
+impl Settings for Settings_ {
+    fn get(&self) -> Result<Values, AppError> {
+        Ok(self.values.try_lock_default_duration()?.clone())
+    }
+
+    fn update(&self, update: &dyn Fn(&mut Values)) -> Result<(), AppError> {
+        if !self.is_writable {
+            return Err(AppError::InvalidState(format!(
+                "The settings file `{}` is unreadable, it is not overwritten.",
+                self.path.display()
+            )));
+        }
+
+        let mut values = self.values.try_lock_default_duration()?;
+        update(&mut values);
+
+        save(&self.path, &values)
+    }
+}
+
+fn load(path: &Path) -> Result<Values, AppError> {
+    if !path.exists() {
+        return Ok(Values::default());
+    }
+
+    let content = fs::read_to_string(path)?;
+
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// As `settings.json.invalid`, next to the file.
+fn backup(path: &Path) -> Result<PathBuf, AppError> {
+    let mut backup_path = path.as_os_str().to_owned();
+    backup_path.push(".");
+    backup_path.push(BACKUP_EXTENSION);
+    let backup_path = PathBuf::from(backup_path);
+
+    fs::rename(path, &backup_path)?;
+
+    Ok(backup_path)
+}
+
+fn save(path: &Path, values: &Values) -> Result<(), AppError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(path, serde_json::to_string_pretty(values)?)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::PathBuf, process};
+
+    use super::{new_arc, Values};
+
+    fn test_path(test_name: &str) -> PathBuf {
+        env::temp_dir()
+            .join(format!("cappella-settings-{}-{test_name}", process::id()))
+            .join("settings.json")
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let path = test_path("missing");
+
+        let settings = new_arc(path);
+
+        assert_eq!(settings.get().unwrap(), Values::default());
+    }
+
+    #[test]
+    fn test_update_is_persisted() {
+        let path = test_path("update");
+
+        new_arc(path.clone())
+            .update(&|values| values.volume = 0.5)
+            .unwrap();
+        let settings = new_arc(path.clone());
+
+        assert_eq!(settings.get().unwrap().volume, 0.5);
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_unreadable_file_is_kept() {
+        let path = test_path("unreadable");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "{ \"remote\": ").unwrap();
+
+        new_arc(path.clone())
+            .update(&|values| values.volume = 0.5)
+            .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(path.with_extension("json.invalid")).unwrap(),
+            "{ \"remote\": "
+        );
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
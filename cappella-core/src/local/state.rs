@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use crate::{frontend, library, player, stations};
+
+use super::settings::Settings;
+
+/// Cheap to clone, every user interface shares the same player.
+#[derive(Clone)]
+pub struct State {
+    frontend_pipe: Arc<dyn frontend::pipe::Pipe>,
+    library_front: Arc<dyn library::front::Front>,
+    player_front: Arc<dyn player::front::Front>,
+    settings: Arc<dyn Settings>,
+    stations_front: Arc<dyn stations::front::Front>,
+}
+
+impl State {
+    pub fn new(
+        frontend_pipe: Arc<dyn frontend::pipe::Pipe>,
+        library_front: Box<dyn library::front::Front>,
+        player_front: Box<dyn player::front::Front>,
+        settings: Arc<dyn Settings>,
+        stations_front: Box<dyn stations::front::Front>,
+    ) -> Self {
+        Self {
+            frontend_pipe,
+            library_front: Arc::from(library_front),
+            player_front: Arc::from(player_front),
+            settings,
+            stations_front: Arc::from(stations_front),
+        }
+    }
+
+    pub fn frontend_pipe(&self) -> &dyn frontend::pipe::Pipe {
+        &*self.frontend_pipe
+    }
+
+    pub fn library_front(&self) -> &dyn library::front::Front {
+        &*self.library_front
+    }
+
+    pub fn player_front(&self) -> &dyn player::front::Front {
+        &*self.player_front
+    }
+
+    pub fn settings(&self) -> &dyn Settings {
+        &*self.settings
+    }
+
+    pub fn stations_front(&self) -> &dyn stations::front::Front {
+        &*self.stations_front
+    }
+
+    /// Stops the playback and waits for the background threads.
+    pub fn end(&self) {
+        if let Err(err) = self.player_front.stop() {
+            eprintln!("Error on stop before the end: {err}");
+        }
+        if let Err(err) = self.player_front.wait_until_end() {
+            eprintln!("Error on wait until end: {err}");
+        }
+        self.library_front.wait_until_end();
+    }
+}
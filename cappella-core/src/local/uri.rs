@@ -0,0 +1,162 @@
+use std::{
+    env,
+    path::{Component, Path, PathBuf},
+};
+
+use super::app_error::AppError;
+
+const FILE_SCHEME: &str = "file://";
+const SCHEME_SEPARATOR: &str = "://";
+
+/// Characters kept as is in a file URI path, the others are percent-encoded.
+fn is_unreserved(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || b"-._~/!$&'()*+,;=:@".contains(&byte)
+}
+
+pub fn is_file(uri: &str) -> bool {
+    uri.starts_with(FILE_SCHEME)
+}
+
+pub fn from_path(path: &Path) -> Result<String, AppError> {
+    if !path.is_absolute() {
+        return Err(AppError::InvalidPath(format!(
+            "The path `{}` is not absolute.",
+            path.display()
+        )));
+    }
+
+    let path_str = path.to_str().ok_or_else(|| {
+        AppError::InvalidPath(format!("The path `{}` is not valid UTF-8.", path.display()))
+    })?;
+
+    let mut uri = FILE_SCHEME.to_owned();
+
+    for byte in path_str.bytes() {
+        if is_unreserved(byte) {
+            uri.push(byte as char);
+        } else {
+            uri.push_str(&format!("%{byte:02X}"));
+        }
+    }
+
+    Ok(uri)
+}
+
+/// For the user input: a URI is kept as is, a path is relative to the current directory.
+pub fn from_uri_or_path(value: &str) -> Result<String, AppError> {
+    from_uri_or_relative_path(value, &env::current_dir()?)
+}
+
+/// For a playlist entry: a URI is kept as is, a path is relative to the playlist folder.
+pub fn from_uri_or_relative_path(value: &str, base_dir: &Path) -> Result<String, AppError> {
+    if value.contains(SCHEME_SEPARATOR) {
+        return Ok(value.to_owned());
+    }
+
+    // Without `..`, the same file has the same URI as in the library.
+    let mut path = PathBuf::new();
+    for component in base_dir.join(value).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                path.pop();
+            }
+            component => path.push(component),
+        }
+    }
+
+    from_path(&path)
+}
+
+pub fn to_path(uri: &str) -> Result<PathBuf, AppError> {
+    let encoded = uri
+        .strip_prefix(FILE_SCHEME)
+        .ok_or_else(|| AppError::InvalidUri(format!("The URI `{uri}` is not a file URI.")))?;
+
+    Ok(PathBuf::from(percent_decode(encoded)?))
+}
+
+pub fn percent_decode(encoded: &str) -> Result<String, AppError> {
+    let mut bytes = Vec::with_capacity(encoded.len());
+    let mut iter = encoded.bytes();
+
+    while let Some(byte) = iter.next() {
+        if byte == b'%' {
+            let hex = [iter.next(), iter.next()];
+            let value = match hex {
+                [Some(high), Some(low)] => std::str::from_utf8(&[high, low])
+                    .ok()
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+                _ => None,
+            }
+            .ok_or_else(|| {
+                AppError::InvalidUri(format!("Invalid percent-encoding in `{encoded}`."))
+            })?;
+            bytes.push(value);
+        } else {
+            bytes.push(byte);
+        }
+    }
+
+    String::from_utf8(bytes).map_err(|err| {
+        AppError::InvalidUri(format!("The URI `{encoded}` is not valid UTF-8: {err}"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{from_path, from_uri_or_path, from_uri_or_relative_path, to_path};
+
+    #[test]
+    fn test_from_path() {
+        let uri = from_path(Path::new("/music/Björk/01 Hyper #1.flac")).unwrap();
+
+        assert_eq!(uri, "file:///music/Bj%C3%B6rk/01%20Hyper%20%231.flac");
+    }
+
+    #[test]
+    fn test_from_relative_path() {
+        assert!(from_path(Path::new("music/a.flac")).is_err());
+    }
+
+    #[test]
+    fn test_from_uri_or_path() {
+        assert_eq!(
+            from_uri_or_path("http://example.com/a.mp3").unwrap(),
+            "http://example.com/a.mp3"
+        );
+        assert_eq!(
+            from_uri_or_path("/music/a b.flac").unwrap(),
+            "file:///music/a%20b.flac"
+        );
+        assert!(from_uri_or_path("a.flac").unwrap().ends_with("/a.flac"));
+    }
+
+    #[test]
+    fn test_from_uri_or_relative_path() {
+        let base_dir = Path::new("/music/playlists");
+
+        assert_eq!(
+            from_uri_or_relative_path("../a b.flac", base_dir).unwrap(),
+            "file:///music/a%20b.flac"
+        );
+        assert_eq!(
+            from_uri_or_relative_path("/music/a.flac", base_dir).unwrap(),
+            "file:///music/a.flac"
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let path = Path::new("/music/a b/c%d?.ogg");
+
+        assert_eq!(to_path(&from_path(path).unwrap()).unwrap(), path);
+    }
+
+    #[test]
+    fn test_to_path_not_file() {
+        assert!(to_path("http://example.com/a.mp3").is_err());
+    }
+}
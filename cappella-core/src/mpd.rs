@@ -0,0 +1,5 @@
+//! A subset of the MPD protocol, for the MPD clients on the desktop and the phones.
+
+pub mod command;
+pub mod events;
+pub mod server;
@@ -0,0 +1,255 @@
+use crate::local::app_error::AppError;
+
+/// The idle subsystems, as named in the protocol.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Subsystem {
+    Database,
+    Playlist,
+    Player,
+    Mixer,
+    Options,
+}
+
+pub const SUBSYSTEMS: [Subsystem; 5] = [
+    Subsystem::Database,
+    Subsystem::Playlist,
+    Subsystem::Player,
+    Subsystem::Mixer,
+    Subsystem::Options,
+];
+
+impl Subsystem {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Subsystem::Database => "database",
+            Subsystem::Playlist => "playlist",
+            Subsystem::Player => "player",
+            Subsystem::Mixer => "mixer",
+            Subsystem::Options => "options",
+        }
+    }
+}
+
+const COMMAND_NAMES: [&str; 21] = [
+    "status",
+    "currentsong",
+    "play",
+    "playid",
+    "pause",
+    "stop",
+    "next",
+    "previous",
+    "seek",
+    "seekcur",
+    "setvol",
+    "playlistinfo",
+    "plchanges",
+    "add",
+    "clear",
+    "noidle",
+    "command_list_begin",
+    "command_list_ok_begin",
+    "command_list_end",
+    "ping",
+    "close",
+];
+
+/// The time argument of `seekcur`.
+#[derive(Debug, PartialEq)]
+pub enum SeekTime {
+    Absolute(f64),
+    Relative(f64),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Status,
+    CurrentSong,
+    /// The song position in the queue.
+    Play(Option<usize>),
+    /// The song id is the position in the queue.
+    PlayId(Option<usize>),
+    /// `None` toggles.
+    Pause(Option<bool>),
+    Stop,
+    Next,
+    Previous,
+    Seek {
+        position: usize,
+        seconds: f64,
+    },
+    SeekCur(SeekTime),
+    SetVol(u8),
+    PlaylistInfo,
+    Add(String),
+    Clear,
+    /// Empty for all the subsystems.
+    Idle(Vec<Subsystem>),
+    NoIdle,
+    CommandListBegin,
+    CommandListOkBegin,
+    CommandListEnd,
+    Ping,
+    Close,
+    Unknown(String),
+}
+
+impl Command {
+    pub fn parse(line: &str) -> Result<Self, AppError> {
+        let args = tokenize(line)?;
+        let (name, args) = args
+            .split_first()
+            .ok_or_else(|| AppError::InvalidInput("No command given".to_owned()))?;
+
+        match (name.as_str(), args) {
+            ("status", []) => Ok(Command::Status),
+            ("currentsong", []) => Ok(Command::CurrentSong),
+            ("play", []) => Ok(Command::Play(None)),
+            ("play", [position]) => Ok(Command::Play(Some(parse_number(position)?))),
+            ("playid", []) => Ok(Command::PlayId(None)),
+            ("playid", [id]) => Ok(Command::PlayId(Some(parse_number(id)?))),
+            ("pause", []) => Ok(Command::Pause(None)),
+            ("pause", [pause]) => Ok(Command::Pause(Some(parse_bool(pause)?))),
+            ("stop", []) => Ok(Command::Stop),
+            ("next", []) => Ok(Command::Next),
+            ("previous", []) => Ok(Command::Previous),
+            ("seek", [position, seconds]) => Ok(Command::Seek {
+                position: parse_number(position)?,
+                seconds: parse_number(seconds)?,
+            }),
+            ("seekcur", [time]) if time.starts_with(['+', '-']) => {
+                Ok(Command::SeekCur(SeekTime::Relative(parse_number(time)?)))
+            }
+            ("seekcur", [time]) => Ok(Command::SeekCur(SeekTime::Absolute(parse_number(time)?))),
+            ("setvol", [volume]) => match parse_number(volume)? {
+                volume @ 0..=100 => Ok(Command::SetVol(volume)),
+                _ => Err(AppError::InvalidInput(format!(
+                    "Invalid volume value: {volume}"
+                ))),
+            },
+            // The whole queue as the changes since any version.
+            ("playlistinfo", []) | ("plchanges", [_]) => Ok(Command::PlaylistInfo),
+            ("add", [uri]) => Ok(Command::Add(uri.to_owned())),
+            ("clear", []) => Ok(Command::Clear),
+            ("idle", subsystems) => Ok(Command::Idle(
+                subsystems
+                    .iter()
+                    .map(|name| parse_subsystem(name))
+                    .collect::<Result<_, _>>()?,
+            )),
+            ("noidle", []) => Ok(Command::NoIdle),
+            ("command_list_begin", []) => Ok(Command::CommandListBegin),
+            ("command_list_ok_begin", []) => Ok(Command::CommandListOkBegin),
+            ("command_list_end", []) => Ok(Command::CommandListEnd),
+            ("ping", []) => Ok(Command::Ping),
+            ("close", []) => Ok(Command::Close),
+            (name, _) if COMMAND_NAMES.contains(&name) => Err(AppError::InvalidInput(format!(
+                "wrong number of arguments for \"{name}\""
+            ))),
+            _ => Ok(Command::Unknown(name.to_owned())),
+        }
+    }
+}
+
+/// The arguments are separated by spaces, or quoted with backslash escapes.
+fn tokenize(line: &str) -> Result<Vec<String>, AppError> {
+    let mut args = Vec::new();
+    let mut chars = line.trim().chars().peekable();
+
+    while let Some(char) = chars.next() {
+        match char {
+            ' ' | '\t' => {}
+            '"' => {
+                let mut arg = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => arg.extend(chars.next()),
+                        Some(char) => arg.push(char),
+                        None => {
+                            return Err(AppError::InvalidInput("Missing closing '\"'".to_owned()))
+                        }
+                    }
+                }
+                args.push(arg);
+            }
+            char => {
+                let mut arg = String::from(char);
+                while let Some(char) = chars.next_if(|char| !char.is_ascii_whitespace()) {
+                    arg.push(char);
+                }
+                args.push(arg);
+            }
+        }
+    }
+
+    Ok(args)
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, AppError> {
+    value
+        .parse()
+        .map_err(|_| AppError::InvalidInput(format!("Number expected: {value}")))
+}
+
+fn parse_bool(value: &str) -> Result<bool, AppError> {
+    match value {
+        "0" => Ok(false),
+        "1" => Ok(true),
+        _ => Err(AppError::InvalidInput(format!(
+            "Boolean (0/1) expected: {value}"
+        ))),
+    }
+}
+
+fn parse_subsystem(name: &str) -> Result<Subsystem, AppError> {
+    SUBSYSTEMS
+        .into_iter()
+        .find(|subsystem| subsystem.as_str() == name)
+        .ok_or_else(|| AppError::InvalidInput(format!("Unrecognized idle event: {name}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tokenize, Command, SeekTime, Subsystem};
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize(r#"add "/music/a \"b\".flac""#).unwrap(),
+            vec!["add", r#"/music/a "b".flac"#]
+        );
+        assert_eq!(tokenize("  seek 2  30 ").unwrap(), vec!["seek", "2", "30"]);
+        assert!(tokenize(r#"add "/music"#).is_err());
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Command::parse("play").unwrap(), Command::Play(None));
+        assert_eq!(
+            Command::parse("pause 1").unwrap(),
+            Command::Pause(Some(true))
+        );
+        assert_eq!(
+            Command::parse("seekcur -5.5").unwrap(),
+            Command::SeekCur(SeekTime::Relative(-5.5))
+        );
+        assert_eq!(
+            Command::parse("idle player mixer").unwrap(),
+            Command::Idle(vec![Subsystem::Player, Subsystem::Mixer])
+        );
+        assert_eq!(
+            Command::parse("outputs").unwrap(),
+            Command::Unknown("outputs".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_parse_err() {
+        assert!(Command::parse("").is_err());
+        assert!(Command::parse("setvol 101").is_err());
+        assert!(Command::parse("pause 2").is_err());
+        assert!(Command::parse("stop now").is_err());
+        assert!(Command::parse("idle nothing").is_err());
+    }
+}
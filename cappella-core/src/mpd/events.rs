@@ -0,0 +1,163 @@
+use std::{
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+};
+
+use parking_lot::Mutex;
+
+use crate::{
+    frontend::{message::Message, pipe::Pipe},
+    local::{app_error::AppError, mutex_lock_timeout::MutexLockTimeout},
+    streamer::now_playing::NowPlaying,
+};
+
+use super::command::Subsystem;
+
+/// Returns `false` when the connection is closed.
+pub type Listener = Box<dyn Fn(Subsystem) -> bool + Send>;
+
+/// Forwards the events to the next pipe and wakes up the idle connections.
+pub struct Events {
+    next: Arc<dyn Pipe>,
+    listeners: Mutex<Vec<Listener>>,
+    playlist_version: AtomicU32,
+    now_playing: Mutex<Option<NowPlaying>>,
+}
+
+pub fn new_arc(next: Arc<dyn Pipe>) -> Arc<Events> {
+    Arc::new(Events {
+        next,
+        listeners: Mutex::default(),
+        playlist_version: AtomicU32::new(1),
+        now_playing: Mutex::default(),
+    })
+}
+
+impl Events {
+    pub fn add(&self, listener: Listener) -> Result<(), AppError> {
+        self.listeners.try_lock_default_duration()?.push(listener);
+
+        Ok(())
+    }
+
+    /// A change of the queue increments the playlist version, whichever client made it.
+    pub fn notify(&self, subsystem: Subsystem) {
+        if subsystem == Subsystem::Playlist {
+            self.playlist_version.fetch_add(1, Ordering::Relaxed);
+        }
+
+        match self.listeners.try_lock_default_duration() {
+            Ok(mut listeners) => listeners.retain(|listener| listener(subsystem)),
+            Err(err) => eprintln!("Unable to notify the MPD connections: {err}"),
+        }
+    }
+
+    pub fn playlist_version(&self) -> u32 {
+        self.playlist_version.load(Ordering::Relaxed)
+    }
+
+    /// The tags of the playing track, for `currentsong`.
+    pub fn now_playing(&self) -> Result<Option<NowPlaying>, AppError> {
+        Ok(self.now_playing.try_lock_default_duration()?.clone())
+    }
+
+    fn set_now_playing(&self, now_playing: NowPlaying) {
+        match self.now_playing.try_lock_default_duration() {
+            Ok(mut current) => *current = Some(now_playing),
+            Err(err) => eprintln!("Unable to keep the playing track for MPD: {err}"),
+        }
+    }
+}
+
+impl Debug for Events {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Events")
+            .field("next", &self.next)
+            .field("playlist_version", &self.playlist_version)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Pipe for Events {
+    fn send(&self, frontend_message: Message) {
+        match &frontend_message {
+            Message::TrackStarted(uri) => self.set_now_playing(NowPlaying::new(uri)),
+            Message::NowPlaying(now_playing) => self.set_now_playing(now_playing.clone()),
+            _ => {}
+        }
+
+        let subsystem = subsystem(&frontend_message);
+
+        self.next.send(frontend_message);
+
+        if let Some(subsystem) = subsystem {
+            self.notify(subsystem);
+        }
+    }
+}
+
+fn subsystem(message: &Message) -> Option<Subsystem> {
+    match message {
+        Message::StateChanged(_) | Message::TrackStarted(_) | Message::NowPlaying(_) => {
+            Some(Subsystem::Player)
+        }
+        Message::VolumeChanged { .. } => Some(Subsystem::Mixer),
+        Message::QueueChanged => Some(Subsystem::Playlist),
+        Message::LibraryScanFinished { .. } => Some(Subsystem::Database),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{mpsc, Arc};
+
+    use crate::frontend::{
+        message::{Message, PlayerState},
+        pipe::Pipe,
+    };
+
+    use super::{new_arc, Subsystem};
+
+    #[derive(Debug)]
+    struct MockPipe;
+
+    impl Pipe for MockPipe {
+        fn send(&self, _frontend_message: Message) {}
+    }
+
+    #[test]
+    fn test_send() {
+        let events = new_arc(Arc::new(MockPipe));
+        let (sender, receiver) = mpsc::channel();
+        events
+            .add(Box::new(move |subsystem| sender.send(subsystem).is_ok()))
+            .unwrap();
+
+        events.send(Message::Position {
+            position_ns: 0,
+            duration_ns: 0,
+        });
+        events.send(Message::StateChanged(PlayerState::Playing));
+        events.send(Message::QueueChanged);
+
+        assert_eq!(
+            receiver.try_iter().collect::<Vec<_>>(),
+            vec![Subsystem::Player, Subsystem::Playlist]
+        );
+        assert_eq!(events.playlist_version(), 2);
+    }
+
+    #[test]
+    fn test_closed_listener_is_removed() {
+        let events = new_arc(Arc::new(MockPipe));
+        events.add(Box::new(|_| false)).unwrap();
+
+        events.notify(Subsystem::Mixer);
+
+        assert!(events.listeners.lock().is_empty());
+    }
+}
@@ -0,0 +1,614 @@
+use std::{
+    collections::BTreeSet,
+    fmt::Write as _,
+    io::{BufRead, BufReader, Write},
+    net::{Shutdown, TcpListener, TcpStream},
+    path::{Component, Path},
+    sync::{mpsc, Arc},
+    thread,
+};
+
+use crate::{
+    control::request,
+    frontend::message::PlayerState,
+    library::track::Folder,
+    local::{app_error::AppError, state::State, uri},
+    player::{front::Status, order::Repeat},
+    streamer::now_playing::NowPlaying,
+};
+
+use super::{
+    command::{Command, SeekTime, Subsystem, SUBSYSTEMS},
+    events::Events,
+};
+
+const THREAD_NAME: &str = "mpd";
+const GREETING: &str = "OK MPD 0.23.5";
+const COMMAND_LIST_END: &str = "command_list_end";
+
+const ACK_ERROR_ARG: u32 = 2;
+const ACK_ERROR_UNKNOWN: u32 = 5;
+const ACK_ERROR_NO_EXIST: u32 = 50;
+const ACK_ERROR_SYSTEM: u32 = 52;
+
+const VOLUME_PERCENT: f64 = 100.0;
+const NANOS_PER_SECOND: f64 = 1_000_000_000.0;
+
+/// Listens in background, one thread per client.
+pub fn start(address: &str, state: State, events: Arc<Events>) -> Result<(), AppError> {
+    let listener = TcpListener::bind(address)?;
+
+    thread::Builder::new()
+        .name(THREAD_NAME.to_owned())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                let state = state.clone();
+                let events = events.clone();
+
+                let result = stream.map_err(AppError::from).and_then(|stream| {
+                    thread::Builder::new()
+                        .name(THREAD_NAME.to_owned())
+                        .spawn(move || {
+                            if let Err(err) = handle_connection(stream, &state, &events) {
+                                eprintln!("MPD connection error: {err}");
+                            }
+                        })
+                        .map_err(AppError::from)
+                });
+
+                if let Err(err) = result {
+                    eprintln!("MPD connection error: {err}");
+                }
+            }
+        })?;
+
+    Ok(())
+}
+
+/// The lines and the changes are received on the same channel, for `idle`.
+enum Input {
+    Line(String),
+    Changed(Subsystem),
+    Closed,
+}
+
+enum Output {
+    None,
+    Text(String),
+    Close,
+}
+
+fn handle_connection(stream: TcpStream, state: &State, events: &Events) -> Result<(), AppError> {
+    let mut writer = stream.try_clone()?;
+    let (sender, receiver) = mpsc::channel();
+
+    let line_sender = sender.clone();
+    let reader = BufReader::new(stream);
+    thread::Builder::new()
+        .name(THREAD_NAME.to_owned())
+        .spawn(move || {
+            for line in reader.lines().map_while(Result::ok) {
+                if line_sender.send(Input::Line(line)).is_err() {
+                    return;
+                }
+            }
+            line_sender.send(Input::Closed).ok();
+        })?;
+
+    events.add(Box::new(move |subsystem| {
+        sender.send(Input::Changed(subsystem)).is_ok()
+    }))?;
+
+    writeln!(writer, "{GREETING}")?;
+
+    let mut session = Session::new(state, events);
+    for input in receiver {
+        let output = match input {
+            Input::Line(line) => session.on_line(&line),
+            Input::Changed(subsystem) => session.on_changed(subsystem),
+            Input::Closed => Output::Close,
+        };
+
+        match output {
+            Output::None => {}
+            Output::Text(text) => writer.write_all(text.as_bytes())?,
+            Output::Close => break,
+        }
+    }
+
+    // Ends the reader thread.
+    writer.shutdown(Shutdown::Both).ok();
+
+    Ok(())
+}
+
+struct CommandList {
+    /// `list_OK` after each command.
+    is_ok: bool,
+    lines: Vec<String>,
+}
+
+struct Session<'a> {
+    state: &'a State,
+    events: &'a Events,
+    /// The changes not reported by `idle` yet.
+    pending: BTreeSet<Subsystem>,
+    idle: Option<Vec<Subsystem>>,
+    command_list: Option<CommandList>,
+}
+
+impl<'a> Session<'a> {
+    fn new(state: &'a State, events: &'a Events) -> Self {
+        Self {
+            state,
+            events,
+            pending: BTreeSet::new(),
+            idle: None,
+            command_list: None,
+        }
+    }
+
+    fn on_changed(&mut self, subsystem: Subsystem) -> Output {
+        self.pending.insert(subsystem);
+
+        match self.idle.take() {
+            Some(subsystems) => self.idle(subsystems),
+            None => Output::None,
+        }
+    }
+
+    fn on_line(&mut self, line: &str) -> Output {
+        if let Some(command_list) = &mut self.command_list {
+            if line.trim() != COMMAND_LIST_END {
+                command_list.lines.push(line.to_owned());
+                return Output::None;
+            }
+            if let Some(command_list) = self.command_list.take() {
+                return Output::Text(self.run_list(command_list));
+            }
+        }
+
+        // Only `noidle` is expected while idle.
+        if self.idle.is_some() {
+            return match Command::parse(line) {
+                Ok(Command::NoIdle) => {
+                    self.idle = None;
+                    Output::Text("OK\n".to_owned())
+                }
+                _ => Output::Close,
+            };
+        }
+
+        match Command::parse(line) {
+            Ok(Command::CommandListBegin) => self.begin_list(false),
+            Ok(Command::CommandListOkBegin) => self.begin_list(true),
+            Ok(Command::Idle(subsystems)) => self.idle(subsystems),
+            Ok(Command::NoIdle) => Output::None,
+            Ok(Command::Close) => Output::Close,
+            command => Output::Text(match self.run(command, line, 0) {
+                Ok(text) => text + "OK\n",
+                Err(ack) => ack,
+            }),
+        }
+    }
+
+    fn begin_list(&mut self, is_ok: bool) -> Output {
+        self.command_list = Some(CommandList {
+            is_ok,
+            lines: Vec::new(),
+        });
+
+        Output::None
+    }
+
+    /// Stops on the first error.
+    fn run_list(&mut self, command_list: CommandList) -> String {
+        let mut text = String::new();
+
+        for (index, line) in command_list.lines.iter().enumerate() {
+            match self.run(Command::parse(line), line, index) {
+                Ok(command_text) => text += &command_text,
+                Err(ack) => return text + &ack,
+            }
+            if command_list.is_ok {
+                text += "list_OK\n";
+            }
+        }
+
+        text + "OK\n"
+    }
+
+    /// Returns immediately if a change is pending.
+    fn idle(&mut self, subsystems: Vec<Subsystem>) -> Output {
+        let subsystems = match subsystems.is_empty() {
+            true => SUBSYSTEMS.to_vec(),
+            false => subsystems,
+        };
+        let changed: Vec<Subsystem> = self
+            .pending
+            .iter()
+            .filter(|subsystem| subsystems.contains(subsystem))
+            .copied()
+            .collect();
+
+        if changed.is_empty() {
+            self.idle = Some(subsystems);
+            return Output::None;
+        }
+
+        let mut text = String::new();
+        for subsystem in changed {
+            self.pending.remove(&subsystem);
+            text += &format!("changed: {}\n", subsystem.as_str());
+        }
+
+        Output::Text(text + "OK\n")
+    }
+
+    /// The response without the final `OK`, or the `ACK` line.
+    fn run(
+        &mut self,
+        command: Result<Command, AppError>,
+        line: &str,
+        index: usize,
+    ) -> Result<String, String> {
+        let name = line.split_whitespace().next().unwrap_or_default();
+
+        match command {
+            Ok(Command::Unknown(name)) => Err(ack(
+                ACK_ERROR_UNKNOWN,
+                index,
+                "",
+                &format!("unknown command \"{name}\""),
+            )),
+            Ok(command) => self
+                .execute(command)
+                .map_err(|err| ack(ack_code(&err), index, name, &err.to_string())),
+            Err(err) => Err(ack(ack_code(&err), index, name, &err.to_string())),
+        }
+    }
+
+    fn execute(&mut self, command: Command) -> Result<String, AppError> {
+        let player_front = self.state.player_front();
+
+        match command {
+            Command::Status => Ok(status_lines(
+                &player_front.status()?,
+                player_front.queue()?.len(),
+                self.events.playlist_version(),
+            )),
+            Command::CurrentSong => {
+                let status = player_front.status()?;
+                match (status.queue_index, &status.uri) {
+                    (Some(index), Some(uri)) => Ok(song_lines(
+                        index,
+                        uri,
+                        self.events.now_playing()?.as_ref(),
+                        status.duration_ns,
+                    )),
+                    _ => Ok(String::new()),
+                }
+            }
+            Command::Play(position) | Command::PlayId(position) => {
+                let status = player_front.status()?;
+                match (position, status.state) {
+                    (Some(position), _) => player_front.jump_to(position)?,
+                    (None, PlayerState::Stopped) => {
+                        player_front.jump_to(status.queue_index.unwrap_or_default())?
+                    }
+                    // The pause of the streamer is a toggle.
+                    (None, PlayerState::Paused) => player_front.pause()?,
+                    (None, PlayerState::Playing) => {}
+                }
+                Ok(String::new())
+            }
+            Command::Pause(pause) => {
+                let state = player_front.status()?.state;
+                let is_toggle = match pause {
+                    Some(true) => state == PlayerState::Playing,
+                    Some(false) => state == PlayerState::Paused,
+                    None => state != PlayerState::Stopped,
+                };
+                if is_toggle {
+                    player_front.pause()?;
+                }
+                Ok(String::new())
+            }
+            Command::Stop => {
+                player_front.stop()?;
+                Ok(String::new())
+            }
+            Command::Next => {
+                player_front.next()?;
+                Ok(String::new())
+            }
+            Command::Previous => {
+                player_front.previous()?;
+                Ok(String::new())
+            }
+            Command::Seek { position, seconds } => {
+                if player_front.status()?.queue_index != Some(position) {
+                    return Err(AppError::InvalidInput(
+                        "Only the current song can be seeked".to_owned(),
+                    ));
+                }
+                self.seek(seconds)
+            }
+            Command::SeekCur(SeekTime::Absolute(seconds)) => self.seek(seconds),
+            Command::SeekCur(SeekTime::Relative(seconds)) => {
+                let position_ns = player_front.status()?.position_ns.unwrap_or_default();
+                self.seek(position_ns as f64 / NANOS_PER_SECOND + seconds)
+            }
+            Command::SetVol(volume) => {
+                let volume = player_front.set_volume(f64::from(volume) / VOLUME_PERCENT)?;
+                request::send_volume_changed(self.state, volume);
+                Ok(String::new())
+            }
+            Command::PlaylistInfo => {
+                let now_playing = self.events.now_playing()?;
+                Ok(player_front
+                    .queue()?
+                    .iter()
+                    .enumerate()
+                    .map(|(index, uri)| song_lines(index, uri, now_playing.as_ref(), None))
+                    .collect())
+            }
+            Command::Add(path) => {
+                let folders = self.state.library_front().library_folders()?;
+                player_front.enqueue(vec![library_uri(&path, &folders)?])?;
+                Ok(String::new())
+            }
+            Command::Clear => {
+                player_front.clear_queue()?;
+                Ok(String::new())
+            }
+            Command::Ping => Ok(String::new()),
+            Command::Idle(_)
+            | Command::NoIdle
+            | Command::CommandListBegin
+            | Command::CommandListOkBegin
+            | Command::CommandListEnd
+            | Command::Close
+            | Command::Unknown(_) => Err(AppError::InvalidInput(
+                "Not allowed in a command list".to_owned(),
+            )),
+        }
+    }
+
+    fn seek(&self, seconds: f64) -> Result<String, AppError> {
+        let position_ns = (seconds.max(0.0) * NANOS_PER_SECOND) as i64;
+        self.state.player_front().seek(position_ns)?;
+        self.events.notify(Subsystem::Player);
+
+        Ok(String::new())
+    }
+}
+
+/// As in MPD, a path is relative to the music folders, a URI is kept as is.
+fn library_uri(value: &str, folders: &[Folder]) -> Result<String, AppError> {
+    if value.contains("://") {
+        return Ok(value.to_owned());
+    }
+
+    let path = Path::new(value);
+
+    if !path
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+    {
+        return Err(AppError::InvalidPath(format!(
+            "The path `{value}` is not relative to a library folder."
+        )));
+    }
+
+    let path = folders
+        .iter()
+        .map(|folder| folder.path.join(path))
+        .find(|path| path.exists())
+        .ok_or_else(|| {
+            AppError::InvalidPath(format!("The path `{value}` is not in the library."))
+        })?;
+
+    uri::from_path(&path)
+}
+
+fn ack(code: u32, index: usize, name: &str, message: &str) -> String {
+    format!("ACK [{code}@{index}] {{{name}}} {message}\n")
+}
+
+fn ack_code(err: &AppError) -> u32 {
+    match err {
+        AppError::InvalidInput(_) | AppError::InvalidUri(_) | AppError::InvalidPath(_) => {
+            ACK_ERROR_ARG
+        }
+        AppError::OutOfRange { .. } => ACK_ERROR_NO_EXIST,
+        _ => ACK_ERROR_SYSTEM,
+    }
+}
+
+fn status_lines(status: &Status, playlist_length: usize, playlist_version: u32) -> String {
+    let volume = match status.volume.muted {
+        true => 0.0,
+        false => (status.volume.volume * VOLUME_PERCENT).round(),
+    };
+    let state = match status.state {
+        PlayerState::Stopped => "stop",
+        PlayerState::Paused => "pause",
+        PlayerState::Playing => "play",
+    };
+
+    // MPD repeats one track with both `repeat` and `single`.
+    let (repeat, single) = match status.repeat {
+        Repeat::Off => (0, 0),
+        Repeat::One => (1, 1),
+        Repeat::All => (1, 0),
+    };
+    let random = u8::from(status.shuffle);
+
+    let mut text = format!(
+        "volume: {volume}\nrepeat: {repeat}\nrandom: {random}\nsingle: {single}\nconsume: 0\n\
+         playlist: {playlist_version}\nplaylistlength: {playlist_length}\nstate: {state}\n"
+    );
+
+    if let Some(index) = status.queue_index {
+        writeln!(text, "song: {index}\nsongid: {index}").ok();
+    }
+
+    if status.state != PlayerState::Stopped {
+        let elapsed = status.position_ns.unwrap_or_default() as f64 / NANOS_PER_SECOND;
+        match status.duration_ns.filter(|duration_ns| *duration_ns >= 0) {
+            Some(duration_ns) => {
+                let duration = duration_ns as f64 / NANOS_PER_SECOND;
+                writeln!(
+                    text,
+                    "time: {}:{}\nelapsed: {elapsed:.3}\nduration: {duration:.3}",
+                    elapsed as u64, duration as u64
+                )
+                .ok();
+            }
+            None => {
+                writeln!(text, "time: {}:0\nelapsed: {elapsed:.3}", elapsed as u64).ok();
+            }
+        }
+    }
+
+    text
+}
+
+/// The tags are known only for the playing track.
+fn song_lines(
+    index: usize,
+    uri: &str,
+    now_playing: Option<&NowPlaying>,
+    duration_ns: Option<i64>,
+) -> String {
+    let mut text = format!("file: {uri}\n");
+
+    if let Some(now_playing) = now_playing.filter(|now_playing| now_playing.uri == uri) {
+        let tags = [
+            ("Title", &now_playing.title),
+            ("Artist", &now_playing.artist),
+            ("Album", &now_playing.album),
+            ("AlbumArtist", &now_playing.album_artist),
+            ("Genre", &now_playing.genre),
+        ];
+        for (name, value) in tags {
+            if let Some(value) = value {
+                writeln!(text, "{name}: {value}").ok();
+            }
+        }
+    }
+
+    if let Some(duration_ns) = duration_ns.filter(|duration_ns| *duration_ns >= 0) {
+        let duration = duration_ns as f64 / NANOS_PER_SECOND;
+        writeln!(text, "Time: {}\nduration: {duration:.3}", duration as u64).ok();
+    }
+
+    writeln!(text, "Pos: {index}\nId: {index}").ok();
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use crate::{
+        frontend::message::PlayerState,
+        library::track::Folder,
+        local::{app_error::AppError, uri},
+        player::{
+            front::{Status, Volume},
+            order::Repeat,
+        },
+        streamer::now_playing::NowPlaying,
+    };
+
+    use super::{ack, ack_code, library_uri, song_lines, status_lines};
+
+    #[test]
+    fn test_status_lines() {
+        let status = Status {
+            state: PlayerState::Playing,
+            uri: Some("file:///a.flac".to_owned()),
+            position_ns: Some(61_500_000_000),
+            duration_ns: Some(180_000_000_000),
+            seekable: true,
+            volume: Volume {
+                volume: 0.5,
+                muted: false,
+            },
+            queue_index: Some(1),
+            repeat: Repeat::Off,
+            shuffle: false,
+        };
+
+        assert_eq!(
+            status_lines(&status, 3, 7),
+            "volume: 50\nrepeat: 0\nrandom: 0\nsingle: 0\nconsume: 0\nplaylist: 7\n\
+             playlistlength: 3\nstate: play\nsong: 1\nsongid: 1\ntime: 61:180\n\
+             elapsed: 61.500\nduration: 180.000\n"
+        );
+
+        let status = Status {
+            repeat: Repeat::One,
+            shuffle: true,
+            ..status
+        };
+
+        assert!(status_lines(&status, 3, 7)
+            .starts_with("volume: 50\nrepeat: 1\nrandom: 1\nsingle: 1\nconsume: 0\n"));
+    }
+
+    #[test]
+    fn test_song_lines() {
+        let now_playing = NowPlaying {
+            title: Some("Fragments".to_owned()),
+            ..NowPlaying::new("file:///a.mp3")
+        };
+
+        assert_eq!(
+            song_lines(0, "file:///a.mp3", Some(&now_playing), Some(3_000_000_000)),
+            "file: file:///a.mp3\nTitle: Fragments\nTime: 3\nduration: 3.000\nPos: 0\nId: 0\n"
+        );
+        assert_eq!(
+            song_lines(1, "file:///b.mp3", Some(&now_playing), None),
+            "file: file:///b.mp3\nPos: 1\nId: 1\n"
+        );
+    }
+
+    #[test]
+    fn test_library_uri() {
+        let root = env::temp_dir().join(format!("cappella-mpd-{}", process::id()));
+        fs::create_dir_all(root.join("album")).unwrap();
+        fs::write(root.join("album/01.flac"), b"").unwrap();
+        let folders = [Folder {
+            id: 1,
+            path: root.clone(),
+        }];
+
+        assert_eq!(
+            library_uri("album/01.flac", &folders).unwrap(),
+            uri::from_path(&root.join("album/01.flac")).unwrap()
+        );
+        assert_eq!(
+            library_uri("http://radio.example/live", &folders).unwrap(),
+            "http://radio.example/live"
+        );
+        assert!(library_uri("album/02.flac", &folders).is_err());
+        assert!(library_uri("../album/01.flac", &folders).is_err());
+        assert!(library_uri("/etc/passwd", &folders).is_err());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_ack() {
+        let err = AppError::OutOfRange { index: 4, len: 2 };
+
+        assert_eq!(
+            ack(ack_code(&err), 1, "play", &err.to_string()),
+            "ACK [50@1] {play} The index `4` is out of range (length: 2).\n"
+        );
+    }
+}
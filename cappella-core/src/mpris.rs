@@ -0,0 +1,5 @@
+//! MPRIS2 on the session bus, for the media keys, the desktop widgets and `playerctl`.
+
+pub mod bridge;
+pub mod interfaces;
+pub mod values;
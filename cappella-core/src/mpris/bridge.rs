@@ -0,0 +1,144 @@
+use std::{
+    fmt::Debug,
+    process,
+    sync::{
+        mpsc::{self, Sender},
+        Arc,
+    },
+    thread,
+};
+
+use parking_lot::Mutex;
+use zbus::blocking::{connection, Connection};
+
+use crate::{
+    frontend::{message::Message, pipe::Pipe},
+    local::{app_error::AppError, mutex_lock_timeout::MutexLockTimeout, state::State},
+};
+
+use super::{
+    interfaces::{MediaPlayer2, Player},
+    values::{Change, Values},
+};
+
+pub const BUS_NAME: &str = "org.mpris.MediaPlayer2.cappella";
+pub const OBJECT_PATH: &str = "/org/mpris/MediaPlayer2";
+
+const THREAD_NAME: &str = "mpris";
+
+/// Forwards the events to the next pipe and signals the changes on the session bus.
+pub struct Bridge {
+    next: Arc<dyn Pipe>,
+    sender: Mutex<Option<Sender<Message>>>,
+}
+
+pub fn new_arc(next: Arc<dyn Pipe>) -> Arc<Bridge> {
+    Arc::new(Bridge {
+        next,
+        sender: Mutex::default(),
+    })
+}
+
+impl Bridge {
+    /// Before the start, the events are only forwarded.
+    pub fn start(&self, state: State) -> Result<(), AppError> {
+        let values = Arc::new(Mutex::new(Values {
+            volume: state.player_front().volume()?.volume,
+            ..Values::default()
+        }));
+
+        let connection = connection::Builder::session()?
+            .serve_at(OBJECT_PATH, MediaPlayer2)?
+            .serve_at(OBJECT_PATH, Player::new(state, values.clone()))?
+            .build()?;
+
+        // Another instance has the name, as suggested by the specification.
+        if connection.request_name(BUS_NAME).is_err() {
+            connection.request_name(format!("{BUS_NAME}.instance{}", process::id()))?;
+        }
+
+        // The bus calls are out of the streamer thread.
+        let (sender, receiver) = mpsc::channel::<Message>();
+        thread::Builder::new()
+            .name(THREAD_NAME.to_owned())
+            .spawn(move || {
+                for message in receiver {
+                    let changes = match values.try_lock_default_duration() {
+                        Ok(mut values) => values.update(&message),
+                        Err(err) => {
+                            eprintln!("Unable to update the MPRIS values: {err}");
+                            continue;
+                        }
+                    };
+
+                    for change in changes {
+                        if let Err(err) = signal(&connection, change) {
+                            eprintln!("Unable to signal the MPRIS change: {err}");
+                        }
+                    }
+                }
+            })?;
+
+        *self.sender.try_lock_default_duration()? = Some(sender);
+
+        Ok(())
+    }
+}
+
+impl Debug for Bridge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Bridge")
+            .field("next", &self.next)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Pipe for Bridge {
+    fn send(&self, frontend_message: Message) {
+        let is_signaled = matches!(
+            frontend_message,
+            Message::Position { .. }
+                | Message::StateChanged(_)
+                | Message::TrackStarted(_)
+                | Message::NowPlaying(_)
+                | Message::VolumeChanged { .. }
+        );
+
+        if is_signaled {
+            match self.sender.try_lock_default_duration() {
+                Ok(sender) => {
+                    if let Some(sender) = sender.as_ref() {
+                        sender
+                            .send(frontend_message.clone())
+                            .unwrap_or_else(|err| eprintln!("MPRIS channel error: {err}"));
+                    }
+                }
+                Err(err) => eprintln!("Unable to send the event to MPRIS: {err}"),
+            }
+        }
+
+        self.next.send(frontend_message);
+    }
+}
+
+fn signal(connection: &Connection, change: Change) -> Result<(), AppError> {
+    let player = connection
+        .object_server()
+        .interface::<_, Player>(OBJECT_PATH)?;
+    let emitter = player.signal_emitter();
+    let player = player.get();
+
+    zbus::block_on(async {
+        match change {
+            Change::PlaybackStatus => player.playback_status_changed(emitter).await,
+            Change::Metadata => {
+                player.metadata_changed(emitter).await?;
+                player.can_seek_changed(emitter).await
+            }
+            Change::Volume => player.volume_changed(emitter).await,
+            Change::Seeked(position_us) => Player::seeked(emitter, position_us).await,
+        }
+    })?;
+
+    Ok(())
+}
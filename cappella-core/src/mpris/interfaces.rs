@@ -0,0 +1,264 @@
+use std::{collections::HashMap, sync::Arc};
+
+use parking_lot::Mutex;
+use zbus::{
+    fdo, interface,
+    object_server::SignalEmitter,
+    zvariant::{ObjectPath, OwnedValue},
+};
+
+use crate::{
+    control::request,
+    frontend::message::PlayerState,
+    local::{app_error::AppError, mutex_lock_timeout::MutexLockTimeout, state::State},
+};
+
+use super::values::{self, Values};
+
+const IDENTITY: &str = "Cappella";
+const URI_SCHEMES: [&str; 3] = ["file", "http", "https"];
+const RATE: f64 = 1.0;
+
+impl From<AppError> for fdo::Error {
+    fn from(value: AppError) -> Self {
+        fdo::Error::Failed(value.to_string())
+    }
+}
+
+/// `org.mpris.MediaPlayer2`, the application itself.
+#[derive(Debug)]
+pub struct MediaPlayer2;
+
+#[interface(name = "org.mpris.MediaPlayer2")]
+impl MediaPlayer2 {
+    /// Not supported, `CanRaise` is false.
+    fn raise(&self) {}
+
+    /// Not supported, `CanQuit` is false.
+    fn quit(&self) {}
+
+    #[zbus(property)]
+    fn can_quit(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn can_raise(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn has_track_list(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn identity(&self) -> &str {
+        IDENTITY
+    }
+
+    #[zbus(property)]
+    fn supported_uri_schemes(&self) -> Vec<String> {
+        URI_SCHEMES.map(str::to_owned).to_vec()
+    }
+
+    #[zbus(property)]
+    fn supported_mime_types(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// `org.mpris.MediaPlayer2.Player`, the commands go to the player front.
+pub struct Player {
+    state: State,
+    values: Arc<Mutex<Values>>,
+}
+
+impl Player {
+    pub fn new(state: State, values: Arc<Mutex<Values>>) -> Self {
+        Self { state, values }
+    }
+
+    fn player_state(&self) -> Result<PlayerState, AppError> {
+        Ok(self.state.player_front().status()?.state)
+    }
+
+    /// The pause of the streamer is a toggle.
+    fn toggle_or_start(&self) -> Result<(), AppError> {
+        let player_front = self.state.player_front();
+        let status = player_front.status()?;
+
+        match status.state {
+            PlayerState::Stopped => player_front.jump_to(status.queue_index.unwrap_or_default()),
+            PlayerState::Paused | PlayerState::Playing => player_front.pause(),
+        }
+    }
+}
+
+#[interface(name = "org.mpris.MediaPlayer2.Player")]
+impl Player {
+    fn next(&self) -> fdo::Result<()> {
+        Ok(self.state.player_front().next()?)
+    }
+
+    fn previous(&self) -> fdo::Result<()> {
+        Ok(self.state.player_front().previous()?)
+    }
+
+    fn pause(&self) -> fdo::Result<()> {
+        if self.player_state()? == PlayerState::Playing {
+            self.state.player_front().pause()?;
+        }
+        Ok(())
+    }
+
+    fn play_pause(&self) -> fdo::Result<()> {
+        Ok(self.toggle_or_start()?)
+    }
+
+    fn stop(&self) -> fdo::Result<()> {
+        Ok(self.state.player_front().stop()?)
+    }
+
+    fn play(&self) -> fdo::Result<()> {
+        if self.player_state()? != PlayerState::Playing {
+            self.toggle_or_start()?;
+        }
+        Ok(())
+    }
+
+    /// The offset is in microseconds, relative to the current position.
+    fn seek(&self, offset: i64) -> fdo::Result<()> {
+        let (position_ns, duration_ns) = {
+            let values = self.values.try_lock_default_duration()?;
+            (values.position_ns, values.duration_ns)
+        };
+        let position_ns = position_ns.saturating_add(values::to_nanos(offset)).max(0);
+
+        match duration_ns {
+            Some(duration_ns) if position_ns > duration_ns => {
+                Ok(self.state.player_front().next()?)
+            }
+            Some(_) => Ok(self.state.player_front().seek(position_ns)?),
+            None => Err(fdo::Error::NotSupported(
+                "The stream is not seekable.".to_owned(),
+            )),
+        }
+    }
+
+    /// Ignored if the track is not the current one anymore.
+    fn set_position(&self, track_id: ObjectPath<'_>, position: i64) -> fdo::Result<()> {
+        let (current_track_id, duration_ns) = {
+            let values = self.values.try_lock_default_duration()?;
+            (values.track_id(), values.duration_ns)
+        };
+        let position_ns = values::to_nanos(position);
+
+        match duration_ns {
+            Some(duration_ns)
+                if track_id.as_str() == current_track_id
+                    && (0..=duration_ns).contains(&position_ns) =>
+            {
+                Ok(self.state.player_front().seek(position_ns)?)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn open_uri(&self, uri: &str) -> fdo::Result<()> {
+        Ok(self.state.player_front().play(uri)?)
+    }
+
+    /// The new position in microseconds, after a jump.
+    #[zbus(signal)]
+    pub async fn seeked(emitter: &SignalEmitter<'_>, position: i64) -> zbus::Result<()>;
+
+    #[zbus(property)]
+    fn playback_status(&self) -> fdo::Result<&str> {
+        Ok(self.values.try_lock_default_duration()?.playback_status())
+    }
+
+    #[zbus(property)]
+    fn rate(&self) -> f64 {
+        RATE
+    }
+
+    #[zbus(property)]
+    fn set_rate(&self, _rate: f64) {}
+
+    #[zbus(property)]
+    fn minimum_rate(&self) -> f64 {
+        RATE
+    }
+
+    #[zbus(property)]
+    fn maximum_rate(&self) -> f64 {
+        RATE
+    }
+
+    #[zbus(property)]
+    fn metadata(&self) -> fdo::Result<HashMap<String, OwnedValue>> {
+        self.values
+            .try_lock_default_duration()?
+            .metadata()
+            .map_err(|err| fdo::Error::Failed(err.to_string()))
+    }
+
+    /// On the same cubic scale as the volume of the player.
+    #[zbus(property)]
+    fn volume(&self) -> fdo::Result<f64> {
+        Ok(self.values.try_lock_default_duration()?.volume)
+    }
+
+    /// The value is updated before the change signal sent on return.
+    #[zbus(property)]
+    fn set_volume(&self, volume: f64) -> fdo::Result<()> {
+        let volume = self.state.player_front().set_volume(volume)?;
+        self.values.try_lock_default_duration()?.volume = volume.volume;
+        request::send_volume_changed(&self.state, volume);
+        Ok(())
+    }
+
+    /// In microseconds, not signaled as the position changes continuously.
+    #[zbus(property(emits_changed_signal = "false"))]
+    fn position(&self) -> fdo::Result<i64> {
+        Ok(values::to_micros(
+            self.values.try_lock_default_duration()?.position_ns,
+        ))
+    }
+
+    #[zbus(property)]
+    fn can_go_next(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn can_go_previous(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn can_play(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn can_pause(&self) -> bool {
+        true
+    }
+
+    /// A live stream has no duration.
+    #[zbus(property)]
+    fn can_seek(&self) -> fdo::Result<bool> {
+        Ok(self
+            .values
+            .try_lock_default_duration()?
+            .duration_ns
+            .is_some())
+    }
+
+    #[zbus(property(emits_changed_signal = "const"))]
+    fn can_control(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+
+use zbus::zvariant::{ObjectPath, OwnedValue, Value};
+
+use crate::{
+    frontend::message::{Message, PlayerState},
+    streamer::now_playing::NowPlaying,
+};
+
+pub const NO_TRACK: &str = "/org/mpris/MediaPlayer2/TrackList/NoTrack";
+
+const TRACK_PATH: &str = "/me/tabinol/cappella/track";
+const NANOS_PER_MICRO: i64 = 1_000;
+/// A position jump larger than this between two position events is a seek.
+const SEEK_THRESHOLD_NS: i64 = 1_000_000_000;
+
+/// The player as seen by MPRIS, kept up to date from the frontend events.
+#[derive(Debug, Default)]
+pub struct Values {
+    pub state: PlayerState,
+    /// Incremented on each track, for a new MPRIS track id even if the URI is the same.
+    pub track_number: u64,
+    pub now_playing: Option<NowPlaying>,
+    pub position_ns: i64,
+    pub duration_ns: Option<i64>,
+    pub volume: f64,
+}
+
+/// What to signal on the bus after an event.
+#[derive(Debug, PartialEq)]
+pub enum Change {
+    PlaybackStatus,
+    Metadata,
+    Volume,
+    /// The new position in microseconds.
+    Seeked(i64),
+}
+
+impl Values {
+    pub fn update(&mut self, message: &Message) -> Vec<Change> {
+        match message {
+            Message::StateChanged(state) if *state != self.state => {
+                self.state = state.clone();
+                if *state == PlayerState::Stopped {
+                    self.position_ns = 0;
+                }
+                vec![Change::PlaybackStatus]
+            }
+            Message::TrackStarted(uri) => {
+                self.track_number += 1;
+                self.now_playing = Some(NowPlaying::new(uri));
+                self.position_ns = 0;
+                self.duration_ns = None;
+                vec![Change::Metadata]
+            }
+            Message::NowPlaying(now_playing) if self.now_playing.as_ref() != Some(now_playing) => {
+                self.now_playing = Some(now_playing.clone());
+                vec![Change::Metadata]
+            }
+            Message::Position {
+                position_ns,
+                duration_ns,
+            } => {
+                let mut changes = Vec::new();
+
+                // Negative on a live stream.
+                let duration_ns = Some(*duration_ns).filter(|duration_ns| *duration_ns >= 0);
+                if duration_ns != self.duration_ns {
+                    self.duration_ns = duration_ns;
+                    changes.push(Change::Metadata);
+                }
+
+                if (position_ns - self.position_ns).abs() > SEEK_THRESHOLD_NS {
+                    changes.push(Change::Seeked(to_micros(*position_ns)));
+                }
+                self.position_ns = *position_ns;
+
+                changes
+            }
+            Message::VolumeChanged { volume, .. } if *volume != self.volume => {
+                self.volume = *volume;
+                vec![Change::Volume]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn playback_status(&self) -> &'static str {
+        match self.state {
+            PlayerState::Stopped => "Stopped",
+            PlayerState::Paused => "Paused",
+            PlayerState::Playing => "Playing",
+        }
+    }
+
+    pub fn track_id(&self) -> String {
+        match self.now_playing {
+            Some(_) => format!("{TRACK_PATH}/{}", self.track_number),
+            None => NO_TRACK.to_owned(),
+        }
+    }
+
+    /// The `xesam` and `mpris` keys known for the current track.
+    pub fn metadata(&self) -> zbus::zvariant::Result<HashMap<String, OwnedValue>> {
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "mpris:trackid".to_owned(),
+            Value::from(ObjectPath::try_from(self.track_id())?).try_into()?,
+        );
+
+        let Some(now_playing) = &self.now_playing else {
+            return Ok(metadata);
+        };
+
+        metadata.insert(
+            "xesam:url".to_owned(),
+            Value::from(now_playing.uri.as_str()).try_into()?,
+        );
+        if let Some(duration_ns) = self.duration_ns {
+            metadata.insert(
+                "mpris:length".to_owned(),
+                Value::from(to_micros(duration_ns)).try_into()?,
+            );
+        }
+
+        let texts = [
+            ("xesam:title", &now_playing.title),
+            ("xesam:album", &now_playing.album),
+            ("mpris:artUrl", &now_playing.cover),
+        ];
+        for (key, text) in texts {
+            if let Some(text) = text {
+                metadata.insert(key.to_owned(), Value::from(text.as_str()).try_into()?);
+            }
+        }
+
+        // Lists in MPRIS, a single value from the tags.
+        let lists = [
+            ("xesam:artist", &now_playing.artist),
+            ("xesam:albumArtist", &now_playing.album_artist),
+            ("xesam:genre", &now_playing.genre),
+        ];
+        for (key, text) in lists {
+            if let Some(text) = text {
+                metadata.insert(key.to_owned(), Value::from(vec![text.as_str()]).try_into()?);
+            }
+        }
+
+        Ok(metadata)
+    }
+}
+
+pub fn to_micros(time_ns: i64) -> i64 {
+    time_ns / NANOS_PER_MICRO
+}
+
+pub fn to_nanos(time_us: i64) -> i64 {
+    time_us.saturating_mul(NANOS_PER_MICRO)
+}
+
+#[cfg(test)]
+mod tests {
+    use zbus::zvariant::OwnedObjectPath;
+
+    use crate::{
+        frontend::message::{Message, PlayerState},
+        streamer::now_playing::NowPlaying,
+    };
+
+    use super::{Change, Values, NO_TRACK};
+
+    fn position(position_ns: i64, duration_ns: i64) -> Message {
+        Message::Position {
+            position_ns,
+            duration_ns,
+        }
+    }
+
+    #[test]
+    fn test_update_track() {
+        let mut values = Values::default();
+
+        assert_eq!(
+            values.update(&Message::StateChanged(PlayerState::Playing)),
+            vec![Change::PlaybackStatus]
+        );
+        assert_eq!(
+            values.update(&Message::StateChanged(PlayerState::Playing)),
+            vec![]
+        );
+        assert_eq!(
+            values.update(&Message::TrackStarted("file:///a.flac".to_owned())),
+            vec![Change::Metadata]
+        );
+        assert_eq!(
+            values.update(&position(250_000_000, 180_000_000_000)),
+            vec![Change::Metadata]
+        );
+        assert_eq!(
+            values.update(&position(500_000_000, 180_000_000_000)),
+            vec![]
+        );
+        assert_eq!(values.track_id(), "/me/tabinol/cappella/track/1");
+    }
+
+    #[test]
+    fn test_update_seeked() {
+        let mut values = Values {
+            position_ns: 500_000_000,
+            duration_ns: Some(180_000_000_000),
+            ..Values::default()
+        };
+
+        assert_eq!(
+            values.update(&position(60_000_000_000, 180_000_000_000)),
+            vec![Change::Seeked(60_000_000)]
+        );
+    }
+
+    #[test]
+    fn test_update_live() {
+        let mut values = Values {
+            duration_ns: Some(1),
+            ..Values::default()
+        };
+
+        assert_eq!(values.update(&position(0, -1)), vec![Change::Metadata]);
+        assert_eq!(values.duration_ns, None);
+    }
+
+    #[test]
+    fn test_metadata() {
+        let values = Values {
+            track_number: 2,
+            now_playing: Some(NowPlaying {
+                title: Some("Fragments".to_owned()),
+                artist: Some("Nomyn".to_owned()),
+                ..NowPlaying::new("file:///a.mp3")
+            }),
+            duration_ns: Some(3_000_000_000),
+            ..Values::default()
+        };
+
+        let metadata = values.metadata().unwrap();
+
+        assert_eq!(metadata.len(), 5);
+        assert_eq!(
+            String::try_from(metadata["xesam:title"].clone()).unwrap(),
+            "Fragments"
+        );
+        assert_eq!(
+            Vec::<String>::try_from(metadata["xesam:artist"].clone()).unwrap(),
+            vec!["Nomyn".to_owned()]
+        );
+        assert_eq!(i64::try_from(&metadata["mpris:length"]).unwrap(), 3_000_000);
+    }
+
+    #[test]
+    fn test_metadata_no_track() {
+        let metadata = Values::default().metadata().unwrap();
+
+        assert_eq!(metadata.len(), 1);
+        assert_eq!(
+            OwnedObjectPath::try_from(metadata["mpris:trackid"].clone())
+                .unwrap()
+                .as_str(),
+            NO_TRACK
+        );
+    }
+}
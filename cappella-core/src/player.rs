@@ -0,0 +1,3 @@
+pub mod front;
+pub mod order;
+pub mod queue;
@@ -0,0 +1,841 @@
+use std::{
+    fmt::Debug,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    frontend::{self, message::PlayerState},
+    local::{app_error::AppError, settings::Settings},
+    streamer::{self, message::Uri},
+};
+
+use super::{order::Repeat, queue::Queue};
+
+const VOLUME_MIN: f64 = 0.0;
+const VOLUME_MAX: f64 = 1.0;
+
+pub trait Front: Debug + Send + Sync {
+    fn play(&self, uri: &str) -> Result<(), AppError>;
+    fn pause(&self) -> Result<(), AppError>;
+    fn stop(&self) -> Result<(), AppError>;
+    fn seek(&self, position_ns: i64) -> Result<(), AppError>;
+    fn next(&self) -> Result<(), AppError>;
+    fn previous(&self) -> Result<(), AppError>;
+    fn jump_to(&self, index: usize) -> Result<(), AppError>;
+    fn enqueue(&self, uris: Vec<String>) -> Result<(), AppError>;
+    fn remove_from_queue(&self, index: usize) -> Result<(), AppError>;
+    fn move_in_queue(&self, from: usize, to: usize) -> Result<(), AppError>;
+    fn clear_queue(&self) -> Result<(), AppError>;
+    fn queue(&self) -> Result<Vec<Uri>, AppError>;
+    fn set_gapless(&self, gapless: bool);
+    fn set_repeat(&self, repeat: Repeat) -> Result<(), AppError>;
+    fn set_shuffle(&self, shuffle: bool, seed: Option<u64>) -> Result<(), AppError>;
+    fn set_volume(&self, volume: f64) -> Result<Volume, AppError>;
+    fn set_mute(&self, muted: bool) -> Result<Volume, AppError>;
+    fn volume(&self) -> Result<Volume, AppError>;
+    fn status(&self) -> Result<Status, AppError>;
+    fn is_running(&self) -> Result<bool, AppError>;
+    fn wait_until_end(&self) -> Result<(), AppError>;
+}
+
+/// The volume is on the cubic scale, from 0.0 to 1.0.
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct Volume {
+    pub volume: f64,
+    pub muted: bool,
+}
+
+/// A snapshot for a frontend which starts or reloads while the player is running.
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct Status {
+    pub state: PlayerState,
+    pub uri: Option<Uri>,
+    pub position_ns: Option<i64>,
+    pub duration_ns: Option<i64>,
+    pub seekable: bool,
+    pub volume: Volume,
+    pub queue_index: Option<usize>,
+    pub repeat: Repeat,
+    pub shuffle: bool,
+}
+
+pub fn new_box(
+    streamer_front: Box<dyn streamer::front::Front>,
+    streamer_pipe: Box<dyn streamer::pipe::Pipe>,
+    streamer_status: Arc<dyn streamer::status::Status>,
+    queue: Arc<dyn Queue>,
+    settings: Arc<dyn Settings>,
+    frontend_pipe: Arc<dyn frontend::pipe::Pipe>,
+) -> Box<dyn Front> {
+    Box::new(Front_ {
+        streamer_front,
+        streamer_pipe,
+        streamer_status,
+        queue,
+        settings,
+        frontend_pipe,
+    })
+}
+
+#[derive(Debug)]
+struct Front_ {
+    streamer_front: Box<dyn streamer::front::Front>,
+    streamer_pipe: Box<dyn streamer::pipe::Pipe>,
+    streamer_status: Arc<dyn streamer::status::Status>,
+    queue: Arc<dyn Queue>,
+    settings: Arc<dyn Settings>,
+    frontend_pipe: Arc<dyn frontend::pipe::Pipe>,
+}
+
+unsafe impl Send for Front_ {}
+unsafe impl Sync for Front_ {}
+
+impl Front for Front_ {
+    fn play(&self, uri: &str) -> Result<(), AppError> {
+        self.queue.play_now(uri)?;
+        self.frontend_pipe
+            .send(frontend::message::Message::QueueChanged);
+
+        self.play_uri(uri)
+    }
+
+    fn pause(&self) -> Result<(), AppError> {
+        self.streamer_pipe.send(streamer::message::Message::Pause)
+    }
+
+    /// Stopping a stopped player is not an error.
+    fn stop(&self) -> Result<(), AppError> {
+        self.send_if_running(streamer::message::Message::Stop)
+    }
+
+    /// A live stream, or a track not prerolled yet, has no known duration to seek in.
+    fn seek(&self, position_ns: i64) -> Result<(), AppError> {
+        if !self.streamer_status.get()?.seekable {
+            return Err(AppError::InvalidState(
+                "The stream is not seekable.".to_owned(),
+            ));
+        }
+
+        self.streamer_pipe
+            .send(streamer::message::Message::Seek(position_ns))
+    }
+
+    fn next(&self) -> Result<(), AppError> {
+        match self.queue.next()? {
+            Some(uri) => self.play_uri(&uri),
+            None => Ok(()),
+        }
+    }
+
+    fn previous(&self) -> Result<(), AppError> {
+        match self.queue.previous()? {
+            Some(uri) => self.play_uri(&uri),
+            None => Ok(()),
+        }
+    }
+
+    fn jump_to(&self, index: usize) -> Result<(), AppError> {
+        let uri = self.queue.jump_to(index)?;
+
+        self.play_uri(&uri)
+    }
+
+    fn enqueue(&self, uris: Vec<String>) -> Result<(), AppError> {
+        self.queue.enqueue(uris)?;
+        self.frontend_pipe
+            .send(frontend::message::Message::QueueChanged);
+
+        Ok(())
+    }
+
+    fn remove_from_queue(&self, index: usize) -> Result<(), AppError> {
+        self.queue.remove(index)?;
+        self.frontend_pipe
+            .send(frontend::message::Message::QueueChanged);
+
+        Ok(())
+    }
+
+    fn move_in_queue(&self, from: usize, to: usize) -> Result<(), AppError> {
+        self.queue.move_to(from, to)?;
+        self.frontend_pipe
+            .send(frontend::message::Message::QueueChanged);
+
+        Ok(())
+    }
+
+    fn clear_queue(&self) -> Result<(), AppError> {
+        self.queue.clear()?;
+        self.frontend_pipe
+            .send(frontend::message::Message::QueueChanged);
+
+        Ok(())
+    }
+
+    fn queue(&self) -> Result<Vec<Uri>, AppError> {
+        self.queue.uris()
+    }
+
+    fn set_gapless(&self, gapless: bool) {
+        self.streamer_front.set_gapless(gapless);
+    }
+
+    fn set_repeat(&self, repeat: Repeat) -> Result<(), AppError> {
+        self.queue.set_repeat(repeat)
+    }
+
+    fn set_shuffle(&self, shuffle: bool, seed: Option<u64>) -> Result<(), AppError> {
+        let seed = match (shuffle, seed) {
+            (false, _) => None,
+            (true, Some(seed)) => Some(seed),
+            (true, None) => Some(new_seed()?),
+        };
+
+        self.queue.set_shuffle_seed(seed)
+    }
+
+    fn set_volume(&self, volume: f64) -> Result<Volume, AppError> {
+        let volume = volume.clamp(VOLUME_MIN, VOLUME_MAX);
+
+        self.settings.update(&|values| values.volume = volume)?;
+        self.send_if_running(streamer::message::Message::SetVolume(volume))?;
+
+        self.volume()
+    }
+
+    fn set_mute(&self, muted: bool) -> Result<Volume, AppError> {
+        self.settings.update(&|values| values.muted = muted)?;
+        self.send_if_running(streamer::message::Message::SetMute(muted))?;
+
+        self.volume()
+    }
+
+    fn volume(&self) -> Result<Volume, AppError> {
+        let values = self.settings.get()?;
+
+        Ok(Volume {
+            volume: values.volume,
+            muted: values.muted,
+        })
+    }
+
+    fn status(&self) -> Result<Status, AppError> {
+        let streamer_values = self.streamer_status.get()?;
+
+        Ok(Status {
+            state: streamer_values.state,
+            uri: streamer_values.uri,
+            position_ns: streamer_values.position_ns,
+            duration_ns: streamer_values.duration_ns,
+            seekable: streamer_values.seekable,
+            volume: self.volume()?,
+            queue_index: self.queue.index()?,
+            repeat: self.queue.repeat()?,
+            shuffle: self.queue.shuffle_seed()?.is_some(),
+        })
+    }
+
+    /// `false` once the queue is played to the end or on stop.
+    fn is_running(&self) -> Result<bool, AppError> {
+        self.streamer_front.is_running()
+    }
+
+    fn wait_until_end(&self) -> Result<(), AppError> {
+        self.streamer_front.wait_until_end()
+    }
+}
+
+impl Front_ {
+    /// Without streamer, the value is applied from the settings on the next start.
+    fn send_if_running(&self, message: streamer::message::Message) -> Result<(), AppError> {
+        if self.streamer_front.is_running()? {
+            self.streamer_pipe.send(message)?;
+        }
+
+        Ok(())
+    }
+
+    fn play_uri(&self, uri: &str) -> Result<(), AppError> {
+        if self.streamer_front.is_running()? {
+            self.streamer_pipe
+                .send(streamer::message::Message::Play(uri.to_owned()))
+        } else {
+            self.streamer_front.start(uri)
+        }
+    }
+}
+
+/// A new seed on each call, so a shuffle without seed is a reshuffle.
+fn new_seed() -> Result<u64, AppError> {
+    Ok(SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process, sync::Arc};
+
+    use parking_lot::Mutex;
+
+    use crate::{
+        frontend::{self, message::PlayerState},
+        local::{app_error::AppError, settings},
+        player::queue::{self, Queue},
+        streamer::{self, message::Message, status::Status},
+    };
+
+    use super::new_box;
+
+    #[derive(Debug)]
+    struct MockStreamerFront {
+        is_running: bool,
+    }
+
+    impl streamer::front::Front for MockStreamerFront {
+        fn start(&self, _uri: &str) -> Result<(), AppError> {
+            Ok(())
+        }
+
+        fn is_running(&self) -> Result<bool, AppError> {
+            Ok(self.is_running)
+        }
+
+        fn set_gapless(&self, _gapless: bool) {}
+
+        fn wait_until_end(&self) -> Result<(), AppError> {
+            Ok(())
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct MockStreamerPipe {
+        messages: Arc<Mutex<Vec<Message>>>,
+    }
+
+    impl streamer::pipe::Pipe for MockStreamerPipe {
+        fn send(&self, message: Message) -> Result<(), AppError> {
+            self.messages.lock().push(message);
+            Ok(())
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct MockFrontendPipe {
+        messages: Mutex<Vec<frontend::message::Message>>,
+    }
+
+    impl frontend::pipe::Pipe for MockFrontendPipe {
+        fn send(&self, frontend_message: frontend::message::Message) {
+            self.messages.lock().push(frontend_message);
+        }
+    }
+
+    fn new_front(
+        is_running: bool,
+        status: Arc<dyn Status>,
+        queue: Arc<dyn Queue>,
+    ) -> (Box<dyn super::Front>, Arc<Mutex<Vec<Message>>>) {
+        new_front_with_pipe(is_running, status, queue, Arc::default())
+    }
+
+    fn new_front_with_pipe(
+        is_running: bool,
+        status: Arc<dyn Status>,
+        queue: Arc<dyn Queue>,
+        frontend_pipe: Arc<MockFrontendPipe>,
+    ) -> (Box<dyn super::Front>, Arc<Mutex<Vec<Message>>>) {
+        let streamer_pipe = MockStreamerPipe::default();
+        let messages = streamer_pipe.messages.clone();
+        let settings_path = env::temp_dir()
+            .join(format!("cappella-player-front-{}", process::id()))
+            .join(settings::SETTINGS_FILE_NAME);
+
+        let front = new_box(
+            Box::new(MockStreamerFront { is_running }),
+            Box::new(streamer_pipe),
+            status,
+            queue,
+            settings::new_arc(settings_path),
+            frontend_pipe,
+        );
+
+        (front, messages)
+    }
+
+    #[test]
+    fn test_status() {
+        let status = streamer::status::new_arc();
+        let queue = queue::new_arc();
+        queue
+            .enqueue(vec![
+                "file:///a.flac".to_owned(),
+                "file:///b.flac".to_owned(),
+            ])
+            .unwrap();
+        queue.jump_to(1).unwrap();
+        status
+            .update(&|values| {
+                values.state = PlayerState::Paused;
+                values.uri = Some("file:///b.flac".to_owned());
+                values.position_ns = Some(42);
+            })
+            .unwrap();
+        let (front, _) = new_front(true, status, queue);
+
+        let front_status = front.status().unwrap();
+
+        assert_eq!(front_status.state, PlayerState::Paused);
+        assert_eq!(front_status.uri.as_deref(), Some("file:///b.flac"));
+        assert_eq!(front_status.position_ns, Some(42));
+        assert_eq!(front_status.duration_ns, None);
+        assert_eq!(front_status.volume.volume, 1.0);
+        assert_eq!(front_status.queue_index, Some(1));
+    }
+
+    #[test]
+    fn test_stop_when_stopped() {
+        let (front, messages) = new_front(false, streamer::status::new_arc(), queue::new_arc());
+
+        front.stop().unwrap();
+
+        assert!(messages.lock().is_empty());
+    }
+
+    #[test]
+    fn test_stop_when_running() {
+        let (front, messages) = new_front(true, streamer::status::new_arc(), queue::new_arc());
+
+        front.stop().unwrap();
+
+        assert!(matches!(messages.lock().as_slice(), [Message::Stop]));
+    }
+
+    #[test]
+    fn test_seek() {
+        let status = streamer::status::new_arc();
+        let (front, messages) = new_front(true, status.clone(), queue::new_arc());
+
+        let live_result = front.seek(42);
+        status.update(&|values| values.seekable = true).unwrap();
+        front.seek(42).unwrap();
+
+        assert!(matches!(live_result, Err(AppError::InvalidState(_))));
+        assert!(matches!(messages.lock().as_slice(), [Message::Seek(42)]));
+    }
+
+    #[test]
+    fn test_queue_changed() {
+        let frontend_pipe = Arc::new(MockFrontendPipe::default());
+        let (front, _) = new_front_with_pipe(
+            false,
+            streamer::status::new_arc(),
+            queue::new_arc(),
+            frontend_pipe.clone(),
+        );
+
+        front.enqueue(vec!["file:///a.flac".to_owned()]).unwrap();
+        front.move_in_queue(0, 0).unwrap();
+        front.remove_from_queue(0).unwrap();
+        front.clear_queue().unwrap();
+
+        assert!(front.remove_from_queue(0).is_err());
+        assert_eq!(
+            frontend_pipe
+                .messages
+                .lock()
+                .iter()
+                .filter(|message| matches!(message, frontend::message::Message::QueueChanged))
+                .count(),
+            4
+        );
+    }
+
+    #[test]
+    fn test_remove_from_queue_err() {
+        let (front, _) = new_front(true, streamer::status::new_arc(), queue::new_arc());
+
+        let result = front.remove_from_queue(3);
+
+        assert!(matches!(
+            result,
+            Err(AppError::OutOfRange { index: 3, len: 0 })
+        ));
+    }
+}
+
+// #[cfg(test)]
+// mod tests {
+//     use std::sync::{Arc, Mutex};
+
+//     use crate::player::{
+//         player_front::{Player, Player_},
+//         streamer::Streamer,
+//         streamer_pipe::{Message, StreamerPipe},
+//     };
+
+//     #[derive(Debug)]
+//     struct MockStreamer {
+//         is_running: bool,
+//         uri: Mutex<String>,
+//     }
+
+//     impl MockStreamer {
+//         fn new(is_running: bool) -> Self {
+//             Self {
+//                 is_running,
+//                 uri: Mutex::default(),
+//             }
+//         }
+//     }
+
+//     impl Streamer for MockStreamer {
+//         fn is_running(&self) -> bool {
+//             self.is_running
+//         }
+
+//         fn start_thread(&self) {}
+
+//         fn play(&self, uri: &str) {
+//             *self.uri.lock().unwrap() = uri.to_string();
+//         }
+
+//         fn end(&self) {}
+//     }
+
+//     #[derive(Debug, Default)]
+//     struct MockStreamerPipe {
+//         message: Mutex<Message>,
+//     }
+
+//     impl StreamerPipe for MockStreamerPipe {
+//         fn send(&self, message: Message) {
+//             *self.message.lock().unwrap() = message;
+//         }
+//     }
+
+//     #[test]
+//     fn test_play_when_streamer_inactive() {
+//         let streamer = Arc::new(MockStreamer::new(false));
+//         let streamer_pipe = Arc::<MockStreamerPipe>::default();
+//         let player = Player_::new(streamer.clone(), streamer_pipe.clone());
+
+//         player.play("test_uri");
+
+//         assert_eq!(*streamer.uri.lock().unwrap(), "test_uri");
+//     }
+
+//     #[test]
+//     fn test_play_when_streamer_active() {
+//         let streamer = Arc::new(MockStreamer::new(true));
+//         let streamer_pipe = Arc::<MockStreamerPipe>::default();
+//         let player = Player_::new(streamer.clone(), streamer_pipe.clone());
+
+//         player.play("test_uri");
+
+//         let sent_message_lock = streamer_pipe.message.lock().unwrap();
+//         assert!(matches!(&*sent_message_lock, Message::Next(_)));
+//         assert!(if let Message::Next(uri) = &*sent_message_lock {
+//             uri.eq("test_uri")
+//         } else {
+//             false
+//         });
+//     }
+
+//     #[test]
+//     fn test_pause() {
+//         let streamer = Arc::new(MockStreamer::new(true));
+//         let streamer_pipe = Arc::<MockStreamerPipe>::default();
+//         let player = Player_::new(streamer.clone(), streamer_pipe.clone());
+
+//         player.pause();
+
+//         let sent_message_lock = streamer_pipe.message.lock().unwrap();
+//         assert!(matches!(&*sent_message_lock, Message::Pause));
+//     }
+
+//     #[test]
+//     fn test_stop() {
+//         let streamer = Arc::new(MockStreamer::new(true));
+//         let streamer_pipe = Arc::<MockStreamerPipe>::default();
+//         let player = Player_::new(streamer.clone(), streamer_pipe.clone());
+
+//         player.stop();
+
+//         let sent_message_lock = streamer_pipe.message.lock().unwrap();
+//         assert!(matches!(&*sent_message_lock, Message::Stop));
+//     }
+// }
+
+// #[cfg(test)]
+// mod tests {
+//     use std::{
+//         sync::{mpsc::Receiver, Arc, Mutex, RwLock},
+//         time::Duration,
+//     };
+
+//     use crate::player::{
+//         streamer::{self, Streamer, Streamer_},
+//         streamer_loop::StreamerLoop,
+//         streamer_pipe::{Message, StreamerPipe},
+//     };
+
+//     use super::Status;
+
+//     #[derive(Debug, Default)]
+//     struct MockStreamerPipe {
+//         last_message: RwLock<Message>,
+//     }
+
+//     impl StreamerPipe for MockStreamerPipe {
+//         fn send(&self, message: Message) {
+//             *self.last_message.write().unwrap() = message;
+//         }
+//     }
+
+//     #[derive(Debug, Default)]
+//     struct MockStreamerLoop {
+//         status: RwLock<Status>,
+//         last_message: RwLock<streamer::Message>,
+//         receiver: Mutex<Option<Receiver<super::Message>>>,
+//     }
+
+//     impl StreamerLoop for MockStreamerLoop {
+//         fn run(&self, receiver: Receiver<super::Message>) {
+//             let mut receiver_lock = self.receiver.lock().unwrap();
+//             *receiver_lock = Some(receiver);
+//             let last_message = (*receiver_lock)
+//                 .as_ref()
+//                 .unwrap()
+//                 .recv_timeout(Duration::from_secs(10))
+//                 .expect("Message timeout.");
+//             *self.last_message.write().unwrap() = last_message;
+//         }
+
+//         fn status(&self) -> Status {
+//             (*self.status.read().unwrap()).clone()
+//         }
+//     }
+
+//     #[test]
+//     fn test_is_running_false() {
+//         let streamer_pipe = Arc::<MockStreamerPipe>::default();
+//         let streamer_loop = Arc::<MockStreamerLoop>::default();
+
+//         *streamer_loop.status.write().unwrap() = Status::Wait;
+//         let streamer = Streamer_::new(streamer_pipe.clone(), streamer_loop.clone());
+
+//         assert!(!streamer.is_running());
+//     }
+
+//     #[test]
+//     fn test_is_running_true() {
+//         let streamer_pipe = Arc::<MockStreamerPipe>::default();
+//         let streamer_loop = Arc::<MockStreamerLoop>::default();
+
+//         *streamer_loop.status.write().unwrap() = Status::Play("uri".to_string());
+//         let streamer = Streamer_::new(streamer_pipe.clone(), streamer_loop.clone());
+
+//         assert!(streamer.is_running());
+//     }
+
+//     #[test]
+//     fn test_start_thread() {
+//         let streamer_pipe = Arc::<MockStreamerPipe>::default();
+//         let streamer_loop = Arc::<MockStreamerLoop>::default();
+
+//         let streamer = Streamer_::new(streamer_pipe.clone(), streamer_loop.clone());
+
+//         streamer.start_thread();
+
+//         streamer
+//             .sender
+//             .get()
+//             .unwrap()
+//             .send(streamer::Message::End)
+//             .unwrap();
+
+//         let join_handle = streamer.join_handle.try_write().unwrap().take().unwrap();
+//         join_handle.join().unwrap();
+//         let last_message = (*streamer_loop.last_message.read().unwrap()).clone();
+
+//         assert!(matches!(last_message, streamer::Message::End));
+//     }
+
+//     #[test]
+//     fn test_play_on_wait() {
+//         let streamer_pipe = Arc::<MockStreamerPipe>::default();
+//         let streamer_loop = Arc::<MockStreamerLoop>::default();
+
+//         *streamer_loop.status.write().unwrap() = Status::Wait;
+//         let streamer = Streamer_::new(streamer_pipe.clone(), streamer_loop.clone());
+
+//         streamer.start_thread();
+
+//         streamer.play("new_uri");
+
+//         let join_handle = streamer.join_handle.try_write().unwrap().take().unwrap();
+//         join_handle.join().unwrap();
+//         let status = (*streamer_loop.status.read().unwrap()).clone();
+
+//         assert!(matches!(status, Status::Play(_)));
+//         assert!(if let Status::Play(uri) = status {
+//             uri.eq("new_uri")
+//         } else {
+//             false
+//         });
+//     }
+
+//     #[test]
+//     fn test_play_on_play() {
+//         let streamer_pipe = Arc::<MockStreamerPipe>::default();
+//         let streamer_loop = Arc::<MockStreamerLoop>::default();
+
+//         *streamer_loop.status.write().unwrap() = Status::Play("old_uri".to_string());
+//         let streamer = Streamer_::new(streamer_pipe.clone(), streamer_loop.clone());
+
+//         streamer.play("new_uri");
+
+//         let message = (*streamer_pipe.last_message.read().unwrap()).clone();
+
+//         assert!(matches!(message, Message::Next(_)));
+//         assert!(if let Message::Next(uri) = message {
+//             uri.eq("new_uri")
+//         } else {
+//             false
+//         });
+//     }
+
+//     #[test]
+//     fn test_end_on_play() {
+//         let streamer_pipe = Arc::<MockStreamerPipe>::default();
+//         let streamer_loop = Arc::<MockStreamerLoop>::default();
+
+//         *streamer_loop.status.write().unwrap() = Status::Play("uri".to_owned());
+//         let streamer = Streamer_::new(streamer_pipe.clone(), streamer_loop.clone());
+
+//         streamer.start_thread();
+
+//         streamer.end();
+
+//         let status = (*streamer_loop.status.read().unwrap()).clone();
+//         let message = (*streamer_pipe.last_message.read().unwrap()).clone();
+
+//         assert!(matches!(status, Status::End));
+//         assert!(matches!(message, Message::Stop));
+//     }
+
+//     #[test]
+//     fn test_end_on_wait() {
+//         let streamer_pipe = Arc::<MockStreamerPipe>::default();
+//         let streamer_loop = Arc::<MockStreamerLoop>::default();
+
+//         *streamer_loop.status.write().unwrap() = Status::Wait;
+//         let streamer = Streamer_::new(streamer_pipe.clone(), streamer_loop.clone());
+
+//         streamer.start_thread();
+
+//         streamer.end();
+
+//         let status = (*streamer_loop.status.read().unwrap()).clone();
+
+//         assert!(matches!(status, Status::End));
+//     }
+// }
+
+// #[cfg(test)]
+// mod tests {
+//     use std::ffi::{c_char, c_int};
+
+//     use gstreamer::glib::ffi::GError;
+//     use gstreamer::ffi::{GstBus, GstElement, GST_STATE_CHANGE_FAILURE};
+
+//     use crate::gstreamer::{
+//         gstreamer::{Gstreamer, Gstreamer_},
+//         gstreamer_tests_common::{
+//             self, get_gst_bus_ptr, get_gst_element_ptr, ELEMENT_SET_STATE_RESULT,
+//             OBJECT_UNREF_CALL_NB,
+//         },
+//     };
+
+//     static mut INIT_CALL_NB: u32 = 0;
+//     static mut PARSE_LAUNCH_CALL_NB: u32 = 0;
+//     static mut ELEMENT_GET_BUS_CALL_NB: u32 = 0;
+
+//     #[no_mangle]
+//     extern "C" fn gst_init(_argc: *mut c_int, _argv: *mut *mut *mut c_char) {
+//         unsafe { INIT_CALL_NB += 1 };
+//     }
+
+//     #[no_mangle]
+//     extern "C" fn gst_parse_launch(
+//         _pipeline_description: *const c_char,
+//         _error: *mut *mut GError,
+//     ) -> *mut GstElement {
+//         unsafe {
+//             PARSE_LAUNCH_CALL_NB += 1;
+//         }
+
+//         get_gst_element_ptr()
+//     }
+
+//     #[no_mangle]
+//     extern "C" fn gst_element_get_bus(_element: *mut GstElement) -> *mut GstBus {
+//         unsafe {
+//             ELEMENT_GET_BUS_CALL_NB += 1;
+//         }
+
+//         get_gst_bus_ptr()
+//     }
+
+//     fn before_each() {
+//         gstreamer_tests_common::before_each();
+
+//         unsafe {
+//             INIT_CALL_NB = 0;
+//             PARSE_LAUNCH_CALL_NB = 0;
+//             ELEMENT_GET_BUS_CALL_NB = 0;
+//         }
+//     }
+
+//     #[test]
+//     fn test_init() {
+//         let _lock = gstreamer_tests_common::lock();
+//         before_each();
+
+//         let gstreamer = Gstreamer_::default();
+
+//         gstreamer.init();
+
+//         assert_eq!(unsafe { INIT_CALL_NB }, 1);
+//     }
+
+//     #[test]
+//     fn test_launch() {
+//         let _lock = gstreamer_tests_common::lock();
+//         before_each();
+
+//         let gstreamer = Gstreamer_::default();
+
+//         gstreamer.launch("uri");
+
+//         assert_eq!(unsafe { PARSE_LAUNCH_CALL_NB }, 1);
+//         assert_eq!(unsafe { ELEMENT_GET_BUS_CALL_NB }, 1);
+//         assert_eq!(unsafe { OBJECT_UNREF_CALL_NB }, 2);
+//     }
+
+//     #[test]
+//     #[should_panic]
+//     fn test_launch_failure() {
+//         let _lock = gstreamer_tests_common::lock();
+//         before_each();
+
+//         let gstreamer = Gstreamer_::default();
+
+//         unsafe { ELEMENT_SET_STATE_RESULT = GST_STATE_CHANGE_FAILURE }
+//         gstreamer.launch("uri");
+
+//         assert_eq!(unsafe { PARSE_LAUNCH_CALL_NB }, 1);
+//         assert_eq!(unsafe { ELEMENT_GET_BUS_CALL_NB }, 1);
+//         assert_eq!(unsafe { OBJECT_UNREF_CALL_NB }, 1);
+//     }
+// }
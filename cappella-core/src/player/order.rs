@@ -0,0 +1,312 @@
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum Repeat {
+    #[default]
+    Off,
+    One,
+    All,
+}
+
+/// The playback order of the queue: the queue index at each play position.
+#[derive(Clone, Debug, Default)]
+pub struct Order {
+    repeat: Repeat,
+    seed: Option<u64>,
+    rng: SplitMix64,
+    permutation: Vec<usize>,
+}
+
+impl Order {
+    pub fn repeat(&self) -> Repeat {
+        self.repeat
+    }
+
+    pub fn set_repeat(&mut self, repeat: Repeat) {
+        self.repeat = repeat;
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// The same seed always gives the same permutation for the same queue. The current track is
+    /// moved first.
+    pub fn set_shuffle(&mut self, seed: Option<u64>, len: usize, current: Option<usize>) {
+        self.seed = seed;
+        self.rng = SplitMix64(seed.unwrap_or_default());
+        self.permutation = (0..len).collect();
+
+        if seed.is_some() {
+            for i in (1..len).rev() {
+                let j = self.rng.below(i + 1);
+                self.permutation.swap(i, j);
+            }
+
+            if let Some(position) = current.and_then(|current| self.position(current)) {
+                self.permutation[..=position].rotate_right(1);
+            }
+        }
+    }
+
+    /// The queue index is inserted, in a shuffled order at a random position after the current
+    /// track: the played tracks stay played.
+    pub fn insert(&mut self, index: usize, current: Option<usize>) {
+        self.shift_from(index);
+
+        let position = match self.seed {
+            Some(_) => {
+                let first = current
+                    .map(|current| {
+                        if current >= index {
+                            current + 1
+                        } else {
+                            current
+                        }
+                    })
+                    .and_then(|current| self.position(current))
+                    .map_or(0, |position| position + 1);
+                first + self.rng.below(self.permutation.len() - first + 1)
+            }
+            None => index,
+        };
+        self.permutation.insert(position, index);
+    }
+
+    /// The queue index is inserted to be played right after the current track.
+    pub fn insert_next(&mut self, index: usize, current: Option<usize>) {
+        self.shift_from(index);
+
+        let position = match self.seed {
+            Some(_) => current
+                .map(|current| {
+                    if current >= index {
+                        current + 1
+                    } else {
+                        current
+                    }
+                })
+                .and_then(|current| self.position(current))
+                .map_or(0, |position| position + 1),
+            None => index,
+        };
+        self.permutation.insert(position, index);
+    }
+
+    /// Returns the queue index played before the removed one, after the removal.
+    pub fn remove(&mut self, index: usize) -> Option<usize> {
+        let position = self.position(index)?;
+        self.permutation.remove(position);
+
+        for value in &mut self.permutation {
+            if *value > index {
+                *value -= 1;
+            }
+        }
+
+        position
+            .checked_sub(1)
+            .map(|previous| self.permutation[previous])
+    }
+
+    /// The play positions are kept, only the queue indexes change.
+    pub fn move_to(&mut self, from: usize, to: usize) {
+        match self.seed {
+            Some(_) => {
+                for value in &mut self.permutation {
+                    *value = if *value == from {
+                        to
+                    } else if from < *value && *value <= to {
+                        *value - 1
+                    } else if to <= *value && *value < from {
+                        *value + 1
+                    } else {
+                        *value
+                    };
+                }
+            }
+            None => self.permutation = (0..self.permutation.len()).collect(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.permutation.clear();
+    }
+
+    pub fn next(&self, current: Option<usize>, on_end: bool) -> Option<usize> {
+        let len = self.permutation.len();
+
+        if on_end && self.repeat == Repeat::One && current.is_some_and(|current| current < len) {
+            return current;
+        }
+
+        let next_position = match current.and_then(|current| self.position(current)) {
+            Some(position) => position + 1,
+            None => 0,
+        };
+
+        if next_position < len {
+            Some(self.permutation[next_position])
+        } else if self.repeat == Repeat::All && len > 0 {
+            Some(self.permutation[0])
+        } else {
+            None
+        }
+    }
+
+    pub fn previous(&self, current: Option<usize>) -> Option<usize> {
+        let position = current.and_then(|current| self.position(current))?;
+
+        if position > 0 {
+            Some(self.permutation[position - 1])
+        } else if self.repeat == Repeat::All {
+            self.permutation.last().copied()
+        } else {
+            None
+        }
+    }
+
+    fn position(&self, index: usize) -> Option<usize> {
+        self.permutation.iter().position(|value| *value == index)
+    }
+
+    /// The queue indexes from `index` are moved up by one for an insertion.
+    fn shift_from(&mut self, index: usize) {
+        for value in &mut self.permutation {
+            if *value >= index {
+                *value += 1;
+            }
+        }
+    }
+}
+
+/// Small deterministic generator, enough for a shuffle and reproducible everywhere.
+#[derive(Clone, Debug, Default)]
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    /// Below `bound`, not zero.
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Order, Repeat};
+
+    fn play_all(order: &Order) -> Vec<usize> {
+        let mut played = Vec::new();
+        let mut current = None;
+
+        while let Some(next) = order.next(current, true) {
+            if played.len() > 100 {
+                break;
+            }
+            played.push(next);
+            current = Some(next);
+        }
+
+        played
+    }
+
+    #[test]
+    fn test_sequential() {
+        let mut order = Order::default();
+        order.set_shuffle(None, 3, None);
+
+        assert_eq!(play_all(&order), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_repeat_one() {
+        let mut order = Order::default();
+        order.set_shuffle(None, 3, None);
+        order.set_repeat(Repeat::One);
+
+        assert_eq!(order.next(Some(1), true), Some(1));
+        assert_eq!(order.next(Some(1), false), Some(2));
+    }
+
+    #[test]
+    fn test_repeat_all() {
+        let mut order = Order::default();
+        order.set_shuffle(None, 3, None);
+        order.set_repeat(Repeat::All);
+
+        assert_eq!(order.next(Some(2), true), Some(0));
+        assert_eq!(order.previous(Some(0)), Some(2));
+    }
+
+    #[test]
+    fn test_shuffle_is_a_permutation() {
+        let mut order = Order::default();
+        order.set_shuffle(Some(42), 10, None);
+
+        let mut played = play_all(&order);
+        played.sort();
+
+        assert_eq!(played, (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_shuffle_is_deterministic() {
+        let mut order_1 = Order::default();
+        let mut order_2 = Order::default();
+        order_1.set_shuffle(Some(42), 10, None);
+        order_2.set_shuffle(Some(42), 10, None);
+
+        assert_eq!(play_all(&order_1), play_all(&order_2));
+    }
+
+    #[test]
+    fn test_shuffle_starts_with_current() {
+        let mut order = Order::default();
+        order.set_shuffle(Some(7), 10, Some(4));
+
+        assert_eq!(order.previous(Some(4)), None);
+        assert_eq!(play_all(&order).len(), 10);
+    }
+
+    #[test]
+    fn test_shuffle_keeps_played_on_insert() {
+        let mut order = Order::default();
+        order.set_shuffle(Some(42), 10, None);
+        let played = play_all(&order)[..4].to_vec();
+
+        for index in 10..15 {
+            order.insert(index, Some(played[3]));
+        }
+
+        let mut all = play_all(&order);
+        assert_eq!(all[..4], played);
+        all.sort();
+        assert_eq!(all, (0..15).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_shuffle_remove_and_move() {
+        let mut order = Order::default();
+        order.set_shuffle(Some(42), 5, None);
+        let played = play_all(&order);
+
+        let previous = order.remove(played[2]);
+        order.move_to(0, 3);
+
+        let removed = |index: usize| if index > played[2] { index - 1 } else { index };
+        let moved = |index: usize| match index {
+            0 => 3,
+            1..=3 => index - 1,
+            _ => index,
+        };
+        let expected = [played[0], played[1], played[3], played[4]].map(|i| moved(removed(i)));
+        assert_eq!(previous, Some(removed(played[1])));
+        assert_eq!(play_all(&order), expected);
+    }
+}
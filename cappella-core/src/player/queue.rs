@@ -0,0 +1,365 @@
+use std::{fmt::Debug, sync::Arc};
+
+use parking_lot::Mutex;
+
+use crate::{
+    local::{app_error::AppError, mutex_lock_timeout::MutexLockTimeout},
+    streamer::message::Uri,
+};
+
+use super::order::{Order, Repeat};
+
+pub trait Queue: Debug + Send + Sync {
+    fn enqueue(&self, uris: Vec<Uri>) -> Result<(), AppError>;
+    fn play_now(&self, uri: &str) -> Result<(), AppError>;
+    fn remove(&self, index: usize) -> Result<(), AppError>;
+    fn move_to(&self, from: usize, to: usize) -> Result<(), AppError>;
+    fn clear(&self) -> Result<(), AppError>;
+    fn next(&self) -> Result<Option<Uri>, AppError>;
+    fn next_on_end(&self) -> Result<Option<Uri>, AppError>;
+    fn peek_next_on_end(&self) -> Result<Option<Uri>, AppError>;
+    fn previous(&self) -> Result<Option<Uri>, AppError>;
+    fn jump_to(&self, index: usize) -> Result<Uri, AppError>;
+    fn current(&self) -> Result<Option<Uri>, AppError>;
+    fn index(&self) -> Result<Option<usize>, AppError>;
+    fn uris(&self) -> Result<Vec<Uri>, AppError>;
+    fn repeat(&self) -> Result<Repeat, AppError>;
+    fn set_repeat(&self, repeat: Repeat) -> Result<(), AppError>;
+    fn shuffle_seed(&self) -> Result<Option<u64>, AppError>;
+    fn set_shuffle_seed(&self, seed: Option<u64>) -> Result<(), AppError>;
+}
+
+pub fn new_arc() -> Arc<dyn Queue> {
+    Arc::<Queue_>::default()
+}
+
+#[derive(Debug, Default)]
+struct Data {
+    uris: Vec<Uri>,
+    index: Option<usize>,
+    order: Order,
+}
+
+impl Data {
+    fn check_index(&self, index: usize) -> Result<(), AppError> {
+        if index >= self.uris.len() {
+            return Err(AppError::OutOfRange {
+                index,
+                len: self.uris.len(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn select(&mut self, index: Option<usize>) -> Option<Uri> {
+        let uri = index.and_then(|index| self.uris.get(index).cloned());
+
+        if uri.is_some() {
+            self.index = index;
+        }
+
+        uri
+    }
+}
+
+#[derive(Debug, Default)]
+struct Queue_(Mutex<Data>);
+
+impl Queue for Queue_ {
+    fn enqueue(&self, uris: Vec<Uri>) -> Result<(), AppError> {
+        let mut data = self.0.try_lock_default_duration()?;
+        let current = data.index;
+
+        for uri in uris {
+            let index = data.uris.len();
+            data.uris.push(uri);
+            data.order.insert(index, current);
+        }
+
+        Ok(())
+    }
+
+    fn play_now(&self, uri: &str) -> Result<(), AppError> {
+        let mut data = self.0.try_lock_default_duration()?;
+
+        if data.index.is_some_and(|index| data.uris[index] == uri) {
+            return Ok(());
+        }
+
+        let index = data.index.map_or(data.uris.len(), |index| index + 1);
+        data.uris.insert(index, uri.to_owned());
+        let current = data.index;
+        data.order.insert_next(index, current);
+        data.index = Some(index);
+
+        Ok(())
+    }
+
+    fn remove(&self, index: usize) -> Result<(), AppError> {
+        let mut data = self.0.try_lock_default_duration()?;
+        data.check_index(index)?;
+
+        data.uris.remove(index);
+        let previous = data.order.remove(index);
+
+        // The playing track is kept until its end, then the following one is played.
+        data.index = match data.index {
+            Some(current) if index == current => previous,
+            Some(current) if index < current => Some(current - 1),
+            current => current,
+        };
+
+        Ok(())
+    }
+
+    fn move_to(&self, from: usize, to: usize) -> Result<(), AppError> {
+        let mut data = self.0.try_lock_default_duration()?;
+        data.check_index(from)?;
+        data.check_index(to)?;
+
+        let uri = data.uris.remove(from);
+        data.uris.insert(to, uri);
+
+        data.index = data.index.map(|current| {
+            if current == from {
+                to
+            } else if from < current && to >= current {
+                current - 1
+            } else if from > current && to <= current {
+                current + 1
+            } else {
+                current
+            }
+        });
+        data.order.move_to(from, to);
+
+        Ok(())
+    }
+
+    fn clear(&self) -> Result<(), AppError> {
+        let mut data = self.0.try_lock_default_duration()?;
+        data.uris.clear();
+        data.index = None;
+        data.order.clear();
+
+        Ok(())
+    }
+
+    fn next(&self) -> Result<Option<Uri>, AppError> {
+        let mut data = self.0.try_lock_default_duration()?;
+        let next_index = data.order.next(data.index, false);
+
+        Ok(data.select(next_index))
+    }
+
+    fn next_on_end(&self) -> Result<Option<Uri>, AppError> {
+        let mut data = self.0.try_lock_default_duration()?;
+        let next_index = data.order.next(data.index, true);
+
+        Ok(data.select(next_index))
+    }
+
+    fn peek_next_on_end(&self) -> Result<Option<Uri>, AppError> {
+        let data = self.0.try_lock_default_duration()?;
+        let next_index = data.order.next(data.index, true);
+
+        Ok(next_index.and_then(|index| data.uris.get(index).cloned()))
+    }
+
+    fn previous(&self) -> Result<Option<Uri>, AppError> {
+        let mut data = self.0.try_lock_default_duration()?;
+        let previous_index = data.order.previous(data.index);
+
+        Ok(data.select(previous_index))
+    }
+
+    fn jump_to(&self, index: usize) -> Result<Uri, AppError> {
+        let mut data = self.0.try_lock_default_duration()?;
+        data.check_index(index)?;
+
+        data.index = Some(index);
+
+        Ok(data.uris[index].clone())
+    }
+
+    fn current(&self) -> Result<Option<Uri>, AppError> {
+        let data = self.0.try_lock_default_duration()?;
+
+        Ok(data.index.map(|index| data.uris[index].clone()))
+    }
+
+    fn index(&self) -> Result<Option<usize>, AppError> {
+        Ok(self.0.try_lock_default_duration()?.index)
+    }
+
+    fn uris(&self) -> Result<Vec<Uri>, AppError> {
+        Ok(self.0.try_lock_default_duration()?.uris.clone())
+    }
+
+    fn repeat(&self) -> Result<Repeat, AppError> {
+        Ok(self.0.try_lock_default_duration()?.order.repeat())
+    }
+
+    fn set_repeat(&self, repeat: Repeat) -> Result<(), AppError> {
+        self.0.try_lock_default_duration()?.order.set_repeat(repeat);
+
+        Ok(())
+    }
+
+    fn shuffle_seed(&self) -> Result<Option<u64>, AppError> {
+        Ok(self.0.try_lock_default_duration()?.order.seed())
+    }
+
+    fn set_shuffle_seed(&self, seed: Option<u64>) -> Result<(), AppError> {
+        let mut data = self.0.try_lock_default_duration()?;
+        let (len, index) = (data.uris.len(), data.index);
+        data.order.set_shuffle(seed, len, index);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::player::order::Repeat;
+
+    use super::{new_arc, Queue};
+
+    fn new_queue(uris: &[&str]) -> std::sync::Arc<dyn Queue> {
+        let queue = new_arc();
+        queue
+            .enqueue(uris.iter().map(|uri| uri.to_string()).collect())
+            .unwrap();
+        queue
+    }
+
+    #[test]
+    fn test_next_from_start() {
+        let queue = new_queue(&["a", "b"]);
+
+        assert_eq!(queue.next().unwrap().as_deref(), Some("a"));
+        assert_eq!(queue.next().unwrap().as_deref(), Some("b"));
+        assert_eq!(queue.next().unwrap(), None);
+        assert_eq!(queue.index().unwrap(), Some(1));
+    }
+
+    #[test]
+    fn test_peek_next_on_end() {
+        let queue = new_queue(&["a", "b"]);
+
+        queue.jump_to(0).unwrap();
+
+        assert_eq!(queue.peek_next_on_end().unwrap().as_deref(), Some("b"));
+        assert_eq!(queue.index().unwrap(), Some(0));
+    }
+
+    #[test]
+    fn test_repeat_one_on_end() {
+        let queue = new_queue(&["a", "b"]);
+
+        queue.jump_to(0).unwrap();
+        queue.set_repeat(Repeat::One).unwrap();
+
+        assert_eq!(queue.next_on_end().unwrap().as_deref(), Some("a"));
+        assert_eq!(queue.next().unwrap().as_deref(), Some("b"));
+    }
+
+    #[test]
+    fn test_shuffle_plays_every_track_once() {
+        let queue = new_queue(&["a", "b", "c", "d"]);
+
+        queue.set_shuffle_seed(Some(1)).unwrap();
+
+        let mut played = Vec::new();
+        while let Some(uri) = queue.next_on_end().unwrap() {
+            played.push(uri);
+        }
+        played.sort();
+
+        assert_eq!(played, vec!["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn test_previous() {
+        let queue = new_queue(&["a", "b"]);
+
+        queue.jump_to(1).unwrap();
+
+        assert_eq!(queue.previous().unwrap().as_deref(), Some("a"));
+        assert_eq!(queue.previous().unwrap(), None);
+        assert_eq!(queue.index().unwrap(), Some(0));
+    }
+
+    #[test]
+    fn test_jump_to_out_of_range() {
+        let queue = new_queue(&["a"]);
+
+        assert!(queue.jump_to(1).is_err());
+        assert_eq!(queue.index().unwrap(), None);
+    }
+
+    #[test]
+    fn test_play_now_inserts_after_current() {
+        let queue = new_queue(&["a", "b"]);
+
+        queue.jump_to(0).unwrap();
+        queue.play_now("c").unwrap();
+
+        assert_eq!(queue.uris().unwrap(), vec!["a", "c", "b"]);
+        assert_eq!(queue.current().unwrap().as_deref(), Some("c"));
+    }
+
+    #[test]
+    fn test_remove_before_current() {
+        let queue = new_queue(&["a", "b", "c"]);
+
+        queue.jump_to(2).unwrap();
+        queue.remove(0).unwrap();
+
+        assert_eq!(queue.current().unwrap().as_deref(), Some("c"));
+    }
+
+    #[test]
+    fn test_remove_current() {
+        let queue = new_queue(&["a", "b", "c"]);
+
+        queue.jump_to(1).unwrap();
+        queue.remove(1).unwrap();
+
+        assert_eq!(queue.next().unwrap().as_deref(), Some("c"));
+    }
+
+    #[test]
+    fn test_move_to() {
+        let queue = new_queue(&["a", "b", "c"]);
+
+        queue.jump_to(1).unwrap();
+        queue.move_to(0, 2).unwrap();
+
+        assert_eq!(queue.uris().unwrap(), vec!["b", "c", "a"]);
+        assert_eq!(queue.current().unwrap().as_deref(), Some("b"));
+    }
+
+    #[test]
+    fn test_move_current() {
+        let queue = new_queue(&["a", "b", "c"]);
+
+        queue.jump_to(0).unwrap();
+        queue.move_to(0, 2).unwrap();
+
+        assert_eq!(queue.current().unwrap().as_deref(), Some("a"));
+        assert_eq!(queue.index().unwrap(), Some(2));
+    }
+
+    #[test]
+    fn test_clear() {
+        let queue = new_queue(&["a", "b"]);
+
+        queue.jump_to(1).unwrap();
+        queue.clear().unwrap();
+
+        assert!(queue.uris().unwrap().is_empty());
+        assert_eq!(queue.current().unwrap(), None);
+    }
+}
@@ -0,0 +1,6 @@
+pub mod cue;
+pub mod entry;
+pub mod file;
+pub mod m3u;
+pub mod pls;
+pub mod xspf;
@@ -0,0 +1,244 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::local::{app_error::AppError, uri};
+
+use super::file;
+
+/// A virtual track is the sheet URI with this fragment and the track number.
+const TRACK_FRAGMENT: &str = "#track=";
+const FRAMES_PER_SECOND: i64 = 75;
+const NS_PER_SECOND: i64 = 1_000_000_000;
+
+/// A track of a single-file album, played as a segment of its file.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CueTrack {
+    pub number: u32,
+    /// The audio file, absolute.
+    pub file: PathBuf,
+    pub title: Option<String>,
+    pub performer: Option<String>,
+    pub album: Option<String>,
+    pub album_performer: Option<String>,
+    pub genre: Option<String>,
+    /// From `INDEX 01`, the pregap belongs to the previous track.
+    pub start_ns: i64,
+    /// `None` for the last track of its file, played to the end.
+    pub end_ns: Option<i64>,
+}
+
+pub fn is_cue_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("cue"))
+}
+
+pub fn load(path: &Path) -> Result<Vec<CueTrack>, AppError> {
+    let content = file::decode(fs::read(path)?);
+
+    parse(&content, path.parent().unwrap_or(path))
+}
+
+/// The file names are relative to the sheet folder, the unknown commands are skipped.
+pub fn parse(content: &str, base_dir: &Path) -> Result<Vec<CueTrack>, AppError> {
+    let mut tracks: Vec<CueTrack> = Vec::new();
+    let mut album = CueTrack::default();
+    let mut file = None;
+
+    for line in content.lines().map(str::trim) {
+        let (command, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let rest = rest.trim();
+        let track = tracks.last_mut();
+
+        match (command.to_ascii_uppercase().as_str(), track) {
+            ("FILE", _) => {
+                // `FILE "name" TYPE`, the name can contain spaces.
+                let name = match rest.rsplit_once(char::is_whitespace) {
+                    Some((name, _)) => unquote(name),
+                    None => unquote(rest),
+                };
+                file = Some(base_dir.join(name));
+            }
+            ("TRACK", _) => {
+                let number = rest
+                    .split_whitespace()
+                    .next()
+                    .and_then(|number| number.parse().ok())
+                    .ok_or_else(|| AppError::InvalidInput(format!("Invalid CUE track: {line}")))?;
+                let file = file.clone().ok_or_else(|| {
+                    AppError::InvalidInput(format!("The CUE track `{number}` has no file."))
+                })?;
+                tracks.push(CueTrack {
+                    number,
+                    file,
+                    start_ns: -1,
+                    ..album.clone()
+                });
+            }
+            ("TITLE", Some(track)) => track.title = Some(unquote(rest)),
+            ("TITLE", None) => album.album = Some(unquote(rest)),
+            ("PERFORMER", Some(track)) => track.performer = Some(unquote(rest)),
+            ("PERFORMER", None) => {
+                album.performer = Some(unquote(rest));
+                album.album_performer = Some(unquote(rest));
+            }
+            ("REM", None) => {
+                if let Some(genre) = rest.strip_prefix("GENRE ") {
+                    album.genre = Some(unquote(genre.trim()));
+                }
+            }
+            ("INDEX", Some(track)) => {
+                if let Some(time) = rest.strip_prefix("01 ") {
+                    track.start_ns = parse_time(time.trim())?;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(track) = tracks.iter().find(|track| track.start_ns < 0) {
+        return Err(AppError::InvalidInput(format!(
+            "The CUE track `{}` has no `INDEX 01`.",
+            track.number
+        )));
+    }
+
+    // A track ends where the next one of the same file starts.
+    let starts = tracks
+        .iter()
+        .map(|track| (track.file.clone(), track.start_ns))
+        .collect::<Vec<_>>();
+    for (track, next) in tracks.iter_mut().zip(starts.iter().skip(1)) {
+        track.end_ns = (track.file == next.0).then_some(next.1);
+    }
+
+    Ok(tracks)
+}
+
+/// Also the library path of the virtual track, unique as the file paths.
+pub fn track_uri(sheet: &Path, number: u32) -> Result<String, AppError> {
+    Ok(format!(
+        "{}{TRACK_FRAGMENT}{number}",
+        uri::from_path(sheet)?
+    ))
+}
+
+pub fn track_path(sheet: &Path, number: u32) -> PathBuf {
+    PathBuf::from(format!("{}{TRACK_FRAGMENT}{number}", sheet.display()))
+}
+
+/// The `#` of a file name is encoded in a file URI, only a fragment has one.
+pub fn is_track_uri(uri: &str) -> bool {
+    uri::is_file(uri) && uri.contains(TRACK_FRAGMENT)
+}
+
+/// Reads the sheet again, it is not kept between the tracks.
+pub fn load_track(track_uri: &str) -> Result<CueTrack, AppError> {
+    let (sheet_uri, number) = track_uri
+        .rsplit_once(TRACK_FRAGMENT)
+        .filter(|_| uri::is_file(track_uri))
+        .and_then(|(sheet_uri, number)| Some((sheet_uri, number.parse::<u32>().ok()?)))
+        .ok_or_else(|| {
+            AppError::InvalidUri(format!("The URI `{track_uri}` is not a CUE track."))
+        })?;
+
+    load(&uri::to_path(sheet_uri)?)?
+        .into_iter()
+        .find(|track| track.number == number)
+        .ok_or_else(|| AppError::InvalidUri(format!("The CUE track `{track_uri}` is missing.")))
+}
+
+/// `mm:ss:ff`, with 75 frames per second.
+fn parse_time(time: &str) -> Result<i64, AppError> {
+    let values = time
+        .split(':')
+        .map(|value| value.parse::<i64>().ok())
+        .collect::<Option<Vec<_>>>();
+
+    match values.as_deref() {
+        Some(&[minutes, seconds, frames]) => {
+            let frames = (minutes * 60 + seconds) * FRAMES_PER_SECOND + frames;
+            Ok(frames * NS_PER_SECOND / FRAMES_PER_SECOND)
+        }
+        _ => Err(AppError::InvalidInput(format!("Invalid CUE time: {time}"))),
+    }
+}
+
+fn unquote(value: &str) -> String {
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .unwrap_or(value)
+        .to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::{is_track_uri, parse, parse_time, track_path, track_uri, CueTrack};
+
+    const SHEET: &str = "REM GENRE Electronic\r\nPERFORMER \"Nomyn\"\r\nTITLE \"Fragments\"\r\n\
+                         FILE \"Nomyn - Fragments.flac\" WAVE\r\n  TRACK 01 AUDIO\r\n\
+                         \x20   TITLE \"Intro\"\r\n    INDEX 01 00:00:00\r\n  TRACK 02 AUDIO\r\n\
+                         \x20   TITLE \"Second\"\r\n    PERFORMER \"Guest\"\r\n\
+                         \x20   INDEX 00 03:59:00\r\n    INDEX 01 04:00:37\r\n";
+
+    #[test]
+    fn test_parse() {
+        let tracks = parse(SHEET, Path::new("/music/album")).unwrap();
+
+        let album = CueTrack {
+            file: PathBuf::from("/music/album/Nomyn - Fragments.flac"),
+            performer: Some("Nomyn".to_owned()),
+            album: Some("Fragments".to_owned()),
+            album_performer: Some("Nomyn".to_owned()),
+            genre: Some("Electronic".to_owned()),
+            ..CueTrack::default()
+        };
+        assert_eq!(
+            tracks,
+            vec![
+                CueTrack {
+                    number: 1,
+                    title: Some("Intro".to_owned()),
+                    start_ns: 0,
+                    end_ns: Some(240_493_333_333),
+                    ..album.clone()
+                },
+                CueTrack {
+                    number: 2,
+                    title: Some("Second".to_owned()),
+                    performer: Some("Guest".to_owned()),
+                    start_ns: 240_493_333_333,
+                    end_ns: None,
+                    ..album
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_err() {
+        assert!(parse("TRACK 01 AUDIO\nINDEX 01 00:00:00", Path::new("/music")).is_err());
+        assert!(parse("FILE \"a.flac\" WAVE\nTRACK 01 AUDIO", Path::new("/music")).is_err());
+        assert!(parse_time("01:02").is_err());
+    }
+
+    #[test]
+    fn test_track_uri() {
+        let sheet = Path::new("/music/album #1/album.cue");
+
+        let uri = track_uri(sheet, 2).unwrap();
+
+        assert_eq!(uri, "file:///music/album%20%231/album.cue#track=2");
+        assert!(is_track_uri(&uri));
+        assert!(!is_track_uri("file:///music/album%20%231/01.flac"));
+        assert_eq!(
+            track_path(sheet, 2),
+            PathBuf::from("/music/album #1/album.cue#track=2")
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use std::path::Path;
+
+use crate::{library::track::Track, local::uri};
+
+use super::cue;
+
+const NS_PER_SECOND: i64 = 1_000_000_000;
+
+/// A playlist item, only the URI is required in every format.
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Entry {
+    pub uri: String,
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub duration_ns: Option<i64>,
+}
+
+impl Entry {
+    pub fn new(uri: &str) -> Self {
+        Self {
+            uri: uri.to_owned(),
+            ..Self::default()
+        }
+    }
+}
+
+impl From<&Track> for Entry {
+    fn from(track: &Track) -> Self {
+        Self {
+            uri: track.uri.clone(),
+            title: track.title.clone(),
+            artist: track.artist.clone(),
+            album: track.album.clone(),
+            duration_ns: track.duration_ns,
+        }
+    }
+}
+
+/// A file under the playlist folder is written relative to it, as the other players do.
+/// A CUE track stays a URI, its fragment is not part of a path.
+pub fn location(uri: &str, base_dir: &Path) -> String {
+    if !uri::is_file(uri) || cue::is_track_uri(uri) {
+        return uri.to_owned();
+    }
+
+    match uri::to_path(uri) {
+        Ok(path) => path
+            .strip_prefix(base_dir)
+            .unwrap_or(&path)
+            .to_string_lossy()
+            .into_owned(),
+        Err(_) => uri.to_owned(),
+    }
+}
+
+/// From the seconds of M3U and PLS, negative for unknown.
+pub fn duration_from_seconds(seconds: &str) -> Option<i64> {
+    let seconds = seconds.trim().parse::<f64>().ok()?;
+
+    (seconds >= 0.0).then(|| (seconds * NS_PER_SECOND as f64).round() as i64)
+}
+
+/// A fraction only when there is one, `-1` for unknown.
+pub fn seconds(duration_ns: Option<i64>) -> String {
+    match duration_ns {
+        None => "-1".to_owned(),
+        Some(duration_ns) if duration_ns % NS_PER_SECOND == 0 => {
+            (duration_ns / NS_PER_SECOND).to_string()
+        }
+        Some(duration_ns) => (duration_ns as f64 / NS_PER_SECOND as f64).to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{duration_from_seconds, location, seconds};
+
+    #[test]
+    fn test_location() {
+        let base_dir = Path::new("/music/playlists");
+
+        assert_eq!(
+            location("file:///music/playlists/a%20b.flac", base_dir),
+            "a b.flac"
+        );
+        assert_eq!(location("file:///music/c.flac", base_dir), "/music/c.flac");
+        assert_eq!(
+            location("http://radio.example/live", base_dir),
+            "http://radio.example/live"
+        );
+        assert_eq!(
+            location("file:///music/playlists/album.cue#track=2", base_dir),
+            "file:///music/playlists/album.cue#track=2"
+        );
+    }
+
+    #[test]
+    fn test_seconds() {
+        assert_eq!(duration_from_seconds("215"), Some(215_000_000_000));
+        assert_eq!(duration_from_seconds("1.5"), Some(1_500_000_000));
+        assert_eq!(duration_from_seconds("-1"), None);
+        assert_eq!(duration_from_seconds("x"), None);
+        assert_eq!(seconds(Some(215_000_000_000)), "215");
+        assert_eq!(seconds(Some(1_500_000_000)), "1.5");
+        assert_eq!(seconds(None), "-1");
+    }
+}
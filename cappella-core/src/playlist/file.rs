@@ -0,0 +1,133 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::local::app_error::AppError;
+
+use super::{entry::Entry, m3u, pls, xspf};
+
+const BYTE_ORDER_MARK: char = '\u{feff}';
+
+/// From the file extension.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    M3u,
+    Pls,
+    Xspf,
+}
+
+impl Format {
+    pub fn from_path(path: &Path) -> Result<Self, AppError> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
+
+        match extension.as_deref() {
+            Some("m3u" | "m3u8") => Ok(Self::M3u),
+            Some("pls") => Ok(Self::Pls),
+            Some("xspf") => Ok(Self::Xspf),
+            _ => Err(AppError::InvalidPath(format!(
+                "The playlist `{}` is not a `.m3u`, `.m3u8`, `.pls` or `.xspf` file.",
+                path.display()
+            ))),
+        }
+    }
+}
+
+/// The relative paths of the entries are from the playlist folder.
+pub fn load(path: &Path) -> Result<Vec<Entry>, AppError> {
+    let format = Format::from_path(path)?;
+    let path = absolute(path)?;
+    let content = decode(fs::read(&path)?);
+    let base_dir = base_dir(&path);
+
+    match format {
+        Format::M3u => m3u::parse(&content, base_dir),
+        Format::Pls => pls::parse(&content, base_dir),
+        Format::Xspf => xspf::parse(&content, base_dir),
+    }
+}
+
+/// Always in UTF-8, even for `.m3u`.
+pub fn save(path: &Path, entries: &[Entry]) -> Result<(), AppError> {
+    let format = Format::from_path(path)?;
+    let path = absolute(path)?;
+    let base_dir = base_dir(&path);
+
+    let content = match format {
+        Format::M3u => m3u::write(entries, base_dir),
+        Format::Pls => pls::write(entries, base_dir),
+        Format::Xspf => xspf::write(entries),
+    };
+
+    fs::write(&path, content)?;
+
+    Ok(())
+}
+
+fn absolute(path: &Path) -> Result<PathBuf, AppError> {
+    Ok(env::current_dir()?.join(path))
+}
+
+fn base_dir(path: &Path) -> &Path {
+    path.parent().unwrap_or(path)
+}
+
+/// The older `.m3u` and `.cue` files are often in Latin-1, each byte is then its character.
+/// The byte order mark of UTF-8 is removed.
+pub fn decode(bytes: Vec<u8>) -> String {
+    match String::from_utf8(bytes) {
+        Ok(content) if content.starts_with(BYTE_ORDER_MARK) => {
+            content[BYTE_ORDER_MARK.len_utf8()..].to_owned()
+        }
+        Ok(content) => content,
+        Err(err) => err.into_bytes().into_iter().map(char::from).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::Path, process};
+
+    use crate::{local::uri, playlist::entry::Entry};
+
+    use super::{decode, load, save, Format};
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(Format::from_path(Path::new("a.M3U8")).unwrap(), Format::M3u);
+        assert_eq!(
+            Format::from_path(Path::new("a.xspf")).unwrap(),
+            Format::Xspf
+        );
+        assert!(Format::from_path(Path::new("a.txt")).is_err());
+        assert!(Format::from_path(Path::new("m3u")).is_err());
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode(b"Bj\xf6rk".to_vec()), "Björk");
+        assert_eq!(decode("Björk".as_bytes().to_vec()), "Björk");
+        assert_eq!(decode("\u{feff}#EXTM3U".as_bytes().to_vec()), "#EXTM3U");
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = env::temp_dir().join(format!("cappella-playlist-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let entries = vec![Entry {
+            title: Some("Fragments".to_owned()),
+            ..Entry::new(&uri::from_path(&dir.join("a b.mp3")).unwrap())
+        }];
+
+        for name in ["list.m3u8", "list.pls", "list.xspf"] {
+            save(&dir.join(name), &entries).unwrap();
+
+            assert_eq!(load(&dir.join(name)).unwrap(), entries);
+        }
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
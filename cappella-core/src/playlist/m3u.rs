@@ -0,0 +1,139 @@
+use std::path::Path;
+
+use crate::local::{app_error::AppError, uri};
+
+use super::entry::{self, Entry};
+
+const HEADER: &str = "#EXTM3U";
+const EXTINF: &str = "#EXTINF:";
+/// The `#EXTINF` title is `Artist - Title` when the artist is known.
+const ARTIST_SEPARATOR: &str = " - ";
+/// Marks a title holding the separator without artist, not to split it on parse.
+const NO_ARTIST_ATTRIBUTE: &str = "artist=\"\"";
+
+/// The comments and the unknown directives are skipped.
+pub fn parse(content: &str, base_dir: &Path) -> Result<Vec<Entry>, AppError> {
+    let mut entries = Vec::new();
+    let mut info = None;
+
+    for line in content.lines().map(str::trim) {
+        if let Some(extinf) = line.strip_prefix(EXTINF) {
+            info = Some(parse_extinf(extinf));
+        } else if !line.is_empty() && !line.starts_with('#') {
+            entries.push(Entry {
+                uri: uri::from_uri_or_relative_path(line, base_dir)?,
+                ..info.take().unwrap_or_default()
+            });
+        }
+    }
+
+    Ok(entries)
+}
+
+pub fn write(entries: &[Entry], base_dir: &Path) -> String {
+    let mut content = format!("{HEADER}\n");
+
+    for entry in entries {
+        if entry.title.is_some() || entry.artist.is_some() || entry.duration_ns.is_some() {
+            let attributes = match (&entry.artist, &entry.title) {
+                (None, Some(title)) if title.contains(ARTIST_SEPARATOR) => {
+                    format!(" {NO_ARTIST_ATTRIBUTE}")
+                }
+                _ => String::new(),
+            };
+            content.push_str(&format!(
+                "{EXTINF}{}{attributes},{}\n",
+                entry::seconds(entry.duration_ns),
+                display_title(entry)
+            ));
+        }
+        content.push_str(&entry::location(&entry.uri, base_dir));
+        content.push('\n');
+    }
+
+    content
+}
+
+/// `<seconds> [attributes],<title>`, the attributes of the IPTV lists are ignored.
+fn parse_extinf(extinf: &str) -> Entry {
+    let (duration, title) = extinf.split_once(',').unwrap_or((extinf, ""));
+    let mut fields = duration.split_whitespace();
+    let duration_ns = fields.next().and_then(entry::duration_from_seconds);
+    let has_artist = !fields.any(|attribute| attribute == NO_ARTIST_ATTRIBUTE);
+    let title = title.trim();
+
+    let (artist, title) = match title.split_once(ARTIST_SEPARATOR) {
+        Some((artist, title))
+            if has_artist && !artist.trim().is_empty() && !title.trim().is_empty() =>
+        {
+            (Some(artist.trim()), Some(title.trim()))
+        }
+        _ => (None, Some(title).filter(|title| !title.is_empty())),
+    };
+
+    Entry {
+        title: title.map(str::to_owned),
+        artist: artist.map(str::to_owned),
+        duration_ns,
+        ..Entry::default()
+    }
+}
+
+fn display_title(entry: &Entry) -> String {
+    match (&entry.artist, &entry.title) {
+        (Some(artist), Some(title)) => format!("{artist}{ARTIST_SEPARATOR}{title}"),
+        (Some(value), None) | (None, Some(value)) => value.clone(),
+        (None, None) => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::playlist::entry::Entry;
+
+    use super::{parse, write};
+
+    #[test]
+    fn test_parse() {
+        let content = "#EXTM3U\r\n#EXTINF:215,Nomyn - Fragments\r\nalbum/01 Fragments.mp3\r\n\
+                       # A comment\r\n#EXTINF:-1 tvg-id=\"radio\",Radio\r\nhttp://radio.example/live\r\n\
+                       /music/other.flac\r\n";
+
+        let entries = parse(content, Path::new("/music")).unwrap();
+
+        assert_eq!(
+            entries,
+            vec![
+                Entry {
+                    title: Some("Fragments".to_owned()),
+                    artist: Some("Nomyn".to_owned()),
+                    duration_ns: Some(215_000_000_000),
+                    ..Entry::new("file:///music/album/01%20Fragments.mp3")
+                },
+                Entry {
+                    title: Some("Radio".to_owned()),
+                    ..Entry::new("http://radio.example/live")
+                },
+                Entry::new("file:///music/other.flac"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let content = "#EXTM3U\n#EXTINF:215,Nomyn - Fragments\nalbum/01 Fragments.mp3\n\
+                       #EXTINF:1.5,Jingle\nhttp://radio.example/jingle.mp3\n/elsewhere/a.flac\n\
+                       #EXTINF:90 artist=\"\",Intro - Live\nintro.flac\n\
+                       #EXTINF:-1,Side B\nfile:///music/album/album.cue#track=2\n";
+        let base_dir = Path::new("/music");
+
+        let entries = parse(content, base_dir).unwrap();
+
+        assert_eq!(entries[3].title.as_deref(), Some("Intro - Live"));
+        assert_eq!(entries[3].artist, None);
+        assert_eq!(entries[4].uri, "file:///music/album/album.cue#track=2");
+        assert_eq!(write(&entries, base_dir), content);
+    }
+}
@@ -0,0 +1,123 @@
+use std::{collections::BTreeMap, path::Path};
+
+use crate::local::{app_error::AppError, uri};
+
+use super::entry::{self, Entry};
+
+const HEADER: &str = "[playlist]";
+
+/// The `FileN` entries with their `TitleN` and `LengthN`, sorted by number.
+pub fn parse(content: &str, base_dir: &Path) -> Result<Vec<Entry>, AppError> {
+    let mut lines = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with(';'));
+
+    if !lines
+        .next()
+        .is_some_and(|line| line.eq_ignore_ascii_case(HEADER))
+    {
+        return Err(AppError::InvalidInput(format!(
+            "A PLS playlist starts with `{HEADER}`."
+        )));
+    }
+
+    let mut entries = BTreeMap::<u32, (Option<String>, Entry)>::new();
+    for line in lines {
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(AppError::InvalidInput(format!("Invalid PLS line: {line}")));
+        };
+        let key = key.trim().to_ascii_lowercase();
+        let value = value.trim();
+
+        if let Some(number) = entry_number(&key, "file") {
+            entries.entry(number).or_default().0 = Some(value.to_owned());
+        } else if let Some(number) = entry_number(&key, "title") {
+            entries.entry(number).or_default().1.title =
+                Some(value.to_owned()).filter(|title| !title.is_empty());
+        } else if let Some(number) = entry_number(&key, "length") {
+            entries.entry(number).or_default().1.duration_ns = entry::duration_from_seconds(value);
+        }
+    }
+
+    entries
+        .into_values()
+        .filter_map(|(location, entry)| Some((location?, entry)))
+        .map(|(location, entry)| {
+            Ok(Entry {
+                uri: uri::from_uri_or_relative_path(&location, base_dir)?,
+                ..entry
+            })
+        })
+        .collect()
+}
+
+/// The artist and the album are not in the format, they are lost.
+pub fn write(entries: &[Entry], base_dir: &Path) -> String {
+    let mut content = format!("{HEADER}\n");
+
+    for (index, entry) in entries.iter().enumerate() {
+        let number = index + 1;
+        content.push_str(&format!(
+            "File{number}={}\n",
+            entry::location(&entry.uri, base_dir)
+        ));
+        if let Some(title) = &entry.title {
+            content.push_str(&format!("Title{number}={title}\n"));
+        }
+        content.push_str(&format!(
+            "Length{number}={}\n",
+            entry::seconds(entry.duration_ns)
+        ));
+    }
+    content.push_str(&format!("NumberOfEntries={}\nVersion=2\n", entries.len()));
+
+    content
+}
+
+fn entry_number(key: &str, prefix: &str) -> Option<u32> {
+    key.strip_prefix(prefix)?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::playlist::entry::Entry;
+
+    use super::{parse, write};
+
+    #[test]
+    fn test_parse() {
+        let content = "[Playlist]\r\nNumberOfEntries=2\r\n; A comment\r\n\
+                       File2=http://b.example/live\r\nFile1=a.flac\r\n\
+                       Title1=Radio A\r\nLength1=215\r\nLength3=1\r\nVersion=2\r\n";
+
+        assert_eq!(
+            parse(content, Path::new("/music")).unwrap(),
+            vec![
+                Entry {
+                    title: Some("Radio A".to_owned()),
+                    duration_ns: Some(215_000_000_000),
+                    ..Entry::new("file:///music/a.flac")
+                },
+                Entry::new("http://b.example/live"),
+            ]
+        );
+        assert!(parse("File1=http://a.example/live", Path::new("/music")).is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let content = "[playlist]\nFile1=a.flac\nTitle1=Radio A\nLength1=215\n\
+                       File2=http://b.example/live\nLength2=-1\n\
+                       File3=file:///music/album.cue#track=2\nLength3=-1\n\
+                       NumberOfEntries=3\nVersion=2\n";
+        let base_dir = Path::new("/music");
+
+        let entries = parse(content, base_dir).unwrap();
+
+        assert_eq!(entries[2].uri, "file:///music/album.cue#track=2");
+        assert_eq!(write(&entries, base_dir), content);
+    }
+}
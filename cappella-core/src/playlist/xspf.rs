@@ -0,0 +1,157 @@
+use std::path::Path;
+
+use roxmltree::{Document, Node};
+
+use crate::local::{app_error::AppError, uri};
+
+use super::entry::Entry;
+
+const NAMESPACE: &str = "http://xspf.org/ns/0/";
+const NS_PER_MS: i64 = 1_000_000;
+
+/// The tracks without location are skipped.
+pub fn parse(content: &str, base_dir: &Path) -> Result<Vec<Entry>, AppError> {
+    let document = Document::parse(content)
+        .map_err(|err| AppError::InvalidInput(format!("Invalid XSPF playlist: {err}")))?;
+
+    document
+        .descendants()
+        .filter(|node| node.has_tag_name("track"))
+        .filter_map(|track| {
+            let location = child_text(track, "location")?;
+
+            Some(parse_location(&location, base_dir).map(|uri| {
+                Entry {
+                    uri,
+                    title: child_text(track, "title"),
+                    artist: child_text(track, "creator"),
+                    album: child_text(track, "album"),
+                    duration_ns: child_text(track, "duration")
+                        .and_then(|duration| duration.parse::<i64>().ok())
+                        .map(|duration_ms| duration_ms * NS_PER_MS),
+                }
+            }))
+        })
+        .collect()
+}
+
+/// The locations are absolute URIs, valid wherever the playlist is moved.
+pub fn write(entries: &[Entry]) -> String {
+    let mut content = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <playlist version=\"1\" xmlns=\"{NAMESPACE}\">\n  <trackList>\n"
+    );
+
+    for entry in entries {
+        content.push_str("    <track>\n");
+        push_element(&mut content, "location", Some(&entry.uri));
+        push_element(&mut content, "title", entry.title.as_deref());
+        push_element(&mut content, "creator", entry.artist.as_deref());
+        push_element(&mut content, "album", entry.album.as_deref());
+        push_element(
+            &mut content,
+            "duration",
+            entry
+                .duration_ns
+                .map(|duration_ns| (duration_ns / NS_PER_MS).to_string())
+                .as_deref(),
+        );
+        content.push_str("    </track>\n");
+    }
+    content.push_str("  </trackList>\n</playlist>\n");
+
+    content
+}
+
+fn child_text(node: Node, name: &str) -> Option<String> {
+    node.children()
+        .find(|child| child.has_tag_name(name))?
+        .text()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .map(str::to_owned)
+}
+
+/// A relative location is a percent-encoded URI reference.
+fn parse_location(location: &str, base_dir: &Path) -> Result<String, AppError> {
+    if location.contains("://") {
+        return Ok(location.to_owned());
+    }
+
+    uri::from_uri_or_relative_path(&uri::percent_decode(location)?, base_dir)
+}
+
+fn push_element(content: &mut String, name: &str, value: Option<&str>) {
+    if let Some(value) = value {
+        content.push_str(&format!("      <{name}>{}</{name}>\n", escape(value)));
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::playlist::entry::Entry;
+
+    use super::{parse, write};
+
+    #[test]
+    fn test_parse() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <playlist version="1" xmlns="http://xspf.org/ns/0/">
+              <title>Mix</title>
+              <trackList>
+                <track>
+                  <location>album/01%20Fragments.mp3</location>
+                  <title>Fragments &amp; more</title>
+                  <creator>Nomyn</creator>
+                  <duration>215000</duration>
+                </track>
+                <track><title>No location</title></track>
+                <track><location>http://radio.example/live</location></track>
+              </trackList>
+            </playlist>"#;
+
+        assert_eq!(
+            parse(content, Path::new("/music")).unwrap(),
+            vec![
+                Entry {
+                    title: Some("Fragments & more".to_owned()),
+                    artist: Some("Nomyn".to_owned()),
+                    duration_ns: Some(215_000_000_000),
+                    ..Entry::new("file:///music/album/01%20Fragments.mp3")
+                },
+                Entry::new("http://radio.example/live"),
+            ]
+        );
+        assert!(parse("<playlist>", Path::new("/music")).is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let entries = vec![
+            Entry {
+                title: Some("Fragments <live>".to_owned()),
+                artist: Some("Nomyn".to_owned()),
+                album: Some("Rock & Roll".to_owned()),
+                duration_ns: Some(215_000_000_000),
+                ..Entry::new("file:///music/01%20Fragments.mp3")
+            },
+            Entry::new("http://radio.example/live"),
+        ];
+
+        assert_eq!(
+            parse(&write(&entries), Path::new("/other")).unwrap(),
+            entries
+        );
+    }
+}
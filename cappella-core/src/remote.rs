@@ -0,0 +1,4 @@
+//! The remote control over HTTP, with the player events on a WebSocket.
+
+pub mod http;
+pub mod server;
@@ -0,0 +1,179 @@
+use std::io::{BufRead, Read, Write};
+
+use crate::local::app_error::AppError;
+
+/// Enough for the commands, a larger request is refused.
+const MAX_LINE_LENGTH: usize = 8 * 1024;
+const MAX_HEADERS: usize = 64;
+const MAX_BODY_LENGTH: usize = 64 * 1024;
+
+/// A minimal HTTP/1.1 request, one per connection.
+#[derive(Debug, Default, PartialEq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub query: Option<String>,
+    /// The names are in lowercase.
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    pub fn read(reader: &mut impl BufRead) -> Result<Self, AppError> {
+        let request_line = read_line(reader)?;
+        let mut parts = request_line.split_whitespace();
+        let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+            return Err(AppError::InvalidInput(format!(
+                "Invalid request line: {request_line}"
+            )));
+        };
+        let (path, query) = match target.split_once('?') {
+            Some((path, query)) => (path, Some(query.to_owned())),
+            None => (target, None),
+        };
+
+        let mut headers = Vec::new();
+        loop {
+            let line = read_line(reader)?;
+            if line.is_empty() {
+                break;
+            }
+            if headers.len() == MAX_HEADERS {
+                return Err(AppError::InvalidInput("Too many headers.".to_owned()));
+            }
+            let (name, value) = line
+                .split_once(':')
+                .ok_or_else(|| AppError::InvalidInput(format!("Invalid header: {line}")))?;
+            headers.push((name.trim().to_ascii_lowercase(), value.trim().to_owned()));
+        }
+
+        let mut request = Self {
+            method: method.to_owned(),
+            path: path.to_owned(),
+            query,
+            headers,
+            body: Vec::new(),
+        };
+
+        let length = match request.header("content-length") {
+            Some(length) => length
+                .parse()
+                .map_err(|_| AppError::InvalidInput(format!("Invalid length: {length}")))?,
+            None => 0,
+        };
+        if length > MAX_BODY_LENGTH {
+            return Err(AppError::InvalidInput("The body is too large.".to_owned()));
+        }
+        request.body = vec![0; length];
+        reader.read_exact(&mut request.body)?;
+
+        Ok(request)
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header_name, _)| header_name == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Not decoded, the values are expected in plain ASCII.
+    pub fn query_param(&self, name: &str) -> Option<&str> {
+        self.query.as_deref()?.split('&').find_map(|param| {
+            param
+                .split_once('=')
+                .filter(|(param_name, _)| *param_name == name)
+                .map(|(_, value)| value)
+        })
+    }
+}
+
+pub fn write_response(
+    writer: &mut impl Write,
+    status: u16,
+    content_type: &str,
+    body: &[u8],
+) -> Result<(), AppError> {
+    write!(
+        writer,
+        "HTTP/1.1 {status} {}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n",
+        reason(status),
+        body.len()
+    )?;
+    writer.write_all(body)?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+fn read_line(reader: &mut impl BufRead) -> Result<String, AppError> {
+    let mut line = String::new();
+    reader.take(MAX_LINE_LENGTH as u64).read_line(&mut line)?;
+
+    if !line.ends_with('\n') {
+        return Err(AppError::InvalidInput(
+            "The line is incomplete or too long.".to_owned(),
+        ));
+    }
+
+    Ok(line.trim_end().to_owned())
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        101 => "Switching Protocols",
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::{write_response, Request};
+
+    #[test]
+    fn test_read() {
+        let mut reader = Cursor::new(
+            "POST /api/seek?token=abc HTTP/1.1\r\nHost: phone\r\nContent-Length: 23\r\n\r\n\
+             {\"position_ns\":1000000}",
+        );
+
+        let request = Request::read(&mut reader).unwrap();
+
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/api/seek");
+        assert_eq!(request.query_param("token"), Some("abc"));
+        assert_eq!(request.header("host"), Some("phone"));
+        assert_eq!(request.body, b"{\"position_ns\":1000000}");
+    }
+
+    #[test]
+    fn test_read_err() {
+        assert!(Request::read(&mut Cursor::new("GET\r\n\r\n")).is_err());
+        assert!(Request::read(&mut Cursor::new("GET / HTTP/1.1\r\nHost")).is_err());
+        assert!(Request::read(&mut Cursor::new(
+            "GET / HTTP/1.1\r\nContent-Length: 9999999\r\n\r\n"
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn test_write_response() {
+        let mut buffer = Vec::new();
+
+        write_response(&mut buffer, 404, "text/plain", b"Not found").unwrap();
+
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "HTTP/1.1 404 Not Found\r\nContent-Type: text/plain\r\nContent-Length: 9\r\n\
+             Connection: close\r\n\r\nNot found"
+        );
+    }
+}
@@ -0,0 +1,364 @@
+use std::{
+    io::{self, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use parking_lot::Mutex;
+use serde_json::{Map, Value};
+use tungstenite::{handshake::derive_accept_key, protocol::Role, Message, WebSocket};
+
+use crate::{
+    control::{
+        request::{self, Request, Response},
+        subscribers::Subscribers,
+    },
+    local::{app_error::AppError, state::State},
+};
+
+use super::http::{self, Request as HttpRequest};
+
+const THREAD_NAME: &str = "remote";
+const API_PREFIX: &str = "/api/";
+const EVENTS_PATH: &str = "/api/events";
+/// The connections over it are closed without response.
+const MAX_CONNECTIONS: usize = 32;
+/// A client which stops sending or reading is disconnected after it.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+const WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+const INDEX_HTML: &str = include_str!("index.html");
+/// Only the player is controlled from the network, the commands reading or writing files are
+/// not served.
+const PLAYER_COMMANDS: [&str; 10] = [
+    "play",
+    "pause",
+    "stop",
+    "seek",
+    "next",
+    "previous",
+    "jump_to",
+    "enqueue",
+    "set_volume",
+    "set_mute",
+];
+
+/// Listens in background, one thread per connection up to `MAX_CONNECTIONS`.
+pub fn start(
+    address: &str,
+    token: &str,
+    state: State,
+    subscribers: Arc<Subscribers>,
+) -> Result<(), AppError> {
+    if token.is_empty() {
+        return Err(AppError::InvalidState(
+            "A token is required for the remote control.".to_owned(),
+        ));
+    }
+
+    let listener = TcpListener::bind(address)?;
+    let token = token.to_owned();
+    let connections = Arc::new(AtomicUsize::new(0));
+
+    thread::Builder::new()
+        .name(THREAD_NAME.to_owned())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                let state = state.clone();
+                let subscribers = subscribers.clone();
+                let token = token.clone();
+                let connections = connections.clone();
+
+                let result = stream.map_err(AppError::from).and_then(|stream| {
+                    let slot = Slot::acquire(&connections).ok_or_else(|| {
+                        AppError::InvalidState("Too many remote connections.".to_owned())
+                    })?;
+
+                    thread::Builder::new()
+                        .name(THREAD_NAME.to_owned())
+                        .spawn(move || {
+                            if let Err(err) =
+                                handle_connection(stream, slot, &token, &state, &subscribers)
+                            {
+                                eprintln!("Remote connection error: {err}");
+                            }
+                        })
+                        .map(|_| ())
+                        .map_err(AppError::from)
+                });
+
+                if let Err(err) = result {
+                    eprintln!("Remote connection error: {err}");
+                }
+            }
+        })?;
+
+    Ok(())
+}
+
+/// A connection counted against `MAX_CONNECTIONS` until it is dropped.
+struct Slot(Arc<AtomicUsize>);
+
+impl Slot {
+    fn acquire(connections: &Arc<AtomicUsize>) -> Option<Self> {
+        if connections.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+            connections.fetch_sub(1, Ordering::SeqCst);
+            return None;
+        }
+
+        Some(Self(connections.clone()))
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+fn handle_connection(
+    mut stream: TcpStream,
+    slot: Slot,
+    token: &str,
+    state: &State,
+    subscribers: &Subscribers,
+) -> Result<(), AppError> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+
+    let request = match HttpRequest::read(&mut BufReader::new(stream.try_clone()?)) {
+        Ok(request) => request,
+        Err(err) => return write_json(&mut stream, Response::Error(err)),
+    };
+
+    let Some(name) = request.path.strip_prefix(API_PREFIX) else {
+        return match (request.method.as_str(), request.path.as_str()) {
+            ("GET", "/" | "/index.html") => http::write_response(
+                &mut stream,
+                200,
+                "text/html; charset=utf-8",
+                INDEX_HTML.as_bytes(),
+            ),
+            _ => http::write_response(&mut stream, 404, "text/plain", b"Not found"),
+        };
+    };
+
+    if !is_authorized(&request, token) {
+        return http::write_response(&mut stream, 401, "text/plain", b"Unauthorized");
+    }
+
+    let api_request = match (request.method.as_str(), name) {
+        ("GET", "events") => return subscribe(stream, slot, &request, subscribers),
+        ("GET", "status") => Ok(Request::GetStatus),
+        ("GET", "volume") => Ok(Request::GetVolume),
+        ("POST", name) if PLAYER_COMMANDS.contains(&name) => command_request(name, &request.body),
+        (_, name)
+            if matches!(name, "events" | "status" | "volume")
+                || PLAYER_COMMANDS.contains(&name) =>
+        {
+            return http::write_response(&mut stream, 405, "text/plain", b"Method not allowed")
+        }
+        _ => return http::write_response(&mut stream, 404, "text/plain", b"Not found"),
+    };
+
+    let response: Response = api_request
+        .and_then(|api_request| request::execute(state, api_request))
+        .into();
+
+    write_json(&mut stream, response)
+}
+
+/// With the `Authorization: Bearer` header. The browsers cannot set it on a WebSocket, the
+/// `token` parameter is accepted there only, to keep the token out of the other URLs.
+fn is_authorized(request: &HttpRequest, token: &str) -> bool {
+    let bearer = request
+        .header("authorization")
+        .and_then(|value| value.strip_prefix("Bearer "));
+    let given = match request.path.as_str() {
+        EVENTS_PATH => bearer.or_else(|| request.query_param("token")),
+        _ => bearer,
+    }
+    .unwrap_or_default();
+
+    // In constant time, the comparison does not reveal the matching prefix.
+    given.len() == token.len()
+        && given
+            .bytes()
+            .zip(token.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// The body is the JSON request of the control socket without `command`, empty for none.
+fn command_request(name: &str, body: &[u8]) -> Result<Request, AppError> {
+    let mut fields = if body.iter().all(u8::is_ascii_whitespace) {
+        Map::new()
+    } else {
+        match serde_json::from_slice(body)? {
+            Value::Object(fields) => fields,
+            _ => {
+                return Err(AppError::InvalidInput(
+                    "A JSON object is expected.".to_owned(),
+                ))
+            }
+        }
+    };
+    fields.insert("command".to_owned(), Value::String(name.to_owned()));
+
+    serde_json::from_value(Value::Object(fields))
+        .map_err(|err| AppError::InvalidInput(format!("Invalid request: {err}")))
+}
+
+fn write_json(stream: &mut TcpStream, response: Response) -> Result<(), AppError> {
+    let status = match &response {
+        Response::Error(err) => status(err),
+        _ => 200,
+    };
+
+    http::write_response(
+        stream,
+        status,
+        "application/json",
+        &serde_json::to_vec(&response)?,
+    )
+}
+
+fn status(err: &AppError) -> u16 {
+    match err {
+        AppError::InvalidInput(_)
+        | AppError::InvalidPath(_)
+        | AppError::InvalidUri(_)
+        | AppError::OutOfRange { .. }
+        | AppError::Json(_) => 400,
+        _ => 500,
+    }
+}
+
+/// Upgrades to a WebSocket receiving the events, one JSON text message per event. The socket
+/// keeps the slot of the connection until the subscriber is removed.
+fn subscribe(
+    mut stream: TcpStream,
+    slot: Slot,
+    request: &HttpRequest,
+    subscribers: &Subscribers,
+) -> Result<(), AppError> {
+    let is_websocket = request
+        .header("upgrade")
+        .is_some_and(|upgrade| upgrade.eq_ignore_ascii_case("websocket"));
+    let (true, Some(key)) = (is_websocket, request.header("sec-websocket-key")) else {
+        return http::write_response(&mut stream, 400, "text/plain", b"WebSocket expected");
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+         Sec-WebSocket-Accept: {}\r\n\r\n",
+        derive_accept_key(key.as_bytes())
+    )?;
+    stream.flush()?;
+
+    subscribers.add(Arc::new(Mutex::new(EventSocket {
+        socket: WebSocket::from_raw_socket(stream, Role::Server, None),
+        line: Vec::new(),
+        _slot: slot,
+    })))
+}
+
+/// The subscribers write the event lines, each line is sent as a message on flush.
+struct EventSocket {
+    socket: WebSocket<TcpStream>,
+    line: Vec<u8>,
+    _slot: Slot,
+}
+
+impl Write for EventSocket {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.line.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let line = String::from_utf8(std::mem::take(&mut self.line))
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+        self.socket
+            .send(Message::text(line.trim_end()))
+            .map_err(io::Error::other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use crate::{control::request::Request, remote::http::Request as HttpRequest};
+
+    use super::{command_request, is_authorized, Slot, MAX_CONNECTIONS, PLAYER_COMMANDS};
+
+    #[test]
+    fn test_command_request() {
+        assert_eq!(command_request("pause", b"").unwrap(), Request::Pause);
+        assert_eq!(
+            command_request("seek", br#"{"position_ns":30000000000}"#).unwrap(),
+            Request::Seek {
+                position_ns: 30_000_000_000
+            }
+        );
+        assert!(command_request("seek", b"").is_err());
+        assert!(command_request("unknown", b"").is_err());
+        assert!(command_request("pause", b"[]").is_err());
+    }
+
+    #[test]
+    fn test_slot() {
+        let connections = Arc::new(AtomicUsize::new(0));
+
+        let slots = (0..MAX_CONNECTIONS)
+            .map(|_| Slot::acquire(&connections).unwrap())
+            .collect::<Vec<_>>();
+        assert!(Slot::acquire(&connections).is_none());
+
+        drop(slots);
+        assert_eq!(connections.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_player_commands() {
+        for name in PLAYER_COMMANDS {
+            let error = command_request(name, b"").err().map(|err| err.to_string());
+            assert!(!error.is_some_and(|error| error.contains("unknown variant")));
+        }
+
+        assert!(!PLAYER_COMMANDS.contains(&"export_stations"));
+        assert!(!PLAYER_COMMANDS.contains(&"load_playlist"));
+    }
+
+    #[test]
+    fn test_is_authorized() {
+        let is_authorized = |path: &str, header: Option<&str>, query: Option<&str>| {
+            let request = HttpRequest {
+                path: path.to_owned(),
+                headers: header
+                    .map(|value| vec![("authorization".to_owned(), value.to_owned())])
+                    .unwrap_or_default(),
+                query: query.map(str::to_owned),
+                ..Default::default()
+            };
+            is_authorized(&request, "secret")
+        };
+
+        assert!(is_authorized("/api/pause", Some("Bearer secret"), None));
+        assert!(is_authorized("/api/events", None, Some("token=secret")));
+        assert!(!is_authorized("/api/pause", None, Some("token=secret")));
+        assert!(!is_authorized("/api/pause", Some("Bearer secre"), None));
+        assert!(!is_authorized("/api/events", None, Some("token=other")));
+        assert!(!is_authorized("/api/events", None, None));
+    }
+}
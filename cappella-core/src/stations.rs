@@ -0,0 +1,4 @@
+pub mod front;
+pub mod icy;
+pub mod station;
+pub mod store;
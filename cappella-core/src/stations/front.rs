@@ -0,0 +1,190 @@
+use std::{fmt::Debug, fs, path::Path, sync::Arc};
+
+use crate::{
+    local::app_error::AppError,
+    playlist::{entry::Entry, pls},
+};
+
+use super::{station::Station, store::Store};
+
+pub trait Front: Debug + Send + Sync {
+    fn stations(&self) -> Result<Vec<Station>, AppError>;
+    fn station(&self, name: &str) -> Result<Station, AppError>;
+    fn add_station(&self, station: Station) -> Result<(), AppError>;
+    fn remove_station(&self, name: &str) -> Result<(), AppError>;
+    fn import_stations(&self, path: &Path) -> Result<usize, AppError>;
+    fn export_stations(&self, path: &Path) -> Result<(), AppError>;
+}
+
+pub fn new_box(store: Arc<dyn Store>) -> Box<dyn Front> {
+    Box::new(Front_ { store })
+}
+
+#[derive(Debug)]
+struct Front_ {
+    store: Arc<dyn Store>,
+}
+
+impl Front for Front_ {
+    fn stations(&self) -> Result<Vec<Station>, AppError> {
+        self.store.stations()
+    }
+
+    fn station(&self, name: &str) -> Result<Station, AppError> {
+        self.store
+            .stations()?
+            .into_iter()
+            .find(|station| station.name == name)
+            .ok_or_else(|| AppError::InvalidInput(format!("Unknown station `{name}`.")))
+    }
+
+    /// A station with the same name is replaced.
+    fn add_station(&self, station: Station) -> Result<(), AppError> {
+        station.validate()?;
+
+        self.store
+            .update(&|stations| upsert(stations, station.clone()))
+    }
+
+    fn remove_station(&self, name: &str) -> Result<(), AppError> {
+        self.station(name)?;
+
+        self.store
+            .update(&|stations| stations.retain(|station| station.name != name))
+    }
+
+    /// Merged by name, the invalid stations are skipped. Returns the number imported.
+    fn import_stations(&self, path: &Path) -> Result<usize, AppError> {
+        let content = fs::read_to_string(path)?;
+        let imported = match Format::from_path(path)? {
+            Format::Json => serde_json::from_str::<Vec<Station>>(&content)?,
+            Format::Pls => pls::parse(&content, base_dir(path))?
+                .into_iter()
+                .map(|entry| Station::new(entry.title.as_deref().unwrap_or(&entry.uri), &entry.uri))
+                .collect(),
+        }
+        .into_iter()
+        .filter(|station| match station.validate() {
+            Ok(()) => true,
+            Err(err) => {
+                eprintln!("Station `{}` skipped: {err}", station.name);
+                false
+            }
+        })
+        .collect::<Vec<_>>();
+
+        self.store.update(&|stations| {
+            for station in &imported {
+                upsert(stations, station.clone());
+            }
+        })?;
+
+        Ok(imported.len())
+    }
+
+    fn export_stations(&self, path: &Path) -> Result<(), AppError> {
+        let stations = self.store.stations()?;
+        let content = match Format::from_path(path)? {
+            Format::Json => serde_json::to_string_pretty(&stations)?,
+            Format::Pls => pls::write(
+                &stations
+                    .iter()
+                    .map(|station| Entry {
+                        title: Some(station.name.clone()),
+                        ..Entry::new(&station.url)
+                    })
+                    .collect::<Vec<_>>(),
+                base_dir(path),
+            ),
+        };
+
+        fs::write(path, content)?;
+
+        Ok(())
+    }
+}
+
+/// From the file extension.
+enum Format {
+    Json,
+    Pls,
+}
+
+impl Format {
+    fn from_path(path: &Path) -> Result<Self, AppError> {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("json") => Ok(Self::Json),
+            Some(extension) if extension.eq_ignore_ascii_case("pls") => Ok(Self::Pls),
+            _ => Err(AppError::InvalidPath(format!(
+                "The station list `{}` is not a `.json` or `.pls` file.",
+                path.display()
+            ))),
+        }
+    }
+}
+
+/// The URLs are absolute, only needed by the parser for the local files.
+fn base_dir(path: &Path) -> &Path {
+    path.parent().unwrap_or(path)
+}
+
+fn upsert(stations: &mut Vec<Station>, station: Station) {
+    match stations
+        .iter_mut()
+        .find(|existing| existing.name == station.name)
+    {
+        Some(existing) => *existing = station,
+        None => stations.push(station),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use crate::stations::{station::Station, store};
+
+    use super::new_box;
+
+    #[test]
+    fn test_add_station_replaces_same_name() {
+        let dir = env::temp_dir().join(format!("cappella-stations-front-{}", process::id()));
+        let front = new_box(store::new_arc(dir.join("stations.json")));
+
+        front
+            .add_station(Station::new("Radio", "http://a.example/live"))
+            .unwrap();
+        front
+            .add_station(Station::new("Radio", "http://b.example/live"))
+            .unwrap();
+
+        assert_eq!(
+            front.stations().unwrap(),
+            vec![Station::new("Radio", "http://b.example/live")]
+        );
+        assert!(front
+            .add_station(Station::new("Local", "file:///music/a.flac"))
+            .is_err());
+        assert!(front.remove_station("Unknown").is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_export_and_import() {
+        let dir = env::temp_dir().join(format!("cappella-stations-export-{}", process::id()));
+        let front = new_box(store::new_arc(dir.join("stations.json")));
+        let other = new_box(store::new_arc(dir.join("other.json")));
+        front
+            .add_station(Station::new("Radio", "http://a.example/live"))
+            .unwrap();
+
+        front.export_stations(&dir.join("stations.pls")).unwrap();
+
+        assert_eq!(other.import_stations(&dir.join("stations.pls")).unwrap(), 1);
+        assert_eq!(other.station("Radio").unwrap().url, "http://a.example/live");
+        assert!(front.export_stations(&dir.join("stations.txt")).is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
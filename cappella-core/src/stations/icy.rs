@@ -0,0 +1,63 @@
+use crate::streamer::now_playing::NowPlaying;
+
+/// By convention, the ICY `StreamTitle` is `Artist - Title`.
+const SEPARATOR: &str = " - ";
+
+/// The ICY metadata of a radio, received by `icydemux` as a title tag without artist.
+pub fn is_stream_title(now_playing: &NowPlaying) -> bool {
+    now_playing.title.is_some() && now_playing.artist.is_none()
+}
+
+/// Splits the stream title into the artist and the title, unchanged without separator.
+pub fn split_stream_title(now_playing: &mut NowPlaying) {
+    let Some((artist, title)) = now_playing
+        .title
+        .as_deref()
+        .and_then(|stream_title| stream_title.split_once(SEPARATOR))
+        .map(|(artist, title)| (artist.trim().to_owned(), title.trim().to_owned()))
+    else {
+        return;
+    };
+
+    if !artist.is_empty() && !title.is_empty() {
+        now_playing.artist = Some(artist);
+        now_playing.title = Some(title);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::streamer::now_playing::NowPlaying;
+
+    use super::{is_stream_title, split_stream_title};
+
+    fn with_title(title: &str) -> NowPlaying {
+        NowPlaying {
+            title: Some(title.to_owned()),
+            ..NowPlaying::new("http://radio.example/live")
+        }
+    }
+
+    #[test]
+    fn test_split_stream_title() {
+        let mut now_playing = with_title("Nomyn - Fragments - Live");
+        assert!(is_stream_title(&now_playing));
+
+        split_stream_title(&mut now_playing);
+
+        assert_eq!(now_playing.artist.as_deref(), Some("Nomyn"));
+        assert_eq!(now_playing.title.as_deref(), Some("Fragments - Live"));
+        assert!(!is_stream_title(&now_playing));
+    }
+
+    #[test]
+    fn test_split_stream_title_unchanged() {
+        for stream_title in ["Radio jingle", " - Fragments", "Nomyn - "] {
+            let mut now_playing = with_title(stream_title);
+
+            split_stream_title(&mut now_playing);
+
+            assert_eq!(now_playing, with_title(stream_title));
+        }
+    }
+}
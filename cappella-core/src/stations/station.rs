@@ -0,0 +1,56 @@
+use crate::{local::app_error::AppError, streamer::reconnect::is_network_uri};
+
+/// A radio station saved by the user, the name is its key.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Station {
+    pub name: String,
+    pub url: String,
+    pub genre: Option<String>,
+    /// An image URL.
+    pub logo: Option<String>,
+}
+
+impl Station {
+    pub fn new(name: &str, url: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            url: url.to_owned(),
+            genre: None,
+            logo: None,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.name.trim().is_empty() {
+            return Err(AppError::InvalidInput(
+                "A station name is required.".to_owned(),
+            ));
+        }
+        if !is_network_uri(&self.url) {
+            return Err(AppError::InvalidUri(format!(
+                "The station URL `{}` is not a network stream.",
+                self.url
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Station;
+
+    #[test]
+    fn test_validate() {
+        assert!(Station::new("Radio", "http://radio.example/live")
+            .validate()
+            .is_ok());
+        assert!(Station::new(" ", "http://radio.example/live")
+            .validate()
+            .is_err());
+        assert!(Station::new("Radio", "file:///music/a.flac")
+            .validate()
+            .is_err());
+    }
+}
@@ -0,0 +1,120 @@
+use std::{
+    fmt::Debug,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use parking_lot::Mutex;
+
+use crate::local::{app_error::AppError, mutex_lock_timeout::MutexLockTimeout};
+
+use super::station::Station;
+
+pub const STATIONS_FILE_NAME: &str = "stations.json";
+
+pub trait Store: Debug + Send + Sync {
+    fn stations(&self) -> Result<Vec<Station>, AppError>;
+    fn update(&self, update: &dyn Fn(&mut Vec<Station>)) -> Result<(), AppError>;
+}
+
+pub fn new_arc(path: PathBuf) -> Arc<dyn Store> {
+    let stations = load(&path).unwrap_or_else(|err| {
+        eprintln!("Unable to load the stations, the list is empty: {err}");
+        Vec::new()
+    });
+
+    Arc::new(Store_ {
+        path,
+        stations: Mutex::new(stations),
+    })
+}
+
+/// A JSON file next to the settings, also the export format.
+#[derive(Debug)]
+struct Store_ {
+    path: PathBuf,
+    stations: Mutex<Vec<Station>>,
+}
+
+impl Store for Store_ {
+    fn stations(&self) -> Result<Vec<Station>, AppError> {
+        Ok(self.stations.try_lock_default_duration()?.clone())
+    }
+
+    /// Kept in memory only once saved, the list stays as the file on a failed write.
+    fn update(&self, update: &dyn Fn(&mut Vec<Station>)) -> Result<(), AppError> {
+        let mut stations = self.stations.try_lock_default_duration()?;
+        let mut updated = stations.clone();
+        update(&mut updated);
+
+        save(&self.path, &updated)?;
+        *stations = updated;
+
+        Ok(())
+    }
+}
+
+fn load(path: &Path) -> Result<Vec<Station>, AppError> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(path)?;
+
+    Ok(serde_json::from_str(&content)?)
+}
+
+fn save(path: &Path, stations: &[Station]) -> Result<(), AppError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(path, serde_json::to_string_pretty(stations)?)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use crate::stations::station::Station;
+
+    use super::new_arc;
+
+    #[test]
+    fn test_update_is_persisted() {
+        let path = env::temp_dir()
+            .join(format!("cappella-stations-{}", process::id()))
+            .join("stations.json");
+
+        new_arc(path.clone())
+            .update(&|stations| stations.push(Station::new("Radio", "http://radio.example/live")))
+            .unwrap();
+        let store = new_arc(path.clone());
+
+        assert_eq!(
+            store.stations().unwrap(),
+            vec![Station::new("Radio", "http://radio.example/live")]
+        );
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_update_not_saved() {
+        let file = env::temp_dir().join(format!("cappella-stations-file-{}", process::id()));
+        fs::write(&file, "").unwrap();
+        // The parent is a file, the list cannot be written.
+        let store = new_arc(file.join("stations.json"));
+
+        let result =
+            store.update(&|stations| stations.push(Station::new("Radio", "http://radio.example")));
+
+        assert!(result.is_err());
+        assert!(store.stations().unwrap().is_empty());
+
+        fs::remove_file(file).unwrap();
+    }
+}
@@ -1,6 +1,9 @@
 pub mod bus;
 pub mod front;
 pub mod message;
+pub mod now_playing;
 pub mod pipe;
+pub mod reconnect;
+pub mod status;
 pub mod streamer_loop;
 pub mod sys;
@@ -27,7 +27,7 @@ impl Bus for Bus_ {
         let mut bus_lock = self.0.try_lock_default_duration()?;
 
         if bus_lock.is_some() {
-            return Err(AppError::new(
+            return Err(AppError::InvalidState(
                 "The GStreamer bus is already assigned but it shouldn't. Force reassign."
                     .to_owned(),
             ));
@@ -46,7 +46,7 @@ impl Bus for Bus_ {
         let mut bus_lock = self.0.try_lock_default_duration()?;
 
         bus_lock.take().ok_or_else(|| {
-            AppError::new("Cannot drop the Gstreamer bus because it is null.".to_owned())
+            AppError::InvalidState("Cannot drop the Gstreamer bus because it is null.".to_owned())
         })
     }
 }
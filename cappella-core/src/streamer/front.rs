@@ -1,6 +1,7 @@
 use std::{
     fmt::Debug,
     sync::{
+        atomic::{AtomicBool, Ordering},
         mpsc::{self},
         Arc,
     },
@@ -10,10 +11,15 @@ use std::{
 
 use parking_lot::Mutex;
 
-use crate::local::{app_error::AppError, mutex_lock_timeout::MutexLockTimeout};
+use crate::{
+    frontend,
+    local::{app_error::AppError, mutex_lock_timeout::MutexLockTimeout, settings::Settings},
+    player::queue::Queue,
+};
 
 use super::{
     bus::Bus,
+    status::Status,
     streamer_loop::{self, StreamerLoop},
 };
 
@@ -21,14 +27,28 @@ const THREAD_NAME: &str = "streamer";
 const STOP_TIMEOUT_DURATION: Duration = Duration::from_secs(5);
 
 pub trait Front: Debug + Send + Sync {
-    fn start(&self, app_handle_addr: usize, uri: &str) -> Result<(), AppError>;
+    fn start(&self, uri: &str) -> Result<(), AppError>;
     fn is_running(&self) -> Result<bool, AppError>;
+    fn set_gapless(&self, gapless: bool);
     fn wait_until_end(&self) -> Result<(), AppError>;
 }
 
-pub fn new_box(bus: Arc<dyn Bus>) -> Box<dyn Front> {
+pub fn new_box(
+    bus: Arc<dyn Bus>,
+    frontend_pipe: Arc<dyn frontend::pipe::Pipe>,
+    queue: Arc<dyn Queue>,
+    settings: Arc<dyn Settings>,
+    status: Arc<dyn Status>,
+    position_interval: Duration,
+) -> Box<dyn Front> {
     Box::new(Front_ {
         bus,
+        frontend_pipe,
+        queue,
+        settings,
+        status,
+        gapless: Arc::default(),
+        position_interval,
         receiver: Mutex::default(),
         join_handle: Mutex::default(),
     })
@@ -37,19 +57,33 @@ pub fn new_box(bus: Arc<dyn Bus>) -> Box<dyn Front> {
 #[derive(Debug)]
 struct Front_ {
     bus: Arc<dyn Bus>,
+    frontend_pipe: Arc<dyn frontend::pipe::Pipe>,
+    queue: Arc<dyn Queue>,
+    settings: Arc<dyn Settings>,
+    status: Arc<dyn Status>,
+    gapless: Arc<AtomicBool>,
+    position_interval: Duration,
     receiver: Mutex<Option<mpsc::Receiver<()>>>,
     join_handle: Mutex<Option<JoinHandle<()>>>,
 }
 
 impl Front for Front_ {
-    fn start(&self, app_handle_addr: usize, uri: &str) -> Result<(), AppError> {
+    fn start(&self, uri: &str) -> Result<(), AppError> {
         let mut join_handle_lock = self.join_handle.try_lock_default_duration()?;
 
         if join_handle_lock.is_some() {
-            return Err(AppError::new("GStreamer loop already started".to_owned()));
+            return Err(AppError::InvalidState(
+                "GStreamer loop already started".to_owned(),
+            ));
         }
 
         let bus = self.bus.clone();
+        let frontend_pipe = self.frontend_pipe.clone();
+        let queue = self.queue.clone();
+        let settings = self.settings.clone();
+        let status = self.status.clone();
+        let gapless = self.gapless.clone();
+        let position_interval = self.position_interval;
         let uri_owned = uri.to_owned();
         let (sender, receiver) = mpsc::channel::<()>();
         *self.receiver.try_lock_default_duration()? = Some(receiver);
@@ -58,7 +92,17 @@ impl Front for Front_ {
             thread::Builder::new()
                 .name(THREAD_NAME.to_string())
                 .spawn(move || {
-                    streamer_loop::new_impl(bus, sender).start_loop(app_handle_addr, &uri_owned);
+                    streamer_loop::new_impl(
+                        bus,
+                        frontend_pipe,
+                        sender,
+                        queue,
+                        settings,
+                        status,
+                        gapless,
+                        position_interval,
+                    )
+                    .start_loop(&uri_owned);
                 })?;
 
         *join_handle_lock = Some(join_handle);
@@ -80,6 +124,10 @@ impl Front for Front_ {
         Ok(false)
     }
 
+    fn set_gapless(&self, gapless: bool) {
+        self.gapless.store(gapless, Ordering::SeqCst);
+    }
+
     fn wait_until_end(&self) -> Result<(), AppError> {
         let mut join_handle_lock = self.join_handle.try_lock_default_duration()?;
 
@@ -89,11 +137,13 @@ impl Front for Front_ {
                 .try_lock_default_duration()?
                 .take()
                 .ok_or_else(|| {
-                    AppError::new("The receiver doesn't exist for the parent thread.".to_owned())
+                    AppError::InvalidState(
+                        "The receiver doesn't exist for the parent thread.".to_owned(),
+                    )
                 })?;
             receiver.recv_timeout(STOP_TIMEOUT_DURATION)?;
             join_handle.join().or_else(|err| {
-                Err(AppError::new(format!(
+                Err(AppError::Channel(format!(
                     "Error on GStreamer thread join handle: {err:?}."
                 )))
             })?;
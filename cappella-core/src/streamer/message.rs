@@ -0,0 +1,107 @@
+use crate::local::app_error::AppError;
+
+use super::sys::{structure::Structure, structure_field};
+
+pub type Uri = String;
+pub type PositionNs = i64;
+
+const TITLE_FIELD: &str = "TITLE";
+const URI_FIELD: &str = "URI";
+const POSITION_FIELD: &str = "POSITION";
+const VOLUME_FIELD: &str = "VOLUME";
+const MUTE_FIELD: &str = "MUTE";
+
+const MESSAGE_TITLE_VALUE_NONE: &str = "None";
+const MESSAGE_TITLE_VALUE_PLAY: &str = "Play";
+const MESSAGE_TITLE_VALUE_PAUSE: &str = "Pause";
+const MESSAGE_TITLE_VALUE_STOP: &str = "Stop";
+const MESSAGE_TITLE_VALUE_SEEK: &str = "Seek";
+const MESSAGE_TITLE_VALUE_SET_VOLUME: &str = "SetVolume";
+const MESSAGE_TITLE_VALUE_SET_MUTE: &str = "SetMute";
+
+#[derive(Debug, Default)]
+pub enum Message {
+    #[default]
+    None,
+    Play(Uri),
+    Pause,
+    Stop,
+    Seek(PositionNs),
+    SetVolume(f64),
+    SetMute(bool),
+}
+
+impl Message {
+    pub fn from_structure(structure: Structure) -> Result<Self, AppError> {
+        let name = structure.get_string(TITLE_FIELD)?;
+
+        match name.as_str() {
+            MESSAGE_TITLE_VALUE_NONE => Ok(Message::None),
+            MESSAGE_TITLE_VALUE_PLAY => {
+                let uri = structure.get_string(URI_FIELD)?;
+                Ok(Message::Play(uri))
+            }
+            MESSAGE_TITLE_VALUE_PAUSE => Ok(Message::Pause),
+            MESSAGE_TITLE_VALUE_STOP => Ok(Message::Stop),
+            MESSAGE_TITLE_VALUE_SEEK => {
+                let position_ns = structure.get_i64(POSITION_FIELD)?;
+                Ok(Message::Seek(position_ns))
+            }
+            MESSAGE_TITLE_VALUE_SET_VOLUME => {
+                let volume = structure.get_f64(VOLUME_FIELD)?;
+                Ok(Message::SetVolume(volume))
+            }
+            MESSAGE_TITLE_VALUE_SET_MUTE => {
+                let mute = structure.get_bool(MUTE_FIELD)?;
+                Ok(Message::SetMute(mute))
+            }
+            default => Err(AppError::InvalidMessage(format!(
+                "the message name `{default}` is not supported."
+            ))),
+        }
+    }
+    pub fn to_structure(self, name: &str) -> Result<Structure, AppError> {
+        match self {
+            Message::None => Structure::new(
+                name,
+                vec![(structure_field::new_box_string(TITLE_FIELD, MESSAGE_TITLE_VALUE_NONE))],
+            ),
+            Self::Play(uri) => Structure::new(
+                name,
+                vec![
+                    (structure_field::new_box_string(TITLE_FIELD, MESSAGE_TITLE_VALUE_PLAY)),
+                    (structure_field::new_box_string(URI_FIELD, &uri)),
+                ],
+            ),
+            Message::Pause => Structure::new(
+                name,
+                vec![(structure_field::new_box_string(TITLE_FIELD, MESSAGE_TITLE_VALUE_PAUSE))],
+            ),
+            Message::Stop => Structure::new(
+                name,
+                vec![(structure_field::new_box_string(TITLE_FIELD, MESSAGE_TITLE_VALUE_STOP))],
+            ),
+            Message::Seek(position_ns) => Structure::new(
+                name,
+                vec![
+                    (structure_field::new_box_string(TITLE_FIELD, MESSAGE_TITLE_VALUE_SEEK)),
+                    (structure_field::new_box_i64(POSITION_FIELD, position_ns)),
+                ],
+            ),
+            Message::SetVolume(volume) => Structure::new(
+                name,
+                vec![
+                    (structure_field::new_box_string(TITLE_FIELD, MESSAGE_TITLE_VALUE_SET_VOLUME)),
+                    (structure_field::new_box_f64(VOLUME_FIELD, volume)),
+                ],
+            ),
+            Message::SetMute(mute) => Structure::new(
+                name,
+                vec![
+                    (structure_field::new_box_string(TITLE_FIELD, MESSAGE_TITLE_VALUE_SET_MUTE)),
+                    (structure_field::new_box_bool(MUTE_FIELD, mute)),
+                ],
+            ),
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use crate::{local::app_error::AppError, playlist::cue::CueTrack};
+
+use super::sys::taglist::{
+    Image, TagList, TAG_ALBUM, TAG_ALBUM_ARTIST, TAG_ARTIST, TAG_GENRE, TAG_IMAGE,
+    TAG_PREVIEW_IMAGE, TAG_TITLE,
+};
+
+/// The metadata of the playing stream, from the tags received so far.
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct NowPlaying {
+    pub uri: String,
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub album_artist: Option<String>,
+    pub genre: Option<String>,
+    /// A `data:` URL, usable as is in an `img` element.
+    pub cover: Option<String>,
+}
+
+impl NowPlaying {
+    pub fn new(uri: &str) -> Self {
+        Self {
+            uri: uri.to_owned(),
+            ..Self::default()
+        }
+    }
+
+    pub fn from_tags(uri: &str, tags: &TagList) -> Result<Self, AppError> {
+        let image = match tags.get_image(TAG_IMAGE)? {
+            Some(image) => Some(image),
+            None => tags.get_image(TAG_PREVIEW_IMAGE)?,
+        };
+
+        Ok(Self {
+            uri: uri.to_owned(),
+            title: tags.get_string(TAG_TITLE)?,
+            artist: tags.get_string(TAG_ARTIST)?,
+            album: tags.get_string(TAG_ALBUM)?,
+            album_artist: tags.get_string(TAG_ALBUM_ARTIST)?,
+            genre: tags.get_string(TAG_GENRE)?,
+            cover: image.as_ref().map(data_url),
+        })
+    }
+
+    /// The tags of a single-file album describe the file, the sheet describes the track.
+    pub fn from_cue_track(uri: &str, track: &CueTrack) -> Self {
+        Self {
+            uri: uri.to_owned(),
+            title: track.title.clone(),
+            artist: track.performer.clone(),
+            album: track.album.clone(),
+            album_artist: track.album_performer.clone(),
+            genre: track.genre.clone(),
+            cover: None,
+        }
+    }
+
+    /// Keeps the known values missing in `other`, returns `true` if something changed.
+    pub fn merge(&mut self, other: NowPlaying) -> bool {
+        let merged = Self {
+            uri: other.uri,
+            title: other.title.or_else(|| self.title.clone()),
+            artist: other.artist.or_else(|| self.artist.clone()),
+            album: other.album.or_else(|| self.album.clone()),
+            album_artist: other.album_artist.or_else(|| self.album_artist.clone()),
+            genre: other.genre.or_else(|| self.genre.clone()),
+            cover: other.cover.or_else(|| self.cover.clone()),
+        };
+        let is_changed = merged != *self;
+        *self = merged;
+
+        is_changed
+    }
+}
+
+fn data_url(image: &Image) -> String {
+    format!(
+        "data:{};base64,{}",
+        image.mime_type,
+        STANDARD.encode(&image.data)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::streamer::sys::taglist::Image;
+
+    use super::{data_url, NowPlaying};
+
+    #[test]
+    fn test_merge_keeps_known_values() {
+        let mut now_playing = NowPlaying {
+            title: Some("Title".to_owned()),
+            artist: Some("Artist".to_owned()),
+            ..NowPlaying::new("uri")
+        };
+
+        let is_changed = now_playing.merge(NowPlaying {
+            title: Some("Other".to_owned()),
+            ..NowPlaying::new("uri")
+        });
+
+        assert!(is_changed);
+        assert_eq!(now_playing.title.as_deref(), Some("Other"));
+        assert_eq!(now_playing.artist.as_deref(), Some("Artist"));
+    }
+
+    #[test]
+    fn test_merge_unchanged() {
+        let mut now_playing = NowPlaying {
+            title: Some("Title".to_owned()),
+            ..NowPlaying::new("uri")
+        };
+
+        assert!(!now_playing.merge(NowPlaying::new("uri")));
+    }
+
+    #[test]
+    fn test_data_url() {
+        let image = Image {
+            mime_type: "image/png".to_owned(),
+            data: vec![1, 2, 3],
+        };
+
+        assert_eq!(data_url(&image), "data:image/png;base64,AQID");
+    }
+}
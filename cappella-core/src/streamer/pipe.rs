@@ -30,7 +30,7 @@ impl Pipe for Pipe_ {
             return bus.post(&message);
         }
 
-        Err(AppError::new(format!(
+        Err(AppError::InvalidState(format!(
             "The bus is null or the thread is not started. Message: {message:?}"
         )))
     }
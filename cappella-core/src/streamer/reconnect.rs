@@ -0,0 +1,139 @@
+use std::time::{Duration, Instant};
+
+const FIRST_DELAY: Duration = Duration::from_secs(1);
+const MAX_DELAY: Duration = Duration::from_secs(30);
+/// About two minutes of attempts before giving up.
+const MAX_ATTEMPTS: u32 = 8;
+/// A stream playing this long after the last attempt starts again from the first delay.
+const STABLE_DURATION: Duration = Duration::from_secs(120);
+
+const NETWORK_SCHEMES: [&str; 4] = ["http://", "https://", "icy://", "icyx://"];
+
+/// The reconnection of a network stream after a drop, with an exponential backoff.
+#[derive(Debug, Default)]
+pub struct Reconnect {
+    attempts: u32,
+    at: Option<Instant>,
+    /// The attempts start over once the stream has played long enough after the last one.
+    last_at: Option<Instant>,
+}
+
+impl Reconnect {
+    /// Plans the next attempt and returns its delay, `None` once the attempts are exhausted.
+    pub fn schedule(&mut self) -> Option<Duration> {
+        self.schedule_at(Instant::now())
+    }
+
+    fn schedule_at(&mut self, now: Instant) -> Option<Duration> {
+        if self
+            .last_at
+            .is_some_and(|last_at| now >= last_at + STABLE_DURATION)
+        {
+            self.attempts = 0;
+        }
+
+        if self.attempts == MAX_ATTEMPTS {
+            return None;
+        }
+
+        let delay = delay(self.attempts);
+        self.attempts += 1;
+        self.at = Some(now + delay);
+        self.last_at = self.at;
+
+        Some(delay)
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// `true` once, when the planned attempt is due.
+    pub fn take_due(&mut self) -> bool {
+        if self.at.is_some_and(|at| at <= Instant::now()) {
+            self.at = None;
+            return true;
+        }
+
+        false
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.at.is_some()
+    }
+
+    /// Another track is played.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+pub fn is_network_uri(uri: &str) -> bool {
+    NETWORK_SCHEMES.iter().any(|scheme| {
+        uri.get(..scheme.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(scheme))
+    })
+}
+
+fn delay(attempt: u32) -> Duration {
+    FIRST_DELAY
+        .saturating_mul(2_u32.saturating_pow(attempt))
+        .min(MAX_DELAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{delay, is_network_uri, Reconnect, MAX_ATTEMPTS, STABLE_DURATION};
+
+    #[test]
+    fn test_delay() {
+        assert_eq!(delay(0), Duration::from_secs(1));
+        assert_eq!(delay(3), Duration::from_secs(8));
+        assert_eq!(delay(5), Duration::from_secs(30));
+        assert_eq!(delay(40), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_schedule() {
+        let mut reconnect = Reconnect::default();
+
+        let delays = (0..=MAX_ATTEMPTS)
+            .map(|_| reconnect.schedule())
+            .collect::<Vec<_>>();
+
+        assert_eq!(delays[0], Some(Duration::from_secs(1)));
+        assert_eq!(delays[1], Some(Duration::from_secs(2)));
+        assert_eq!(delays[MAX_ATTEMPTS as usize], None);
+        assert!(reconnect.is_pending());
+        assert!(!reconnect.take_due());
+
+        reconnect.reset();
+
+        assert_eq!(reconnect.attempts(), 0);
+        assert!(!reconnect.is_pending());
+    }
+
+    #[test]
+    fn test_schedule_after_stable() {
+        let mut reconnect = Reconnect::default();
+        let now = Instant::now();
+        reconnect.schedule_at(now);
+        reconnect.schedule_at(now);
+
+        assert_eq!(reconnect.schedule_at(now), Some(Duration::from_secs(4)));
+        assert_eq!(
+            reconnect.schedule_at(now + STABLE_DURATION * 2),
+            Some(Duration::from_secs(1))
+        );
+    }
+
+    #[test]
+    fn test_is_network_uri() {
+        assert!(is_network_uri("http://127.0.0.1:8000/stream"));
+        assert!(is_network_uri("HTTPS://radio.example/live.mp3"));
+        assert!(!is_network_uri("file:///music/a.flac"));
+        assert!(!is_network_uri("http"));
+    }
+}
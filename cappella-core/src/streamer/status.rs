@@ -0,0 +1,73 @@
+use std::{fmt::Debug, sync::Arc};
+
+use parking_lot::Mutex;
+
+use crate::{
+    frontend::message::PlayerState,
+    local::{app_error::AppError, mutex_lock_timeout::MutexLockTimeout},
+};
+
+use super::message::Uri;
+
+/// What the streamer is playing, kept up to date by the streamer loop.
+pub trait Status: Debug + Send + Sync {
+    fn get(&self) -> Result<Values, AppError>;
+    fn update(&self, update: &dyn Fn(&mut Values)) -> Result<(), AppError>;
+}
+
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize)]
+pub struct Values {
+    pub state: PlayerState,
+    pub uri: Option<Uri>,
+    pub position_ns: Option<i64>,
+    pub duration_ns: Option<i64>,
+    /// `false` for a live stream or while the duration is unknown.
+    pub seekable: bool,
+}
+
+pub fn new_arc() -> Arc<dyn Status> {
+    Arc::<Status_>::default()
+}
+
+#[derive(Debug, Default)]
+struct Status_(Mutex<Values>);
+
+impl Status for Status_ {
+    fn get(&self) -> Result<Values, AppError> {
+        Ok(self.0.try_lock_default_duration()?.clone())
+    }
+
+    fn update(&self, update: &dyn Fn(&mut Values)) -> Result<(), AppError> {
+        update(&mut *self.0.try_lock_default_duration()?);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::frontend::message::PlayerState;
+
+    use super::{new_arc, Values};
+
+    #[test]
+    fn test_update() {
+        let status = new_arc();
+
+        status
+            .update(&|values| {
+                values.state = PlayerState::Playing;
+                values.uri = Some("file:///a.flac".to_owned());
+            })
+            .unwrap();
+
+        assert_eq!(
+            status.get().unwrap(),
+            Values {
+                state: PlayerState::Playing,
+                uri: Some("file:///a.flac".to_owned()),
+                ..Values::default()
+            }
+        );
+    }
+}
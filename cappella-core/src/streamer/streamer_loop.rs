@@ -0,0 +1,796 @@
+use std::{
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use gstreamer_sys::{
+    GstObject, GstSeekFlags, GstState, GST_CLOCK_TIME_NONE, GST_FORMAT_TIME,
+    GST_MESSAGE_APPLICATION, GST_MESSAGE_ASYNC_DONE, GST_MESSAGE_BUFFERING,
+    GST_MESSAGE_DURATION_CHANGED, GST_MESSAGE_ELEMENT, GST_MESSAGE_EOS, GST_MESSAGE_ERROR,
+    GST_MESSAGE_SEGMENT_DONE, GST_MESSAGE_STATE_CHANGED, GST_MESSAGE_STREAM_START, GST_MESSAGE_TAG,
+    GST_MESSAGE_WARNING, GST_SEEK_FLAG_ACCURATE, GST_SEEK_FLAG_FLUSH, GST_SEEK_FLAG_KEY_UNIT,
+    GST_SEEK_FLAG_SEGMENT, GST_STATE_CHANGE_NO_PREROLL, GST_STATE_PAUSED, GST_STATE_PLAYING,
+    GST_STATE_READY,
+};
+
+use parking_lot::Mutex;
+
+use crate::{
+    frontend::{
+        self,
+        message::{ErrorReport, PlayerState},
+    },
+    local::{app_error::AppError, mutex_lock_timeout::MutexLockTimeout, settings::Settings, uri},
+    player::queue::Queue,
+    playlist::cue::{self, CueTrack},
+    stations::icy,
+};
+
+use super::{
+    bus::Bus,
+    message::{Message, Uri},
+    now_playing::NowPlaying,
+    pipe::MESSAGE_NAME,
+    reconnect::{self, Reconnect},
+    status::Status,
+    sys::{
+        self,
+        element::{AboutToFinish, Element, VolumeScale},
+        taglist::TAG_ORGANIZATION,
+    },
+};
+
+const UPDATE_POSITION_DURATION: Duration = Duration::from_millis(100);
+const SEEK_FLAGS: GstSeekFlags = GST_SEEK_FLAG_FLUSH | GST_SEEK_FLAG_KEY_UNIT;
+/// A CUE track ends with a segment done message at the start of the next one.
+const SEGMENT_SEEK_FLAGS: GstSeekFlags =
+    GST_SEEK_FLAG_FLUSH | GST_SEEK_FLAG_ACCURATE | GST_SEEK_FLAG_SEGMENT;
+/// Without flush, the next contiguous segment follows without gap.
+const NEXT_SEGMENT_SEEK_FLAGS: GstSeekFlags = GST_SEEK_FLAG_ACCURATE | GST_SEEK_FLAG_SEGMENT;
+const VOLUME_SCALE: VolumeScale = VolumeScale::Cubic;
+/// The duration is queried on the first position updates only, a live stream has none.
+const DURATION_QUERIES: u32 = 10;
+const BUFFER_FULL_PERCENT: i32 = 100;
+
+pub trait StreamerLoop: Debug {
+    fn start_loop(&self, uri: &str);
+}
+
+pub fn new_impl(
+    bus: Arc<dyn Bus>,
+    frontend_pipe: Arc<dyn frontend::pipe::Pipe>,
+    sender: mpsc::Sender<()>,
+    queue: Arc<dyn Queue>,
+    settings: Arc<dyn Settings>,
+    status: Arc<dyn Status>,
+    gapless: Arc<AtomicBool>,
+    position_interval: Duration,
+) -> impl StreamerLoop {
+    StreamerLoop_ {
+        bus,
+        frontend_pipe,
+        sender,
+        queue,
+        settings,
+        status,
+        gapless,
+        position_interval,
+    }
+}
+
+#[derive(Debug)]
+struct StreamerLoop_ {
+    bus: Arc<dyn Bus>,
+    frontend_pipe: Arc<dyn frontend::pipe::Pipe>,
+    sender: mpsc::Sender<()>,
+    queue: Arc<dyn Queue>,
+    settings: Arc<dyn Settings>,
+    status: Arc<dyn Status>,
+    gapless: Arc<AtomicBool>,
+    position_interval: Duration,
+}
+
+#[derive(Debug)]
+enum LoopEnd {
+    Play(Uri),
+    EndOfStream,
+    Stop,
+}
+
+#[derive(Debug)]
+struct Data<'a> {
+    frontend_pipe: &'a dyn frontend::pipe::Pipe,
+    element: Element,
+    uri: Uri,
+    now_playing: NowPlaying,
+    next_uri: Arc<Mutex<Option<Uri>>>,
+    is_playing: bool,
+    is_missing_plugin_sent: bool,
+    /// Paused by the streamer until the buffer is full, `is_playing` keeps the user choice.
+    is_buffering: bool,
+    duration: i64,
+    duration_queries_left: u32,
+    last_position_sent: Option<Instant>,
+    reconnect: Reconnect,
+    /// A radio or a live source, its end is a dropped connection and not the end of a file.
+    is_live: bool,
+    /// A CUE track, played as a segment of its file.
+    segment: Option<CueTrack>,
+    /// The segment is sought once the pipeline is prerolled.
+    is_segment_seek_pending: bool,
+}
+
+impl StreamerLoop for StreamerLoop_ {
+    fn start_loop(&self, uri: &str) {
+        let mut play = Some(uri.to_owned());
+        let mut failures = 0;
+
+        while let Some(uri) = play {
+            let result = self.gst_loop(&*self.frontend_pipe, &uri);
+
+            if result.is_ok() {
+                failures = 0;
+            }
+
+            play = match result {
+                Ok(LoopEnd::Play(uri)) => Some(uri),
+                Ok(LoopEnd::EndOfStream) => self.next_on_end(),
+                Ok(LoopEnd::Stop) => None,
+                Err(err) => {
+                    eprintln!("Error from the GStreamer loop: {err}");
+                    failures += 1;
+
+                    // A repeated queue of unplayable tracks would never end.
+                    if failures > self.queue.uris().map_or(0, |uris| uris.len()) {
+                        None
+                    } else {
+                        self.next_on_end()
+                    }
+                }
+            }
+        }
+
+        self.status
+            .update(&|values| *values = Default::default())
+            .unwrap_or_else(|err| eprintln!("Error on the status reset: {err}"));
+        self.frontend_pipe
+            .send(frontend::message::Message::StateChanged(
+                PlayerState::Stopped,
+            ));
+    }
+}
+
+impl StreamerLoop_ {
+    fn next_on_end(&self) -> Option<Uri> {
+        self.queue.next_on_end().unwrap_or_else(|err| {
+            eprintln!("Error on the queue next track: {err}");
+            None
+        })
+    }
+
+    /// Releases the bus and notifies the end whatever the outcome of the track.
+    fn gst_loop(
+        &self,
+        frontend_pipe: &dyn frontend::pipe::Pipe,
+        uri: &str,
+    ) -> Result<LoopEnd, AppError> {
+        let result = self.play(frontend_pipe, uri);
+
+        let _bus = self.bus.take();
+        self.sender
+            .send(())
+            .unwrap_or_else(|err| eprintln!("Error on the loop end notification: {err}"));
+
+        result
+    }
+
+    fn play(
+        &self,
+        frontend_pipe: &dyn frontend::pipe::Pipe,
+        uri: &str,
+    ) -> Result<LoopEnd, AppError> {
+        let segment = if cue::is_track_uri(uri) {
+            Some(cue::load_track(uri).inspect_err(|err| {
+                frontend_pipe.send(frontend::message::Message::Error(ErrorReport::from(err)))
+            })?)
+        } else {
+            None
+        };
+        let element_uri = match &segment {
+            Some(segment) => uri::from_path(&segment.file)?,
+            None => uri.to_owned(),
+        };
+
+        let mut element = Element::new(&element_uri)?;
+        self.bus.set(element.get_bus()?)?;
+
+        let settings = self.settings.get()?;
+        element.set_volume(settings.volume, VOLUME_SCALE)?;
+        element.set_mute(settings.muted)?;
+
+        let next_uri = Arc::<Mutex<Option<Uri>>>::default();
+        element.connect_about_to_finish(self.about_to_finish(next_uri.clone()))?;
+
+        let mut data = Data {
+            frontend_pipe,
+            element,
+            uri: uri.to_owned(),
+            now_playing: new_now_playing(uri, segment.as_ref()),
+            next_uri,
+            is_playing: true,
+            is_missing_plugin_sent: false,
+            is_buffering: false,
+            duration: GST_CLOCK_TIME_NONE as i64,
+            duration_queries_left: DURATION_QUERIES,
+            last_position_sent: None,
+            reconnect: Reconnect::default(),
+            is_live: false,
+            is_segment_seek_pending: segment.is_some(),
+            segment,
+        };
+
+        // Not to play the beginning of the file before the segment seek.
+        let state = if data.is_segment_seek_pending {
+            GST_STATE_PAUSED
+        } else {
+            GST_STATE_PLAYING
+        };
+        // A live source does not preroll.
+        data.is_live = data.element.set_state(state)? == GST_STATE_CHANGE_NO_PREROLL;
+        self.send_track_started(&data)?;
+
+        let mut loop_end = None;
+
+        while loop_end.is_none() {
+            let bus_lock = self.bus.get_lock()?;
+
+            if let Some(bus) = bus_lock.as_ref() {
+                let msg_opt = bus.timed_pop_filtered(
+                    UPDATE_POSITION_DURATION,
+                    GST_MESSAGE_STATE_CHANGED
+                        | GST_MESSAGE_ERROR
+                        | GST_MESSAGE_WARNING
+                        | GST_MESSAGE_BUFFERING
+                        | GST_MESSAGE_ELEMENT
+                        | GST_MESSAGE_EOS
+                        | GST_MESSAGE_DURATION_CHANGED
+                        | GST_MESSAGE_STREAM_START
+                        | GST_MESSAGE_TAG
+                        | GST_MESSAGE_ASYNC_DONE
+                        | GST_MESSAGE_SEGMENT_DONE
+                        | GST_MESSAGE_APPLICATION,
+                )?;
+
+                if let Some(msg) = msg_opt {
+                    loop_end = self.handle_message(&mut data, &msg)?;
+                } else if data.reconnect.take_due() {
+                    let state = if data.is_playing {
+                        GST_STATE_PLAYING
+                    } else {
+                        GST_STATE_PAUSED
+                    };
+                    self.set_state(&data.element, state)?;
+                } else if data.is_playing {
+                    self.update_position(&mut data);
+                }
+            } else {
+                panic!("The gst bus is null.");
+            }
+        }
+
+        Ok(loop_end.unwrap_or(LoopEnd::Stop))
+    }
+
+    fn handle_message(
+        &self,
+        data: &mut Data,
+        msg: &sys::message::Message,
+    ) -> Result<Option<LoopEnd>, AppError> {
+        match msg.type_() {
+            GST_MESSAGE_ERROR => {
+                let gst_error = msg.error();
+
+                if !gst_error.is_missing_plugin()
+                    && (data.is_live || gst_error.is_network_drop())
+                    && self.schedule_reconnect(data)?
+                {
+                    eprintln!("Network stream error: {gst_error}");
+                    return Ok(None);
+                }
+
+                if !gst_error.is_missing_plugin() {
+                    data.frontend_pipe.send(frontend::message::Message::Error(
+                        ErrorReport::from_gst_error(gst_error.clone(), &data.uri),
+                    ));
+                } else if !data.is_missing_plugin_sent {
+                    data.frontend_pipe
+                        .send(frontend::message::Message::MissingPlugin {
+                            uri: data.uri.clone(),
+                            description: gst_error.message.clone(),
+                        });
+                }
+
+                Err(AppError::from(gst_error))
+            }
+            GST_MESSAGE_WARNING => {
+                let gst_error = msg.warning();
+                eprintln!("Warning from GStreamer: {gst_error}");
+                data.frontend_pipe.send(frontend::message::Message::Warning(
+                    ErrorReport::from_gst_error(gst_error, &data.uri),
+                ));
+                Ok(None)
+            }
+            GST_MESSAGE_ELEMENT => {
+                // The missing plugin message comes before the error and names the plugin.
+                if let Some(description) = msg.missing_plugin_description() {
+                    data.frontend_pipe
+                        .send(frontend::message::Message::MissingPlugin {
+                            uri: data.uri.clone(),
+                            description,
+                        });
+                    data.is_missing_plugin_sent = true;
+                }
+                Ok(None)
+            }
+            GST_MESSAGE_EOS => {
+                // An endless stream only ends when the server closes the connection, a file
+                // without known duration ends as any other.
+                if data.is_live && self.schedule_reconnect(data)? {
+                    return Ok(None);
+                }
+
+                data.frontend_pipe
+                    .send(frontend::message::Message::TrackEnded(data.uri.clone()));
+                Ok(Some(LoopEnd::EndOfStream))
+            }
+            GST_MESSAGE_SEGMENT_DONE => self.handle_segment_done(data),
+            GST_MESSAGE_BUFFERING => {
+                self.handle_buffering(data, msg.buffering())?;
+                Ok(None)
+            }
+            GST_MESSAGE_DURATION_CHANGED => {
+                data.duration = GST_CLOCK_TIME_NONE as i64;
+                data.duration_queries_left = DURATION_QUERIES;
+                Ok(None)
+            }
+            GST_MESSAGE_STATE_CHANGED => {
+                let state = msg.state_changed();
+
+                if msg.src() == data.element.get() as *mut GstObject {
+                    let new_state = state.new_state();
+
+                    // The pauses to buffer, to reconnect or to seek a segment are not changes for
+                    // the user.
+                    if !(data.is_buffering && new_state == GST_STATE_PAUSED)
+                        && !data.reconnect.is_pending()
+                        && !data.is_segment_seek_pending
+                    {
+                        self.send_state(data, player_state(new_state))?;
+                    }
+                }
+
+                Ok(None)
+            }
+            GST_MESSAGE_ASYNC_DONE => {
+                self.handle_async_done(data)?;
+                Ok(None)
+            }
+            GST_MESSAGE_STREAM_START => {
+                self.handle_stream_start(data)?;
+                Ok(None)
+            }
+            GST_MESSAGE_TAG => {
+                self.handle_tag(data, msg)
+                    .unwrap_or_else(|err| eprintln!("Error on the tags: {err}"));
+                Ok(None)
+            }
+            GST_MESSAGE_APPLICATION => self.handle_application_message(data, msg),
+            gst_message_type => {
+                eprintln!("Unexpected message number received: {gst_message_type}");
+                Ok(None)
+            }
+        }
+    }
+
+    /// Without gap, the next track is announced by a stream start on the same pipeline.
+    fn handle_stream_start(&self, data: &mut Data) -> Result<(), AppError> {
+        let Some(next_uri) = data.next_uri.try_lock_default_duration()?.take() else {
+            return Ok(());
+        };
+
+        self.queue.next_on_end()?;
+        data.frontend_pipe
+            .send(frontend::message::Message::TrackEnded(data.uri.clone()));
+
+        self.start_next_track(data, next_uri, None)
+    }
+
+    /// The end of a CUE track, the next track of the same file follows on the same pipeline.
+    fn handle_segment_done(&self, data: &mut Data) -> Result<Option<LoopEnd>, AppError> {
+        data.frontend_pipe
+            .send(frontend::message::Message::TrackEnded(data.uri.clone()));
+
+        let next = self
+            .queue
+            .peek_next_on_end()?
+            .filter(|next_uri| cue::is_track_uri(next_uri))
+            .and_then(|next_uri| Some((cue::load_track(&next_uri).ok()?, next_uri)))
+            .filter(|(next_segment, _)| {
+                data.segment
+                    .as_ref()
+                    .is_some_and(|segment| segment.file == next_segment.file)
+            });
+        let Some((next_segment, next_uri)) = next else {
+            return Ok(Some(LoopEnd::EndOfStream));
+        };
+
+        let is_contiguous = data
+            .segment
+            .as_ref()
+            .is_some_and(|segment| segment.end_ns == Some(next_segment.start_ns));
+        let seek_flags = if is_contiguous {
+            NEXT_SEGMENT_SEEK_FLAGS
+        } else {
+            SEGMENT_SEEK_FLAGS
+        };
+
+        self.queue.next_on_end()?;
+        self.start_next_track(data, next_uri, Some(next_segment))?;
+        self.seek_segment(data, 0, seek_flags)?;
+
+        Ok(None)
+    }
+
+    /// Once prerolled, a CUE track seeks to its segment before playing.
+    fn handle_async_done(&self, data: &mut Data) -> Result<(), AppError> {
+        if !data.is_segment_seek_pending {
+            return Ok(());
+        }
+
+        data.is_segment_seek_pending = false;
+        self.seek_segment(data, 0, SEGMENT_SEEK_FLAGS)?;
+
+        if data.is_playing {
+            self.set_state(&data.element, GST_STATE_PLAYING)?;
+        }
+
+        Ok(())
+    }
+
+    /// The position is relative to the segment start.
+    fn seek_segment(
+        &self,
+        data: &Data,
+        position_ns: i64,
+        seek_flags: GstSeekFlags,
+    ) -> Result<(), AppError> {
+        match &data.segment {
+            Some(segment) => data.element.seek(
+                GST_FORMAT_TIME,
+                seek_flags,
+                segment.start_ns + position_ns,
+                segment.end_ns,
+            ),
+            None => Ok(()),
+        }
+    }
+
+    /// On the same pipeline, without gap or as the next segment of the file.
+    fn start_next_track(
+        &self,
+        data: &mut Data,
+        uri: Uri,
+        segment: Option<CueTrack>,
+    ) -> Result<(), AppError> {
+        data.now_playing = new_now_playing(&uri, segment.as_ref());
+        data.segment = segment;
+        data.is_missing_plugin_sent = false;
+        data.uri = uri;
+        data.duration = GST_CLOCK_TIME_NONE as i64;
+        data.duration_queries_left = DURATION_QUERIES;
+        data.last_position_sent = None;
+        data.reconnect.reset();
+        data.is_live = false;
+
+        self.send_track_started(data)
+    }
+
+    /// A CUE track has its values from the sheet, without waiting for the tags.
+    fn send_track_started(&self, data: &Data) -> Result<(), AppError> {
+        self.update_track_status(&data.uri)?;
+        data.frontend_pipe
+            .send(frontend::message::Message::TrackStarted(data.uri.clone()));
+
+        if data.segment.is_some() {
+            data.frontend_pipe
+                .send(frontend::message::Message::NowPlaying(
+                    data.now_playing.clone(),
+                ));
+        }
+
+        Ok(())
+    }
+
+    /// The tags come from several elements and can change in a stream, as with a radio.
+    fn handle_tag(&self, data: &mut Data, msg: &sys::message::Message) -> Result<(), AppError> {
+        let tags = msg.tags()?;
+        let mut now_playing = NowPlaying::from_tags(&data.uri, &tags)?;
+
+        // The ICY name of a radio comes from its headers, its titles from the stream.
+        if reconnect::is_network_uri(&data.uri) && tags.get_string(TAG_ORGANIZATION)?.is_some() {
+            data.is_live = true;
+        }
+
+        // A new ICY title on a radio, without artist the one of the previous title is not kept.
+        if reconnect::is_network_uri(&data.uri) && icy::is_stream_title(&now_playing) {
+            data.is_live = true;
+            icy::split_stream_title(&mut now_playing);
+            if now_playing.artist.is_none() {
+                data.now_playing.artist = None;
+            }
+        }
+
+        if let Some(segment) = &data.segment {
+            now_playing.merge(NowPlaying::from_cue_track(&data.uri, segment));
+        }
+
+        if data.now_playing.merge(now_playing) {
+            data.frontend_pipe
+                .send(frontend::message::Message::NowPlaying(
+                    data.now_playing.clone(),
+                ));
+        }
+
+        Ok(())
+    }
+
+    fn about_to_finish(&self, next_uri: Arc<Mutex<Option<Uri>>>) -> Box<AboutToFinish> {
+        let queue = self.queue.clone();
+        let gapless = self.gapless.clone();
+
+        Box::new(move || {
+            if !gapless.load(Ordering::SeqCst) {
+                return None;
+            }
+
+            // A CUE track needs its segment seek, the file is not replaced.
+            let current_uri = queue.current().unwrap_or_else(|err| {
+                eprintln!("Error on the queue current track: {err}");
+                None
+            });
+            if current_uri.as_deref().is_some_and(cue::is_track_uri) {
+                return None;
+            }
+
+            let uri = queue
+                .peek_next_on_end()
+                .unwrap_or_else(|err| {
+                    eprintln!("Error on the queue next track: {err}");
+                    None
+                })
+                .filter(|uri| !cue::is_track_uri(uri))?;
+
+            match next_uri.try_lock_default_duration() {
+                Ok(mut next_uri_lock) => {
+                    *next_uri_lock = Some(uri.clone());
+                    Some(uri)
+                }
+                Err(err) => {
+                    eprintln!("Error on the next track lock: {err}");
+                    None
+                }
+            }
+        })
+    }
+
+    fn handle_application_message(
+        &self,
+        data: &mut Data,
+        msg: &sys::message::Message,
+    ) -> Result<Option<LoopEnd>, AppError> {
+        let structure = msg.structure()?;
+        let name = structure.name();
+
+        if name.ne(MESSAGE_NAME) {
+            return Err(AppError::InvalidMessage(format!(
+                "Streamer pipe message name error: {name}"
+            )));
+        }
+
+        let message = Message::from_structure(structure)?;
+
+        match message {
+            Message::None => Err(AppError::InvalidMessage(
+                "Message with 'None' is an error due to a possible receive timeout.".to_owned(),
+            )),
+            // The buffering or the segment seek ends in the state chosen by the user.
+            Message::Pause if data.is_buffering || data.is_segment_seek_pending => {
+                data.is_playing = !data.is_playing;
+                let player_state = if data.is_playing {
+                    PlayerState::Playing
+                } else {
+                    PlayerState::Paused
+                };
+                self.send_state(data, player_state)?;
+                Ok(None)
+            }
+            Message::Pause => {
+                let element = &data.element;
+                if data.is_playing {
+                    self.set_state(element, GST_STATE_PAUSED)?;
+                    data.is_playing = false;
+                } else {
+                    self.set_state(element, GST_STATE_PLAYING)?;
+                    data.is_playing = true;
+                }
+                Ok(None)
+            }
+            Message::Seek(position_ns) => {
+                let result = if data.segment.is_some() {
+                    self.seek_segment(data, position_ns, SEGMENT_SEEK_FLAGS)
+                } else {
+                    data.element
+                        .seek_simple(GST_FORMAT_TIME, SEEK_FLAGS, position_ns)
+                };
+                result.unwrap_or_else(|err| eprintln!("Error on seek: {err}"));
+                Ok(None)
+            }
+            Message::SetVolume(volume) => {
+                data.element.set_volume(volume, VOLUME_SCALE)?;
+                Ok(None)
+            }
+            Message::SetMute(mute) => {
+                data.element.set_mute(mute)?;
+                Ok(None)
+            }
+            Message::Play(uri) => Ok(Some(LoopEnd::Play(uri))),
+            Message::Stop => Ok(Some(LoopEnd::Stop)),
+        }
+    }
+
+    fn update_position(&self, data: &mut Data) {
+        if data
+            .last_position_sent
+            .is_some_and(|last_position_sent| last_position_sent.elapsed() < self.position_interval)
+        {
+            return;
+        }
+
+        // The position is unknown until the pipeline is prerolled.
+        let Ok(position_ns) = data.element.query_position(GST_FORMAT_TIME) else {
+            return;
+        };
+
+        if data.duration == GST_CLOCK_TIME_NONE as i64 && data.duration_queries_left > 0 {
+            data.duration_queries_left -= 1;
+            data.duration = data
+                .element
+                .query_duration(GST_FORMAT_TIME)
+                .unwrap_or(data.duration);
+        }
+
+        let (position_ns, duration_ns) = track_time(data, position_ns);
+        let seekable = is_seekable(data);
+        self.status
+            .update(&|values| {
+                values.position_ns = Some(position_ns);
+                values.duration_ns = seekable.then_some(duration_ns);
+                values.seekable = seekable;
+            })
+            .unwrap_or_else(|err| eprintln!("Error on the status position: {err}"));
+        data.frontend_pipe
+            .send(frontend::message::Message::Position {
+                position_ns,
+                duration_ns,
+            });
+        data.last_position_sent = Some(Instant::now());
+    }
+
+    /// The position and the duration are unknown until the new track is prerolled.
+    fn update_track_status(&self, uri: &str) -> Result<(), AppError> {
+        self.status.update(&|values| {
+            values.uri = Some(uri.to_owned());
+            values.position_ns = None;
+            values.duration_ns = None;
+            values.seekable = false;
+        })
+    }
+
+    fn send_state(&self, data: &Data, player_state: PlayerState) -> Result<(), AppError> {
+        self.status
+            .update(&|values| values.state = player_state.clone())?;
+        data.frontend_pipe
+            .send(frontend::message::Message::StateChanged(player_state));
+
+        Ok(())
+    }
+
+    /// Below 100%, the playback is paused to fill the buffer instead of stuttering.
+    fn handle_buffering(&self, data: &mut Data, percent: i32) -> Result<(), AppError> {
+        data.frontend_pipe
+            .send(frontend::message::Message::Buffering { percent });
+
+        if percent < BUFFER_FULL_PERCENT {
+            if !data.is_buffering && data.is_playing {
+                self.set_state(&data.element, GST_STATE_PAUSED)?;
+            }
+            data.is_buffering = true;
+        } else if data.is_buffering {
+            data.is_buffering = false;
+            if data.is_playing {
+                self.set_state(&data.element, GST_STATE_PLAYING)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// For a network stream, closes the connection and plans the next attempt.
+    /// `false` when it is not a network stream or when the attempts are exhausted.
+    fn schedule_reconnect(&self, data: &mut Data) -> Result<bool, AppError> {
+        if !reconnect::is_network_uri(&data.uri) {
+            return Ok(false);
+        }
+
+        let Some(delay) = data.reconnect.schedule() else {
+            return Ok(false);
+        };
+
+        self.set_state(&data.element, GST_STATE_READY)?;
+        data.is_buffering = false;
+        data.frontend_pipe
+            .send(frontend::message::Message::Reconnecting {
+                uri: data.uri.clone(),
+                attempt: data.reconnect.attempts(),
+                delay_ms: delay.as_millis().try_into()?,
+            });
+
+        Ok(true)
+    }
+
+    pub fn set_state(&self, element: &Element, state: GstState) -> Result<(), AppError> {
+        element.set_state(state).map(|_| ())
+    }
+}
+
+/// A live stream, or a stream without known duration, is not seekable.
+fn is_seekable(data: &Data) -> bool {
+    !data.is_live && data.duration != GST_CLOCK_TIME_NONE as i64
+}
+
+fn new_now_playing(uri: &str, segment: Option<&CueTrack>) -> NowPlaying {
+    match segment {
+        Some(segment) => NowPlaying::from_cue_track(uri, segment),
+        None => NowPlaying::new(uri),
+    }
+}
+
+/// For a CUE track, relative to its segment. The last segment ends with the file.
+fn track_time(data: &Data, position_ns: i64) -> (i64, i64) {
+    let Some(segment) = &data.segment else {
+        return (position_ns, data.duration);
+    };
+
+    let end_ns = segment
+        .end_ns
+        .or((data.duration != GST_CLOCK_TIME_NONE as i64).then_some(data.duration));
+
+    (
+        (position_ns - segment.start_ns).max(0),
+        end_ns.map_or(GST_CLOCK_TIME_NONE as i64, |end_ns| {
+            end_ns - segment.start_ns
+        }),
+    )
+}
+
+fn player_state(state: GstState) -> PlayerState {
+    match state {
+        GST_STATE_PLAYING => PlayerState::Playing,
+        GST_STATE_PAUSED => PlayerState::Paused,
+        _ => PlayerState::Stopped,
+    }
+}
@@ -1,15 +1,43 @@
+use std::ffi::{c_char, CString};
+
+use gstreamer_sys::gst_init;
+
+use crate::local::app_error::AppError;
+
 pub mod bus;
+pub mod discoverer;
 pub mod element;
+pub mod gst_error;
 pub mod message;
 pub mod state;
 pub mod structure;
 pub mod structure_field;
+pub mod taglist;
+
+/// Initializes GStreamer, can be called more than once.
+pub fn init() -> Result<(), AppError> {
+    let mut args = Vec::<CString>::new();
+
+    for arg in std::env::args() {
+        args.push(CString::new(arg)?);
+    }
+
+    let mut c_args = args
+        .iter()
+        .map(|arg| arg.clone().into_raw())
+        .collect::<Vec<*mut c_char>>();
+
+    unsafe { gst_init(&mut (c_args.len() as i32), &mut c_args.as_mut_ptr()) };
+
+    Ok(())
+}
 
 #[cfg(test)]
 mod common_tests {
     use std::{
+        cell::Cell,
         collections::{HashMap, HashSet},
-        ffi::{c_char, c_int, CStr, CString},
+        ffi::{c_char, c_int, c_ulong, CStr, CString},
         ptr::{self, null_mut},
         sync::{
             atomic::{AtomicI64, Ordering},
@@ -17,11 +45,12 @@ mod common_tests {
         },
     };
 
-    use glib_sys::{gboolean, GError, GFALSE};
+    use glib_sys::{g_strdup, gboolean, gpointer, GFALSE, GTRUE};
+    use gobject_sys::{GCallback, GClosureNotify, GConnectFlags, GObject};
     use gstreamer_sys::{
-        GstBus, GstClockTime, GstElement, GstMessage, GstMessageType, GstObject, GstState,
-        GstStateChangeReturn, GstStructure, GST_STATE_CHANGE_SUCCESS, GST_STATE_NULL,
-        GST_STATE_PAUSED, GST_STATE_PLAYING,
+        GstBus, GstClockTime, GstElement, GstFormat, GstMessage, GstMessageType, GstMiniObject,
+        GstObject, GstSeekFlags, GstSeekType, GstState, GstStateChangeReturn, GstStructure,
+        GstTagList, GST_STATE_CHANGE_SUCCESS, GST_STATE_NULL, GST_STATE_PAUSED, GST_STATE_PLAYING,
     };
     use parking_lot::{Mutex, MutexGuard};
 
@@ -29,17 +58,26 @@ mod common_tests {
 
     pub const STRUCTURE_NAME: &str = "STRUCTURE_NAME";
     pub const UNASSIGNED: i64 = -1;
+    pub const QUERY_DURATION_VALUE: i64 = 60_000_000_000;
+    pub const QUERY_POSITION_VALUE: i64 = 30_000_000_000;
+    pub const TAG_TITLE_VALUE: &str = "TAG_TITLE_VALUE";
+    pub const BUFFERING_PERCENT_VALUE: c_int = 42;
 
     static TEST_COUNTER: AtomicI64 = AtomicI64::new(0);
     static TEST_NB_TO_TEST_STRUCTURE: OnceLock<Mutex<HashMap<i64, Arc<Mutex<TestStructure>>>>> =
         OnceLock::new();
 
+    thread_local! {
+        static ELEMENT_TEST_NB: Cell<i64> = const { Cell::new(UNASSIGNED) };
+    }
+
     #[derive(Clone, Debug, Eq, Hash, PartialEq)]
     pub enum TestObjectType {
         GstBus,
         GstElement,
         GstMessage,
         GstStructure,
+        GstTagList,
     }
 
     #[derive(Clone, Debug)]
@@ -62,9 +100,14 @@ mod common_tests {
         fn faked_gst_element(&self) -> *mut GstElement;
         fn faked_gst_message(&self) -> *mut GstMessage;
         fn faked_gst_structure(&self) -> *mut GstStructure;
+        fn faked_gst_tag_list(&self) -> *mut GstTagList;
         fn element_state(&self) -> GstState;
         fn set_gst_bus_post_return(&self, value: gboolean);
         fn set_pop_message(&self, value: bool);
+        fn seek_position(&self) -> i64;
+        fn seek_stop(&self) -> i64;
+        fn connected_signal(&self) -> Option<String>;
+        fn is_property_set(&self, property: &str) -> bool;
         fn is_unref(&self, test_object_type: TestObjectType) -> bool;
         fn try_lock_unwrap(&self) -> MutexGuard<TestStructure>;
     }
@@ -78,6 +121,10 @@ mod common_tests {
         element_state: GstState,
         gst_bus_post_return: gboolean,
         pop_message: bool,
+        seek_position: i64,
+        seek_stop: i64,
+        connected_signal: Option<String>,
+        set_properties: HashSet<String>,
     }
 
     impl TestStructure {
@@ -90,6 +137,10 @@ mod common_tests {
                 element_state: GST_STATE_NULL,
                 gst_bus_post_return: GFALSE,
                 pop_message: false,
+                seek_position: UNASSIGNED,
+                seek_stop: UNASSIGNED,
+                connected_signal: None,
+                set_properties: HashSet::new(),
             }))
         }
 
@@ -102,6 +153,7 @@ mod common_tests {
                 .try_lock_default_duration()
                 .unwrap();
             test_nb_to_test_structure_lock.insert(test_nb, Arc::clone(&self_arc_mutex));
+            ELEMENT_TEST_NB.set(test_nb);
 
             self_arc_mutex
         }
@@ -161,6 +213,10 @@ mod common_tests {
             TestStructure::faked_gst(self, TestObjectType::GstStructure)
         }
 
+        fn faked_gst_tag_list(&self) -> *mut GstTagList {
+            TestStructure::faked_gst(self, TestObjectType::GstTagList)
+        }
+
         fn element_state(&self) -> GstState {
             self.try_lock_unwrap().element_state
         }
@@ -173,6 +229,22 @@ mod common_tests {
             self.try_lock_unwrap().pop_message = value;
         }
 
+        fn seek_position(&self) -> i64 {
+            self.try_lock_unwrap().seek_position
+        }
+
+        fn seek_stop(&self) -> i64 {
+            self.try_lock_unwrap().seek_stop
+        }
+
+        fn connected_signal(&self) -> Option<String> {
+            self.try_lock_unwrap().connected_signal.clone()
+        }
+
+        fn is_property_set(&self, property: &str) -> bool {
+            self.try_lock_unwrap().set_properties.contains(property)
+        }
+
         fn is_unref(&self, test_object_type: TestObjectType) -> bool {
             self.try_lock_unwrap().unrefs.contains(&test_object_type)
         }
@@ -182,6 +254,33 @@ mod common_tests {
         }
     }
 
+    #[no_mangle]
+    pub extern "C" fn g_signal_connect_data(
+        instance: *mut GObject,
+        detailed_signal: *const c_char,
+        c_handler: GCallback,
+        _data: gpointer,
+        _destroy_data: GClosureNotify,
+        _connect_flags: GConnectFlags,
+    ) -> c_ulong {
+        assert!(!instance.is_null());
+        assert!(c_handler.is_some());
+
+        let test_structure = TestStructure::from_raw_ptr(instance as *const TestObject);
+
+        if test_structure.test_nb() == UNASSIGNED {
+            return 0;
+        }
+
+        let signal = unsafe { CStr::from_ptr(detailed_signal) }
+            .to_str()
+            .unwrap()
+            .to_owned();
+        test_structure.try_lock_unwrap().connected_signal = Some(signal);
+
+        1
+    }
+
     #[no_mangle]
     pub extern "C" fn gst_bus_post(bus: *mut GstBus, message: *mut GstMessage) -> gboolean {
         assert!(!bus.is_null());
@@ -224,6 +323,83 @@ mod common_tests {
         test_structure.faked_gst_bus()
     }
 
+    #[no_mangle]
+    pub extern "C" fn gst_element_query_duration(
+        element: *mut GstElement,
+        _format: GstFormat,
+        duration: *mut i64,
+    ) -> gboolean {
+        query_value(element, duration, QUERY_DURATION_VALUE)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn gst_element_query_position(
+        element: *mut GstElement,
+        _format: GstFormat,
+        cur: *mut i64,
+    ) -> gboolean {
+        query_value(element, cur, QUERY_POSITION_VALUE)
+    }
+
+    fn query_value(element: *mut GstElement, value_ptr: *mut i64, value: i64) -> gboolean {
+        assert!(!element.is_null());
+        assert!(!value_ptr.is_null());
+
+        let test_structure = TestStructure::from_raw_ptr(element as *const TestObject);
+
+        if test_structure.test_nb() == UNASSIGNED {
+            return GFALSE;
+        }
+
+        unsafe { *value_ptr = value };
+
+        GTRUE
+    }
+
+    #[no_mangle]
+    pub extern "C" fn gst_element_seek_simple(
+        element: *mut GstElement,
+        _format: GstFormat,
+        _seek_flags: GstSeekFlags,
+        seek_pos: i64,
+    ) -> gboolean {
+        assert!(!element.is_null());
+
+        if seek_pos < 0 {
+            return GFALSE;
+        }
+
+        let test_structure = TestStructure::from_raw_ptr(element as *const TestObject);
+        test_structure.try_lock_unwrap().seek_position = seek_pos;
+
+        GTRUE
+    }
+
+    #[no_mangle]
+    pub extern "C" fn gst_element_seek(
+        element: *mut GstElement,
+        _rate: f64,
+        _format: GstFormat,
+        _seek_flags: GstSeekFlags,
+        _start_type: GstSeekType,
+        start: i64,
+        _stop_type: GstSeekType,
+        stop: i64,
+    ) -> gboolean {
+        assert!(!element.is_null());
+
+        if start < 0 {
+            return GFALSE;
+        }
+
+        let test_structure = TestStructure::from_raw_ptr(element as *const TestObject);
+        let mut test_structure_lock = test_structure.try_lock_unwrap();
+        test_structure_lock.seek_position = start;
+        test_structure_lock.seek_stop = stop;
+
+        GTRUE
+    }
+
     #[no_mangle]
     pub extern "C" fn gst_element_set_state(
         element: *mut GstElement,
@@ -254,6 +430,13 @@ mod common_tests {
         }
     }
 
+    #[no_mangle]
+    pub extern "C" fn gst_message_parse_buffering(message: *mut GstMessage, percent: *mut c_int) {
+        assert!(!message.is_null());
+
+        unsafe { *percent = BUFFERING_PERCENT_VALUE };
+    }
+
     #[no_mangle]
     pub extern "C" fn gst_message_get_structure(message: *mut GstMessage) -> *const GstStructure {
         assert!(!message.is_null());
@@ -267,6 +450,18 @@ mod common_tests {
         test_structure.faked_gst_structure()
     }
 
+    #[no_mangle]
+    pub extern "C" fn gst_message_parse_tag(
+        message: *mut GstMessage,
+        tag_list: *mut *mut GstTagList,
+    ) {
+        assert!(!message.is_null());
+
+        let test_structure = TestStructure::from_raw_ptr(message as *const TestObject);
+
+        unsafe { *tag_list = test_structure.faked_gst_tag_list() };
+    }
+
     #[no_mangle]
     pub extern "C" fn gst_message_unref(msg: *mut GstMessage) {
         assert!(!msg.is_null());
@@ -281,6 +476,21 @@ mod common_tests {
         );
     }
 
+    #[no_mangle]
+    pub extern "C" fn gst_mini_object_unref(mini_object: *mut GstMiniObject) {
+        assert!(!mini_object.is_null());
+
+        let test_object = TestObject::from_raw_ptr(mini_object as *const TestObject);
+        let test_object_type = test_object.test_object_type.clone();
+        let test_structure = test_object.test_structure.clone();
+        let mut test_structure_lock = test_structure.try_lock_unwrap();
+
+        assert!(
+            test_structure_lock.unrefs.insert(test_object_type.clone()),
+            "`{test_object_type:?}` already unref."
+        );
+    }
+
     #[no_mangle]
     pub extern "C" fn gst_object_unref(object: *mut GstObject) {
         assert!(!object.is_null());
@@ -296,21 +506,18 @@ mod common_tests {
         );
     }
 
+    /// The element belongs to the test structure last assigned on the test thread.
     #[no_mangle]
-    pub extern "C" fn gst_parse_launch(
-        pipeline_description: *const c_char,
-        _error: *mut *mut GError,
+    pub extern "C" fn gst_element_factory_make(
+        factoryname: *const c_char,
+        _name: *const c_char,
     ) -> *mut GstElement {
-        let pipeline_description_string = unsafe { CStr::from_ptr(pipeline_description) };
+        assert_eq!(
+            unsafe { CStr::from_ptr(factoryname) }.to_str().unwrap(),
+            "playbin"
+        );
 
-        let test_nb = pipeline_description_string
-            .to_str()
-            .unwrap()
-            .to_owned()
-            .replace("playbin uri=\"", "")
-            .replace("\"", "")
-            .parse::<i64>()
-            .expect("The uri doesn't contain a test number.");
+        let test_nb = ELEMENT_TEST_NB.get();
 
         let test_structure = if test_nb != UNASSIGNED {
             TestStructure::from_test_nb(test_nb)
@@ -321,6 +528,22 @@ mod common_tests {
         test_structure.faked_gst_element()
     }
 
+    /// Only the property name is read, the value is variadic.
+    #[no_mangle]
+    pub extern "C" fn g_object_set(object: *mut GObject, first_property_name: *const c_char) {
+        assert!(!object.is_null());
+
+        let property = unsafe { CStr::from_ptr(first_property_name) }
+            .to_str()
+            .unwrap()
+            .to_owned();
+        let test_structure = TestStructure::from_raw_ptr(object as *const TestObject);
+        test_structure
+            .try_lock_unwrap()
+            .set_properties
+            .insert(property);
+    }
+
     #[no_mangle]
     pub extern "C" fn gst_structure_get_name(structure: *const GstStructure) -> *const c_char {
         assert!(!structure.is_null());
@@ -335,4 +558,24 @@ mod common_tests {
 
         c_string_name_ptr
     }
+
+    #[no_mangle]
+    pub extern "C" fn gst_tag_list_get_string(
+        list: *const GstTagList,
+        tag: *const c_char,
+        value: *mut *mut c_char,
+    ) -> gboolean {
+        assert!(!list.is_null());
+
+        let tag = unsafe { CStr::from_ptr(tag) }.to_str().unwrap();
+
+        if tag != "title" {
+            return GFALSE;
+        }
+
+        let c_string_value = CString::new(TAG_TITLE_VALUE).unwrap();
+        unsafe { *value = g_strdup(c_string_value.as_ptr()) };
+
+        GTRUE
+    }
 }
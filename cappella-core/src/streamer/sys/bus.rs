@@ -15,7 +15,7 @@ pub struct Bus(*mut GstBus);
 impl Bus {
     pub fn new(bus: *mut GstBus) -> Result<Self, AppError> {
         if bus.is_null() {
-            return Err(AppError::new("The bus pointer is null.".to_owned()));
+            return Err(AppError::Ffi("The bus pointer is null.".to_owned()));
         }
 
         Ok(Self(bus))
@@ -29,7 +29,7 @@ impl Bus {
         let message_ptr = message.get();
 
         if unsafe { gst_bus_post(self.get(), message_ptr) } != GTRUE {
-            return Err(AppError::new(format!(
+            return Err(AppError::Ffi(format!(
                 "GStreamer returns `false` for the message: {message}"
             )));
         }
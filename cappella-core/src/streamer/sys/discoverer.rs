@@ -0,0 +1,172 @@
+use std::{
+    ffi::{CStr, CString},
+    ptr::null_mut,
+    time::Duration,
+};
+
+use glib_sys::{g_free, gpointer, GError};
+use gobject_sys::{g_object_unref, GObject};
+use gstreamer_pbutils_sys::{
+    gst_discoverer_audio_info_get_bitrate, gst_discoverer_audio_info_get_channels,
+    gst_discoverer_audio_info_get_sample_rate, gst_discoverer_discover_uri,
+    gst_discoverer_info_get_audio_streams, gst_discoverer_info_get_duration,
+    gst_discoverer_info_get_result, gst_discoverer_info_get_tags, gst_discoverer_new,
+    gst_discoverer_stream_info_get_caps, gst_discoverer_stream_info_list_free,
+    gst_pb_utils_get_codec_description, gst_pb_utils_init, GstDiscoverer, GstDiscovererAudioInfo,
+    GstDiscovererInfo, GstDiscovererStreamInfo, GST_DISCOVERER_OK,
+};
+use gstreamer_sys::{gst_mini_object_unref, GstMiniObject, GST_CLOCK_TIME_NONE};
+
+use crate::local::app_error::AppError;
+
+use super::{gst_error::GstError, taglist::TagList};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AudioStream {
+    pub codec: Option<String>,
+    pub bitrate: Option<u32>,
+    pub sample_rate: Option<u32>,
+    pub channels: Option<u32>,
+}
+
+/// Reads the metadata of a media without playing it.
+#[derive(Debug)]
+pub struct Discoverer(*mut GstDiscoverer);
+
+impl Discoverer {
+    pub fn new(timeout: Duration) -> Result<Self, AppError> {
+        super::init()?;
+        unsafe { gst_pb_utils_init() };
+
+        let mut error: *mut GError = null_mut();
+        let discoverer_ptr = unsafe { gst_discoverer_new(timeout.as_nanos() as u64, &mut error) };
+
+        if discoverer_ptr.is_null() {
+            return Err(take_error(error, "The discoverer is null."));
+        }
+
+        Ok(Self(discoverer_ptr))
+    }
+
+    pub fn get(&self) -> *mut GstDiscoverer {
+        self.0
+    }
+
+    /// Synchronous, returns when the media is analysed or on timeout.
+    pub fn discover_uri(&self, uri: &str) -> Result<DiscovererInfo, AppError> {
+        let uri_c_str = CString::new(uri)?;
+        let mut error: *mut GError = null_mut();
+
+        let info_ptr =
+            unsafe { gst_discoverer_discover_uri(self.get(), uri_c_str.as_ptr(), &mut error) };
+
+        if info_ptr.is_null() {
+            return Err(take_error(error, "The discoverer info is null."));
+        }
+
+        let info = DiscovererInfo(info_ptr);
+
+        if !error.is_null()
+            || unsafe { gst_discoverer_info_get_result(info.get()) } != GST_DISCOVERER_OK
+        {
+            return Err(take_error(
+                error,
+                &format!("Unable to discover the URI `{uri}`."),
+            ));
+        }
+
+        Ok(info)
+    }
+}
+
+impl Drop for Discoverer {
+    fn drop(&mut self) {
+        unsafe { g_object_unref(self.get() as *mut GObject) };
+    }
+}
+
+#[derive(Debug)]
+pub struct DiscovererInfo(*mut GstDiscovererInfo);
+
+impl DiscovererInfo {
+    pub fn get(&self) -> *mut GstDiscovererInfo {
+        self.0
+    }
+
+    pub fn duration(&self) -> Option<Duration> {
+        let duration = unsafe { gst_discoverer_info_get_duration(self.get()) };
+
+        if duration == GST_CLOCK_TIME_NONE {
+            return None;
+        }
+
+        Some(Duration::from_nanos(duration))
+    }
+
+    pub fn tags(&self) -> Option<TagList> {
+        TagList::new_ref(unsafe { gst_discoverer_info_get_tags(self.get()) }).ok()
+    }
+
+    /// The first audio stream, `None` if the media has no audio.
+    pub fn audio_stream(&self) -> Option<AudioStream> {
+        let streams = unsafe { gst_discoverer_info_get_audio_streams(self.get()) };
+
+        if streams.is_null() {
+            return None;
+        }
+
+        let stream_info = unsafe { (*streams).data } as *mut GstDiscovererStreamInfo;
+        let audio_info = stream_info as *const GstDiscovererAudioInfo;
+
+        let audio_stream = AudioStream {
+            codec: codec_description(stream_info),
+            bitrate: non_zero(unsafe { gst_discoverer_audio_info_get_bitrate(audio_info) }),
+            sample_rate: non_zero(unsafe { gst_discoverer_audio_info_get_sample_rate(audio_info) }),
+            channels: non_zero(unsafe { gst_discoverer_audio_info_get_channels(audio_info) }),
+        };
+
+        unsafe { gst_discoverer_stream_info_list_free(streams) };
+
+        Some(audio_stream)
+    }
+}
+
+impl Drop for DiscovererInfo {
+    fn drop(&mut self) {
+        unsafe { g_object_unref(self.get() as *mut GObject) };
+    }
+}
+
+fn codec_description(stream_info: *mut GstDiscovererStreamInfo) -> Option<String> {
+    let caps = unsafe { gst_discoverer_stream_info_get_caps(stream_info) };
+
+    if caps.is_null() {
+        return None;
+    }
+
+    let description_ptr = unsafe { gst_pb_utils_get_codec_description(caps) };
+    unsafe { gst_mini_object_unref(caps as *mut GstMiniObject) };
+
+    if description_ptr.is_null() {
+        return None;
+    }
+
+    let description = unsafe { CStr::from_ptr(description_ptr) }
+        .to_string_lossy()
+        .into_owned();
+    unsafe { g_free(description_ptr as gpointer) };
+
+    Some(description)
+}
+
+fn non_zero(value: u32) -> Option<u32> {
+    (value != 0).then_some(value)
+}
+
+/// The `GError` details are kept when there is one.
+fn take_error(error: *mut GError, default_message: &str) -> AppError {
+    match GstError::from_g_error(error) {
+        Some(gst_error) => AppError::from(gst_error),
+        None => AppError::Ffi(default_message.to_owned()),
+    }
+}
@@ -0,0 +1,513 @@
+use std::{
+    ffi::{c_char, CString},
+    fmt::Debug,
+    mem,
+    ptr::null,
+};
+
+use glib_sys::{gboolean, gpointer, GFALSE, GTRUE};
+use gobject_sys::{g_object_get, g_object_set, g_signal_connect_data, GObject};
+use gstreamer_sys::{
+    gst_element_factory_make, gst_element_get_bus, gst_element_query_duration,
+    gst_element_query_position, gst_element_seek, gst_element_seek_simple, gst_element_set_state,
+    gst_object_unref, GstElement, GstFormat, GstObject, GstSeekFlags, GstState,
+    GstStateChangeReturn, GST_SEEK_TYPE_NONE, GST_SEEK_TYPE_SET, GST_STATE_CHANGE_FAILURE,
+    GST_STATE_NULL,
+};
+
+use crate::local::app_error::AppError;
+
+use super::bus::Bus;
+
+const PLAYBIN_FACTORY: &str = "playbin";
+const ABOUT_TO_FINISH_SIGNAL: &str = "about-to-finish";
+const URI_PROPERTY: &str = "uri";
+const VOLUME_PROPERTY: &str = "volume";
+const MUTE_PROPERTY: &str = "mute";
+
+/// The cubic scale follows the loudness perception, the linear one is the amplitude factor.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VolumeScale {
+    Linear,
+    Cubic,
+}
+
+impl VolumeScale {
+    pub fn to_linear(self, volume: f64) -> f64 {
+        match self {
+            VolumeScale::Linear => volume,
+            VolumeScale::Cubic => volume.powi(3),
+        }
+    }
+
+    pub fn to_scale(self, volume: f64) -> f64 {
+        match self {
+            VolumeScale::Linear => volume,
+            VolumeScale::Cubic => volume.cbrt(),
+        }
+    }
+}
+
+/// Called from a GStreamer streaming thread, returns the next URI to play without gap.
+pub type AboutToFinish = dyn Fn() -> Option<String> + Send + Sync;
+
+pub struct Element {
+    ptr: *mut GstElement,
+    about_to_finish: Option<Box<Box<AboutToFinish>>>,
+}
+
+impl Element {
+    /// The URI is a property and never parsed as a pipeline description.
+    pub fn new(uri: &str) -> Result<Self, AppError> {
+        super::init()?;
+
+        let factory_name = CString::new(PLAYBIN_FACTORY)?;
+
+        let element_ptr = unsafe { gst_element_factory_make(factory_name.as_ptr(), null()) };
+
+        if element_ptr.is_null() {
+            return Err(AppError::Ffi("The pipeline is null.".to_owned()));
+        }
+
+        let element = Self {
+            ptr: element_ptr,
+            about_to_finish: None,
+        };
+        element.set_uri(uri)?;
+
+        Ok(element)
+    }
+
+    pub fn get(&self) -> *mut GstElement {
+        self.ptr
+    }
+
+    pub fn set_uri(&self, uri: &str) -> Result<(), AppError> {
+        set_uri(self.get(), uri)
+    }
+
+    pub fn set_volume(&self, volume: f64, scale: VolumeScale) -> Result<(), AppError> {
+        let property = CString::new(VOLUME_PROPERTY)?;
+        let value: f64 = scale.to_linear(volume);
+
+        unsafe {
+            g_object_set(
+                self.get() as *mut GObject,
+                property.as_ptr(),
+                value,
+                null::<c_char>(),
+            )
+        };
+
+        Ok(())
+    }
+
+    pub fn volume(&self, scale: VolumeScale) -> Result<f64, AppError> {
+        let property = CString::new(VOLUME_PROPERTY)?;
+        let mut value: f64 = 0.0;
+
+        unsafe {
+            g_object_get(
+                self.get() as *mut GObject,
+                property.as_ptr(),
+                &mut value as *mut f64,
+                null::<c_char>(),
+            )
+        };
+
+        Ok(scale.to_scale(value))
+    }
+
+    pub fn set_mute(&self, mute: bool) -> Result<(), AppError> {
+        let property = CString::new(MUTE_PROPERTY)?;
+        let value: gboolean = if mute { GTRUE } else { GFALSE };
+
+        unsafe {
+            g_object_set(
+                self.get() as *mut GObject,
+                property.as_ptr(),
+                value,
+                null::<c_char>(),
+            )
+        };
+
+        Ok(())
+    }
+
+    pub fn is_muted(&self) -> Result<bool, AppError> {
+        let property = CString::new(MUTE_PROPERTY)?;
+        let mut value: gboolean = GFALSE;
+
+        unsafe {
+            g_object_get(
+                self.get() as *mut GObject,
+                property.as_ptr(),
+                &mut value as *mut gboolean,
+                null::<c_char>(),
+            )
+        };
+
+        Ok(value != GFALSE)
+    }
+
+    pub fn connect_about_to_finish(
+        &mut self,
+        about_to_finish: Box<AboutToFinish>,
+    ) -> Result<(), AppError> {
+        let signal = CString::new(ABOUT_TO_FINISH_SIGNAL)?;
+        let about_to_finish_box = Box::new(about_to_finish);
+        let data = &*about_to_finish_box as *const Box<AboutToFinish> as gpointer;
+
+        let handler_id = unsafe {
+            g_signal_connect_data(
+                self.get() as *mut GObject,
+                signal.as_ptr(),
+                Some(mem::transmute::<
+                    unsafe extern "C" fn(*mut GstElement, gpointer),
+                    unsafe extern "C" fn(),
+                >(about_to_finish_trampoline)),
+                data,
+                None,
+                0,
+            )
+        };
+
+        if handler_id == 0 {
+            return Err(AppError::Ffi(format!(
+                "Unable to connect to the signal `{ABOUT_TO_FINISH_SIGNAL}`."
+            )));
+        }
+
+        // Kept alive with the element since GStreamer only holds the raw pointer.
+        self.about_to_finish = Some(about_to_finish_box);
+
+        Ok(())
+    }
+
+    /// The success, asynchronous or no preroll return.
+    pub fn set_state(&self, state: GstState) -> Result<GstStateChangeReturn, AppError> {
+        let state_change_return = unsafe { gst_element_set_state(self.get(), state) };
+
+        if state_change_return == GST_STATE_CHANGE_FAILURE {
+            return Err(AppError::Ffi(format!(
+                "State change return failure: {state_change_return}"
+            )));
+        }
+
+        Ok(state_change_return)
+    }
+
+    pub fn get_bus(&self) -> Result<Bus, AppError> {
+        let bus = unsafe { gst_element_get_bus(self.get()) };
+
+        Bus::new(bus)
+    }
+
+    pub fn query_duration(&self, format: GstFormat) -> Result<i64, AppError> {
+        self.query(|duration| unsafe { gst_element_query_duration(self.get(), format, duration) })
+    }
+
+    pub fn query_position(&self, format: GstFormat) -> Result<i64, AppError> {
+        self.query(|position| unsafe { gst_element_query_position(self.get(), format, position) })
+    }
+
+    pub fn seek_simple(
+        &self,
+        format: GstFormat,
+        seek_flags: GstSeekFlags,
+        seek_pos: i64,
+    ) -> Result<(), AppError> {
+        let result = unsafe { gst_element_seek_simple(self.get(), format, seek_flags, seek_pos) };
+
+        if result == GFALSE {
+            return Err(AppError::Ffi(format!(
+                "The seek to the position `{seek_pos}` has failed."
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// To the end without `stop`. With `GST_SEEK_FLAG_SEGMENT`, a segment done message is
+    /// posted at the stop instead of the end of stream.
+    pub fn seek(
+        &self,
+        format: GstFormat,
+        seek_flags: GstSeekFlags,
+        start: i64,
+        stop: Option<i64>,
+    ) -> Result<(), AppError> {
+        let (stop_type, stop) = match stop {
+            Some(stop) => (GST_SEEK_TYPE_SET, stop),
+            None => (GST_SEEK_TYPE_NONE, -1),
+        };
+
+        let result = unsafe {
+            gst_element_seek(
+                self.get(),
+                1.0,
+                format,
+                seek_flags,
+                GST_SEEK_TYPE_SET,
+                start,
+                stop_type,
+                stop,
+            )
+        };
+
+        if result == GFALSE {
+            return Err(AppError::Ffi(format!(
+                "The seek to the segment `{start}..{stop}` has failed."
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn query<F>(&self, f: F) -> Result<i64, AppError>
+    where
+        F: FnOnce(*mut i64) -> gboolean,
+    {
+        let mut value: i64 = -1;
+        let result = f(&mut value);
+
+        if result == GFALSE || value == -1 {
+            return Err(AppError::Ffi(
+                "No result returned form the duration or position query.".to_owned(),
+            ));
+        }
+
+        Ok(value)
+    }
+}
+
+unsafe extern "C" fn about_to_finish_trampoline(element: *mut GstElement, data: gpointer) {
+    let about_to_finish = &*(data as *const Box<AboutToFinish>);
+
+    if let Some(uri) = about_to_finish() {
+        set_uri(element, &uri).unwrap_or_else(|err| eprintln!("Unable to set the next uri: {err}"));
+    }
+}
+
+fn set_uri(element: *mut GstElement, uri: &str) -> Result<(), AppError> {
+    let property = CString::new(URI_PROPERTY)?;
+    let value = CString::new(uri)?;
+
+    unsafe {
+        g_object_set(
+            element as *mut GObject,
+            property.as_ptr(),
+            value.as_ptr(),
+            null::<c_char>(),
+        )
+    };
+
+    Ok(())
+}
+
+impl Debug for Element {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Element")
+            .field("ptr", &self.ptr)
+            .field("about_to_finish", &self.about_to_finish.is_some())
+            .finish()
+    }
+}
+
+impl Drop for Element {
+    fn drop(&mut self) {
+        if let Err(status) = self.set_state(GST_STATE_NULL) {
+            eprintln!("GStreamer set state returns status `{status}`");
+        }
+
+        unsafe {
+            gst_object_unref(self.get() as *mut GstObject);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gstreamer_sys::{
+        GST_FORMAT_TIME, GST_SEEK_FLAG_FLUSH, GST_SEEK_FLAG_KEY_UNIT, GST_SEEK_FLAG_SEGMENT,
+        GST_STATE_NULL, GST_STATE_PAUSED,
+    };
+
+    use crate::streamer::sys::{
+        common_tests::{
+            RcRefCellTestStructure, TestObjectType, TestStructure, QUERY_DURATION_VALUE,
+            QUERY_POSITION_VALUE, UNASSIGNED,
+        },
+        element::{Element, VolumeScale},
+    };
+
+    #[test]
+    fn test_new() {
+        let test_structure = TestStructure::new_arc_mutex_assigned();
+        let uri = test_structure.test_nb().to_string();
+
+        let element = Element::new(&uri).unwrap();
+
+        assert!(!element.get().is_null());
+        assert!(test_structure.is_property_set("uri"));
+    }
+
+    #[test]
+    fn test_set_test() {
+        let test_structure = TestStructure::new_arc_mutex_assigned();
+        let uri = test_structure.test_nb().to_string();
+
+        let element = Element::new(&uri).unwrap();
+        element.set_state(GST_STATE_PAUSED).unwrap();
+
+        assert!(test_structure.element_state() == GST_STATE_PAUSED);
+    }
+
+    #[test]
+    fn test_get_bus_ok() {
+        let test_structure = TestStructure::new_arc_mutex_assigned();
+        let uri = test_structure.test_nb().to_string();
+
+        let element = Element::new(&uri).unwrap();
+        let bus_res = element.get_bus();
+
+        assert!(bus_res.is_ok());
+    }
+
+    #[test]
+    fn test_get_bus_err() {
+        let uri = UNASSIGNED.to_string();
+
+        let element = Element::new(&uri).unwrap();
+        let bus_res = element.get_bus();
+
+        assert!(bus_res.is_err());
+    }
+
+    #[test]
+    fn test_query_duration() {
+        let test_structure = TestStructure::new_arc_mutex_assigned();
+        let uri = test_structure.test_nb().to_string();
+
+        let element = Element::new(&uri).unwrap();
+        let duration = element.query_duration(GST_FORMAT_TIME).unwrap();
+
+        assert_eq!(duration, QUERY_DURATION_VALUE);
+    }
+
+    #[test]
+    fn test_query_position() {
+        let test_structure = TestStructure::new_arc_mutex_assigned();
+        let uri = test_structure.test_nb().to_string();
+
+        let element = Element::new(&uri).unwrap();
+        let position = element.query_position(GST_FORMAT_TIME).unwrap();
+
+        assert_eq!(position, QUERY_POSITION_VALUE);
+    }
+
+    #[test]
+    fn test_query_err() {
+        let uri = UNASSIGNED.to_string();
+
+        let element = Element::new(&uri).unwrap();
+        let position_res = element.query_position(GST_FORMAT_TIME);
+
+        assert!(position_res.is_err());
+    }
+
+    #[test]
+    fn test_seek_simple_ok() {
+        let test_structure = TestStructure::new_arc_mutex_assigned();
+        let uri = test_structure.test_nb().to_string();
+
+        let element = Element::new(&uri).unwrap();
+        let result = element.seek_simple(
+            GST_FORMAT_TIME,
+            GST_SEEK_FLAG_FLUSH | GST_SEEK_FLAG_KEY_UNIT,
+            1_000_000_000,
+        );
+
+        assert!(result.is_ok());
+        assert_eq!(test_structure.seek_position(), 1_000_000_000);
+    }
+
+    #[test]
+    fn test_seek_simple_err() {
+        let test_structure = TestStructure::new_arc_mutex_assigned();
+        let uri = test_structure.test_nb().to_string();
+
+        let element = Element::new(&uri).unwrap();
+        let result = element.seek_simple(GST_FORMAT_TIME, GST_SEEK_FLAG_FLUSH, -1);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_seek() {
+        let test_structure = TestStructure::new_arc_mutex_assigned();
+        let uri = test_structure.test_nb().to_string();
+
+        let element = Element::new(&uri).unwrap();
+        element
+            .seek(
+                GST_FORMAT_TIME,
+                GST_SEEK_FLAG_FLUSH | GST_SEEK_FLAG_SEGMENT,
+                1_000_000_000,
+                Some(2_000_000_000),
+            )
+            .unwrap();
+
+        assert_eq!(test_structure.seek_position(), 1_000_000_000);
+        assert_eq!(test_structure.seek_stop(), 2_000_000_000);
+        assert!(element
+            .seek(GST_FORMAT_TIME, GST_SEEK_FLAG_FLUSH, -1, None)
+            .is_err());
+    }
+
+    #[test]
+    fn test_connect_about_to_finish_ok() {
+        let test_structure = TestStructure::new_arc_mutex_assigned();
+        let uri = test_structure.test_nb().to_string();
+
+        let mut element = Element::new(&uri).unwrap();
+        let result = element.connect_about_to_finish(Box::new(|| None));
+
+        assert!(result.is_ok());
+        assert_eq!(
+            test_structure.connected_signal().as_deref(),
+            Some("about-to-finish")
+        );
+    }
+
+    #[test]
+    fn test_connect_about_to_finish_err() {
+        let uri = UNASSIGNED.to_string();
+
+        let mut element = Element::new(&uri).unwrap();
+        let result = element.connect_about_to_finish(Box::new(|| None));
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_volume_scale() {
+        assert_eq!(VolumeScale::Cubic.to_linear(0.5), 0.125);
+        assert_eq!(VolumeScale::Cubic.to_scale(0.125), 0.5);
+        assert_eq!(VolumeScale::Linear.to_linear(0.5), 0.5);
+        assert_eq!(VolumeScale::Linear.to_scale(0.5), 0.5);
+    }
+
+    #[test]
+    fn test_drop() {
+        let test_structure = TestStructure::new_arc_mutex_assigned();
+        let uri = test_structure.test_nb().to_string();
+
+        {
+            let _element = Element::new(&uri).unwrap();
+        }
+
+        assert!(test_structure.element_state() == GST_STATE_NULL);
+        assert!(test_structure.is_unref(TestObjectType::GstElement));
+    }
+}
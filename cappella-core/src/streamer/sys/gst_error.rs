@@ -0,0 +1,127 @@
+use std::{ffi::CStr, fmt::Display};
+
+use glib_sys::{g_error_free, g_quark_to_string, GError};
+use gstreamer_sys::{
+    GST_CORE_ERROR_MISSING_PLUGIN, GST_RESOURCE_ERROR_OPEN_READ, GST_RESOURCE_ERROR_READ,
+    GST_STREAM_ERROR_CODEC_NOT_FOUND,
+};
+
+pub const CORE_ERROR_DOMAIN: &str = "gst-core-error-quark";
+pub const RESOURCE_ERROR_DOMAIN: &str = "gst-resource-error-quark";
+pub const STREAM_ERROR_DOMAIN: &str = "gst-stream-error-quark";
+
+/// An error or a warning posted on the bus by an element.
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct GstError {
+    pub domain: String,
+    pub code: i32,
+    pub message: String,
+    pub debug: Option<String>,
+    /// The name of the element posting the message.
+    pub source: Option<String>,
+}
+
+impl GstError {
+    /// Takes the ownership of the `GError`, `None` if it is null.
+    pub fn from_g_error(error: *mut GError) -> Option<Self> {
+        if error.is_null() {
+            return None;
+        }
+
+        let gst_error = unsafe {
+            Self {
+                domain: CStr::from_ptr(g_quark_to_string((*error).domain))
+                    .to_string_lossy()
+                    .into_owned(),
+                code: (*error).code,
+                message: CStr::from_ptr((*error).message)
+                    .to_string_lossy()
+                    .into_owned(),
+                ..Self::default()
+            }
+        };
+        unsafe { g_error_free(error) };
+
+        Some(gst_error)
+    }
+
+    pub fn is_missing_plugin(&self) -> bool {
+        (self.domain == CORE_ERROR_DOMAIN && self.code == GST_CORE_ERROR_MISSING_PLUGIN)
+            || (self.domain == STREAM_ERROR_DOMAIN && self.code == GST_STREAM_ERROR_CODEC_NOT_FOUND)
+    }
+
+    /// The connection is refused or lost, not as a missing file or a decoding error.
+    pub fn is_network_drop(&self) -> bool {
+        self.domain == RESOURCE_ERROR_DOMAIN
+            && (self.code == GST_RESOURCE_ERROR_OPEN_READ || self.code == GST_RESOURCE_ERROR_READ)
+    }
+}
+
+impl Display for GstError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.source {
+            Some(source) => write!(f, "{source}: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gstreamer_sys::{
+        GST_CORE_ERROR_MISSING_PLUGIN, GST_RESOURCE_ERROR_READ, GST_STREAM_ERROR_CODEC_NOT_FOUND,
+    };
+
+    use super::{GstError, CORE_ERROR_DOMAIN, RESOURCE_ERROR_DOMAIN, STREAM_ERROR_DOMAIN};
+
+    #[test]
+    fn test_is_missing_plugin() {
+        let core_error = GstError {
+            domain: CORE_ERROR_DOMAIN.to_owned(),
+            code: GST_CORE_ERROR_MISSING_PLUGIN,
+            ..GstError::default()
+        };
+        let stream_error = GstError {
+            domain: STREAM_ERROR_DOMAIN.to_owned(),
+            code: GST_STREAM_ERROR_CODEC_NOT_FOUND,
+            ..GstError::default()
+        };
+        let other_error = GstError {
+            domain: STREAM_ERROR_DOMAIN.to_owned(),
+            code: GST_CORE_ERROR_MISSING_PLUGIN,
+            ..GstError::default()
+        };
+
+        assert!(core_error.is_missing_plugin());
+        assert!(stream_error.is_missing_plugin());
+        assert!(!other_error.is_missing_plugin());
+    }
+
+    #[test]
+    fn test_is_network_drop() {
+        let read_error = GstError {
+            domain: RESOURCE_ERROR_DOMAIN.to_owned(),
+            code: GST_RESOURCE_ERROR_READ,
+            ..GstError::default()
+        };
+        let stream_error = GstError {
+            domain: STREAM_ERROR_DOMAIN.to_owned(),
+            code: GST_RESOURCE_ERROR_READ,
+            ..GstError::default()
+        };
+
+        assert!(read_error.is_network_drop());
+        assert!(!stream_error.is_network_drop());
+    }
+
+    #[test]
+    fn test_display() {
+        let error = GstError {
+            message: "Could not decode stream.".to_owned(),
+            source: Some("decodebin0".to_owned()),
+            ..GstError::default()
+        };
+
+        assert_eq!(error.to_string(), "decodebin0: Could not decode stream.");
+    }
+}
@@ -0,0 +1,249 @@
+use std::{
+    ffi::{c_char, CStr},
+    fmt::{Debug, Display},
+    ptr::null_mut,
+};
+
+use glib_sys::{g_free, gpointer, GError, GTRUE};
+use gstreamer_pbutils_sys::{
+    gst_is_missing_plugin_message, gst_missing_plugin_message_get_description,
+};
+use gstreamer_sys::{
+    gst_message_get_structure, gst_message_parse_buffering, gst_message_parse_error,
+    gst_message_parse_state_changed, gst_message_parse_tag, gst_message_parse_warning,
+    gst_message_unref, gst_object_get_name, GstMessage, GstMessageType, GstObject, GstState,
+    GstStructure, GstTagList, GST_STATE_NULL,
+};
+
+use crate::local::app_error::AppError;
+
+use super::{gst_error::GstError, state::State, structure::Structure, taglist::TagList};
+
+type ParseErrorFn = unsafe extern "C" fn(*mut GstMessage, *mut *mut GError, *mut *mut c_char);
+
+#[derive(Debug)]
+pub struct Message(*mut GstMessage);
+
+impl Message {
+    pub fn new(message: *mut GstMessage) -> Result<Self, AppError> {
+        if message.is_null() {
+            return Err(AppError::Ffi("The message pointer is null.".to_owned()));
+        }
+        Ok(Self(message))
+    }
+
+    pub fn get(&self) -> *mut GstMessage {
+        self.0
+    }
+
+    pub fn type_(&self) -> GstMessageType {
+        unsafe { (*self.get()).type_ }
+    }
+
+    pub fn src(&self) -> *mut GstObject {
+        unsafe { (*self.get()).src }
+    }
+
+    pub fn structure(&self) -> Result<Structure, AppError> {
+        let structure_ptr = unsafe { gst_message_get_structure(self.get()) } as *mut GstStructure;
+
+        Structure::new_from_message(structure_ptr)
+    }
+
+    pub fn state_changed(&self) -> State {
+        let mut old_state: GstState = GST_STATE_NULL;
+        let mut new_state: GstState = GST_STATE_NULL;
+        let mut pending_state: GstState = GST_STATE_NULL;
+
+        unsafe {
+            gst_message_parse_state_changed(
+                self.get(),
+                &mut old_state,
+                &mut new_state,
+                &mut pending_state,
+            )
+        };
+
+        State::new(old_state, new_state, pending_state)
+    }
+
+    /// For a `GST_MESSAGE_BUFFERING`, the percentage of the buffer filled.
+    pub fn buffering(&self) -> i32 {
+        let mut percent = 0;
+
+        unsafe { gst_message_parse_buffering(self.get(), &mut percent) };
+
+        percent
+    }
+
+    /// For a `GST_MESSAGE_ERROR`.
+    pub fn error(&self) -> GstError {
+        self.parse_error(gst_message_parse_error)
+    }
+
+    /// For a `GST_MESSAGE_WARNING`.
+    pub fn warning(&self) -> GstError {
+        self.parse_error(gst_message_parse_warning)
+    }
+
+    /// For a `GST_MESSAGE_ELEMENT`, `None` if it is not a missing plugin message.
+    pub fn missing_plugin_description(&self) -> Option<String> {
+        if unsafe { gst_is_missing_plugin_message(self.get()) } != GTRUE {
+            return None;
+        }
+
+        take_string(unsafe { gst_missing_plugin_message_get_description(self.get()) })
+    }
+
+    pub fn source_name(&self) -> Option<String> {
+        let src = self.src();
+
+        if src.is_null() {
+            return None;
+        }
+
+        take_string(unsafe { gst_object_get_name(src) })
+    }
+
+    fn parse_error(&self, parse: ParseErrorFn) -> GstError {
+        let mut error: *mut GError = null_mut();
+        let mut debug: *mut c_char = null_mut();
+
+        unsafe { parse(self.get(), &mut error, &mut debug) };
+
+        GstError {
+            debug: take_string(debug),
+            source: self.source_name(),
+            ..GstError::from_g_error(error).unwrap_or_default()
+        }
+    }
+
+    pub fn tags(&self) -> Result<TagList, AppError> {
+        let mut tag_list: *mut GstTagList = null_mut();
+
+        unsafe { gst_message_parse_tag(self.get(), &mut tag_list) };
+
+        TagList::new(tag_list)
+    }
+}
+
+/// Copies and frees a string owned by the caller.
+fn take_string(string_ptr: *mut c_char) -> Option<String> {
+    if string_ptr.is_null() {
+        return None;
+    }
+
+    let string = unsafe { CStr::from_ptr(string_ptr) }
+        .to_string_lossy()
+        .into_owned();
+    unsafe { g_free(string_ptr as gpointer) };
+
+    Some(string)
+}
+
+impl Display for Message {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "message_ptr: {:?}", self.0)
+    }
+}
+
+impl Drop for Message {
+    fn drop(&mut self) {
+        unsafe { gst_message_unref(self.get()) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr::null_mut;
+
+    use gstreamer_sys::{GST_STATE_NULL, GST_STATE_PAUSED, GST_STATE_PLAYING};
+
+    use crate::streamer::sys::{
+        common_tests::{
+            RcRefCellTestStructure, TestObjectType, TestStructure, BUFFERING_PERCENT_VALUE,
+            UNASSIGNED,
+        },
+        message::Message,
+    };
+
+    #[test]
+    fn test_new_ok() {
+        let test_structure = TestStructure::new_arc_mutex(UNASSIGNED);
+        let message_res = Message::new(test_structure.faked_gst_message());
+
+        assert!(message_res.is_ok());
+    }
+
+    #[test]
+    fn test_new_err() {
+        let message_res = Message::new(null_mut());
+
+        assert!(message_res.is_err());
+    }
+
+    #[test]
+    fn test_structure_ok() {
+        let test_structure = TestStructure::new_arc_mutex_assigned();
+        let message = Message::new(test_structure.faked_gst_message()).unwrap();
+
+        let structure_res = message.structure();
+
+        assert!(structure_res.is_ok());
+    }
+
+    #[test]
+    fn test_structure_err() {
+        let test_structure = TestStructure::new_arc_mutex(UNASSIGNED);
+        let message = Message::new(test_structure.faked_gst_message()).unwrap();
+
+        let structure_res = message.structure();
+
+        assert!(structure_res.is_err());
+    }
+
+    #[test]
+    fn test_state_changer() {
+        let test_structure = TestStructure::new_arc_mutex(UNASSIGNED);
+        let message = Message::new(test_structure.faked_gst_message()).unwrap();
+
+        let state = message.state_changed();
+
+        assert_eq!(state.old_state(), GST_STATE_PAUSED);
+        assert_eq!(state.new_state(), GST_STATE_PLAYING);
+        assert_eq!(state.pending_state(), GST_STATE_NULL);
+    }
+
+    #[test]
+    fn test_buffering() {
+        let test_structure = TestStructure::new_arc_mutex(UNASSIGNED);
+        let message = Message::new(test_structure.faked_gst_message()).unwrap();
+
+        let percent = message.buffering();
+
+        assert_eq!(percent, BUFFERING_PERCENT_VALUE);
+    }
+
+    #[test]
+    fn test_tags() {
+        let test_structure = TestStructure::new_arc_mutex_assigned();
+        let message = Message::new(test_structure.faked_gst_message()).unwrap();
+
+        let tags_res = message.tags();
+
+        assert!(tags_res.is_ok());
+    }
+
+    #[test]
+    fn test_drop() {
+        let test_structure = TestStructure::new_arc_mutex_assigned();
+        {
+            let _message = Message::new(test_structure.faked_gst_message());
+        }
+
+        assert!(
+            test_structure.is_unref(TestObjectType::GstMessage),
+            "The message is not unref."
+        )
+    }
+}
@@ -5,10 +5,11 @@ use std::{
     ptr::{null, null_mut},
 };
 
-use glib_sys::{GType, GFALSE};
+use glib_sys::{gboolean, GType, GFALSE};
 use gstreamer_sys::{
-    gst_message_new_application, gst_structure_get_int64, gst_structure_get_name,
-    gst_structure_get_string, gst_structure_get_uint64, gst_structure_new, GstStructure,
+    gst_message_new_application, gst_structure_get_boolean, gst_structure_get_double,
+    gst_structure_get_int64, gst_structure_get_name, gst_structure_get_string, gst_structure_new,
+    GstStructure,
 };
 
 use crate::local::app_error::AppError;
@@ -44,12 +45,12 @@ impl Structure {
 
         c_values.push_back(null());
         let first_field = c_values.pop_front().ok_or_else(|| {
-            AppError::new("The first field (name) of the structure is empty.".to_owned())
+            AppError::InvalidMessage("The first field (name) of the structure is empty.".to_owned())
         })?;
         let ptr = unsafe { gst_structure_new(c_name.as_ptr(), first_field, c_values) };
 
         if ptr.is_null() {
-            return Err(AppError::new(
+            return Err(AppError::Ffi(
                 "GStreamer returned a null structure for the message.".to_owned(),
             ));
         }
@@ -63,7 +64,9 @@ impl Structure {
 
     pub fn new_from_message(ptr: *mut GstStructure) -> Result<Self, AppError> {
         if ptr.is_null() {
-            Err(AppError::new("The message structure is null.".to_owned()))?;
+            Err(AppError::InvalidMessage(
+                "The message structure is null.".to_owned(),
+            ))?;
         }
 
         let name_ptr = unsafe { gst_structure_get_name(ptr) };
@@ -99,7 +102,7 @@ impl Structure {
             unsafe { gst_structure_get_string(self.get(), field_name_cstring.as_ptr()) };
 
         if value_ptr.is_null() {
-            return Err(AppError::new(format!(
+            return Err(AppError::InvalidMessage(format!(
                 "The value is `null` for the String field `{field_name}`."
             )));
         }
@@ -109,7 +112,6 @@ impl Structure {
             .to_owned())
     }
 
-    #[allow(dead_code)]
     pub fn get_i64(&self, field_name: &str) -> Result<i64, AppError> {
         let field_name_cstring = self.field_name_to_cstring(field_name)?;
 
@@ -118,7 +120,7 @@ impl Structure {
             unsafe { gst_structure_get_int64(self.get(), field_name_cstring.as_ptr(), &mut value) };
 
         if result == GFALSE {
-            return Err(AppError::new(format!(
+            return Err(AppError::InvalidMessage(format!(
                 "The value is `null` for the i64 field `{field_name}`."
             )));
         }
@@ -126,23 +128,40 @@ impl Structure {
         Ok(value)
     }
 
-    pub fn get_u64(&self, field_name: &str) -> Result<u64, AppError> {
+    pub fn get_f64(&self, field_name: &str) -> Result<f64, AppError> {
         let field_name_cstring = self.field_name_to_cstring(field_name)?;
 
-        let mut value: u64 = 0;
+        let mut value: f64 = 0.0;
         let result = unsafe {
-            gst_structure_get_uint64(self.get(), field_name_cstring.as_ptr(), &mut value)
+            gst_structure_get_double(self.get(), field_name_cstring.as_ptr(), &mut value)
         };
 
         if result == GFALSE {
-            return Err(AppError::new(format!(
-                "The value is `null` for the u64 field `{field_name}`."
+            return Err(AppError::InvalidMessage(format!(
+                "The value is `null` for the f64 field `{field_name}`."
             )));
         }
 
         Ok(value)
     }
 
+    pub fn get_bool(&self, field_name: &str) -> Result<bool, AppError> {
+        let field_name_cstring = self.field_name_to_cstring(field_name)?;
+
+        let mut value: gboolean = GFALSE;
+        let result = unsafe {
+            gst_structure_get_boolean(self.get(), field_name_cstring.as_ptr(), &mut value)
+        };
+
+        if result == GFALSE {
+            return Err(AppError::InvalidMessage(format!(
+                "The value is `null` for the bool field `{field_name}`."
+            )));
+        }
+
+        Ok(value != GFALSE)
+    }
+
     fn field_name_to_cstring(&self, field_name: &str) -> Result<CString, AppError> {
         Ok(CString::new(field_name)?)
     }
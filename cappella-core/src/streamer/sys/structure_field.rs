@@ -1,7 +1,7 @@
 use std::{any::Any, ffi::CString, fmt::Debug};
 
-use glib_sys::GType;
-use gobject_sys::{G_TYPE_INT64, G_TYPE_STRING, G_TYPE_UINT64};
+use glib_sys::{gboolean, GType, GFALSE, GTRUE};
+use gobject_sys::{G_TYPE_BOOLEAN, G_TYPE_DOUBLE, G_TYPE_INT64, G_TYPE_STRING};
 
 pub trait Field: Debug {
     fn field_name(&self) -> &str;
@@ -16,7 +16,6 @@ pub fn new_box_string(field_name: &str, value: &str) -> Box<dyn Field> {
     })
 }
 
-#[allow(dead_code)]
 pub fn new_box_i64(field_name: &str, value: i64) -> Box<dyn Field> {
     Box::new(FieldI64 {
         field_name: field_name.to_owned(),
@@ -24,8 +23,15 @@ pub fn new_box_i64(field_name: &str, value: i64) -> Box<dyn Field> {
     })
 }
 
-pub fn new_box_u64(field_name: &str, value: u64) -> Box<dyn Field> {
-    Box::new(FieldU64 {
+pub fn new_box_f64(field_name: &str, value: f64) -> Box<dyn Field> {
+    Box::new(FieldF64 {
+        field_name: field_name.to_owned(),
+        value,
+    })
+}
+
+pub fn new_box_bool(field_name: &str, value: bool) -> Box<dyn Field> {
+    Box::new(FieldBool {
         field_name: field_name.to_owned(),
         value,
     })
@@ -72,21 +78,42 @@ impl Field for FieldI64 {
 }
 
 #[derive(Debug)]
-pub struct FieldU64 {
+pub struct FieldF64 {
     field_name: String,
-    value: u64,
+    value: f64,
 }
 
-impl Field for FieldU64 {
+impl Field for FieldF64 {
     fn field_name(&self) -> &str {
         self.field_name.as_str()
     }
 
     fn g_type(&self) -> GType {
-        G_TYPE_UINT64
+        G_TYPE_DOUBLE
     }
 
     fn c_value(&self) -> Box<dyn Any> {
         Box::new(self.value)
     }
 }
+
+#[derive(Debug)]
+pub struct FieldBool {
+    field_name: String,
+    value: bool,
+}
+
+impl Field for FieldBool {
+    fn field_name(&self) -> &str {
+        self.field_name.as_str()
+    }
+
+    fn g_type(&self) -> GType {
+        G_TYPE_BOOLEAN
+    }
+
+    fn c_value(&self) -> Box<dyn Any> {
+        let value: gboolean = if self.value { GTRUE } else { GFALSE };
+        Box::new(value)
+    }
+}
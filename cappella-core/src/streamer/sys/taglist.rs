@@ -0,0 +1,179 @@
+use std::{
+    ffi::{c_char, c_uint, CStr, CString},
+    mem,
+    ptr::null_mut,
+    slice,
+};
+
+use glib_sys::{g_free, gpointer, GTRUE};
+use gstreamer_sys::{
+    gst_buffer_map, gst_buffer_unmap, gst_caps_get_structure, gst_mini_object_ref,
+    gst_mini_object_unref, gst_sample_get_buffer, gst_sample_get_caps, gst_structure_get_name,
+    gst_tag_list_get_sample, gst_tag_list_get_string, gst_tag_list_get_uint, GstMapInfo,
+    GstMiniObject, GstSample, GstTagList, GST_MAP_READ,
+};
+
+use crate::local::app_error::AppError;
+
+pub const TAG_TITLE: &str = "title";
+pub const TAG_ARTIST: &str = "artist";
+pub const TAG_ALBUM: &str = "album";
+pub const TAG_ALBUM_ARTIST: &str = "album-artist";
+pub const TAG_GENRE: &str = "genre";
+pub const TAG_TRACK_NUMBER: &str = "track-number";
+pub const TAG_DISC_NUMBER: &str = "album-disc-number";
+/// The `icy-name` header of a radio.
+pub const TAG_ORGANIZATION: &str = "organization";
+pub const TAG_IMAGE: &str = "image";
+pub const TAG_PREVIEW_IMAGE: &str = "preview-image";
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Image {
+    pub mime_type: String,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub struct TagList(*mut GstTagList);
+
+impl TagList {
+    /// Takes the ownership of the tag list.
+    pub fn new(tag_list: *mut GstTagList) -> Result<Self, AppError> {
+        if tag_list.is_null() {
+            return Err(AppError::Ffi("The tag list pointer is null.".to_owned()));
+        }
+
+        Ok(Self(tag_list))
+    }
+
+    /// Keeps a new reference on a tag list owned by someone else.
+    pub fn new_ref(tag_list: *const GstTagList) -> Result<Self, AppError> {
+        if tag_list.is_null() {
+            return Err(AppError::Ffi("The tag list pointer is null.".to_owned()));
+        }
+
+        let tag_list_ptr = unsafe { gst_mini_object_ref(tag_list as *mut GstMiniObject) };
+
+        Self::new(tag_list_ptr as *mut GstTagList)
+    }
+
+    pub fn get(&self) -> *mut GstTagList {
+        self.0
+    }
+
+    pub fn get_string(&self, tag: &str) -> Result<Option<String>, AppError> {
+        let tag_c_str = CString::new(tag)?;
+        let mut value_ptr: *mut c_char = null_mut();
+
+        let is_found =
+            unsafe { gst_tag_list_get_string(self.get(), tag_c_str.as_ptr(), &mut value_ptr) };
+
+        if is_found != GTRUE || value_ptr.is_null() {
+            return Ok(None);
+        }
+
+        let value = unsafe { CStr::from_ptr(value_ptr) }
+            .to_string_lossy()
+            .into_owned();
+        unsafe { g_free(value_ptr as gpointer) };
+
+        Ok(Some(value))
+    }
+
+    pub fn get_uint(&self, tag: &str) -> Result<Option<u32>, AppError> {
+        let tag_c_str = CString::new(tag)?;
+        let mut value: c_uint = 0;
+
+        let is_found = unsafe { gst_tag_list_get_uint(self.get(), tag_c_str.as_ptr(), &mut value) };
+
+        if is_found != GTRUE {
+            return Ok(None);
+        }
+
+        Ok(Some(value))
+    }
+
+    /// The image is copied out of the sample.
+    pub fn get_image(&self, tag: &str) -> Result<Option<Image>, AppError> {
+        let tag_c_str = CString::new(tag)?;
+        let mut sample: *mut GstSample = null_mut();
+
+        let is_found =
+            unsafe { gst_tag_list_get_sample(self.get(), tag_c_str.as_ptr(), &mut sample) };
+
+        if is_found != GTRUE || sample.is_null() {
+            return Ok(None);
+        }
+
+        let image = sample_to_image(sample);
+        unsafe { gst_mini_object_unref(sample as *mut GstMiniObject) };
+
+        Ok(image)
+    }
+}
+
+fn sample_to_image(sample: *mut GstSample) -> Option<Image> {
+    let buffer = unsafe { gst_sample_get_buffer(sample) };
+    let caps = unsafe { gst_sample_get_caps(sample) };
+
+    if buffer.is_null() || caps.is_null() {
+        return None;
+    }
+
+    let mime_type_ptr = unsafe { gst_structure_get_name(gst_caps_get_structure(caps, 0)) };
+    let mime_type = unsafe { CStr::from_ptr(mime_type_ptr) }
+        .to_string_lossy()
+        .into_owned();
+
+    let mut map_info: GstMapInfo = unsafe { mem::zeroed() };
+
+    if unsafe { gst_buffer_map(buffer, &mut map_info, GST_MAP_READ) } != GTRUE {
+        return None;
+    }
+
+    let data = unsafe { slice::from_raw_parts(map_info.data, map_info.size) }.to_vec();
+    unsafe { gst_buffer_unmap(buffer, &mut map_info) };
+
+    Some(Image { mime_type, data })
+}
+
+impl Drop for TagList {
+    fn drop(&mut self) {
+        unsafe { gst_mini_object_unref(self.get() as *mut GstMiniObject) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::streamer::sys::{
+        common_tests::{RcRefCellTestStructure, TestObjectType, TestStructure, TAG_TITLE_VALUE},
+        taglist::{TagList, TAG_ARTIST, TAG_TITLE},
+    };
+
+    #[test]
+    fn test_get_string() {
+        let test_structure = TestStructure::new_arc_mutex_assigned();
+        let tag_list = TagList::new(test_structure.faked_gst_tag_list()).unwrap();
+
+        assert_eq!(
+            tag_list.get_string(TAG_TITLE).unwrap().as_deref(),
+            Some(TAG_TITLE_VALUE)
+        );
+        assert_eq!(tag_list.get_string(TAG_ARTIST).unwrap(), None);
+    }
+
+    #[test]
+    fn test_new_err() {
+        assert!(TagList::new(std::ptr::null_mut()).is_err());
+    }
+
+    #[test]
+    fn test_drop() {
+        let test_structure = TestStructure::new_arc_mutex_assigned();
+        {
+            let _tag_list = TagList::new(test_structure.faked_gst_tag_list());
+        }
+
+        assert!(test_structure.is_unref(TestObjectType::GstTagList));
+    }
+}
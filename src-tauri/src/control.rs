@@ -0,0 +1,208 @@
+use std::path::PathBuf;
+
+use cappella_core::{
+    control::{
+        client::Client,
+        request::{self, Request},
+        server,
+    },
+    local::{app_error::AppError, uri},
+};
+
+pub const CTL_COMMAND: &str = "ctl";
+
+pub const CTL_USAGE: &str = "Usage: cappella ctl <command>
+
+Sends a command to the running instance and prints the JSON responses.
+
+Commands:
+  play <uri|path>..., pause, stop, next, previous, seek <seconds>,
+  volume <0.0-1.0>, mute, unmute, status, subscribe
+  or a raw JSON request, e.g. '{\"command\":\"get_status\"}'";
+
+const NANOS_PER_SECOND: f64 = 1_000_000_000.0;
+
+/// The socket of this instance, removed at the end.
+pub struct ControlSocket(pub PathBuf);
+
+/// Handles `cappella ctl ...` and forwards the files to a running instance.
+/// `None` when this process must start the application.
+pub fn run_from_args(args: &[String]) -> Option<i32> {
+    match args.split_first() {
+        Some((command, words)) if command == CTL_COMMAND => Some(ctl(words)),
+        _ => forward(args),
+    }
+}
+
+fn forward(args: &[String]) -> Option<i32> {
+    let uris = crate::startup_uris(args);
+    if uris.is_empty() {
+        return None;
+    }
+
+    // No running instance, this one plays the files.
+    let mut client = Client::connect(&server::default_socket_path()).ok()?;
+
+    Some(send_all(&mut client, request::play_uris(uris)))
+}
+
+fn ctl(words: &[String]) -> i32 {
+    let requests = match parse(words) {
+        Ok(requests) => requests,
+        Err(err) => {
+            eprintln!("{err}\n\n{CTL_USAGE}");
+            return 2;
+        }
+    };
+
+    let mut client = match Client::connect(&server::default_socket_path()) {
+        Ok(client) => client,
+        Err(err) => {
+            eprintln!("No running instance: {err}");
+            return 1;
+        }
+    };
+
+    let code = send_all(&mut client, requests.clone());
+
+    if code == 0 && requests.contains(&Request::Subscribe) {
+        print_events(&mut client)
+    } else {
+        code
+    }
+}
+
+/// Stops on the first error.
+fn send_all(client: &mut Client, requests: Vec<Request>) -> i32 {
+    for request in requests {
+        match client.request(&request) {
+            Ok(line) if is_error(&line) => {
+                eprintln!("{line}");
+                return 1;
+            }
+            Ok(line) => println!("{line}"),
+            Err(err) => {
+                eprintln!("{err}");
+                return 1;
+            }
+        }
+    }
+
+    0
+}
+
+/// Until the running instance ends.
+fn print_events(client: &mut Client) -> i32 {
+    loop {
+        match client.read_line() {
+            Ok(Some(line)) => println!("{line}"),
+            Ok(None) => return 0,
+            Err(err) => {
+                eprintln!("{err}");
+                return 1;
+            }
+        }
+    }
+}
+
+fn is_error(line: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(line)
+        .map(|value| value.get("error").is_some())
+        .unwrap_or(true)
+}
+
+fn parse(words: &[String]) -> Result<Vec<Request>, AppError> {
+    let (name, args) = words
+        .split_first()
+        .ok_or_else(|| AppError::InvalidInput("A command is expected.".to_owned()))?;
+
+    if name.starts_with('{') {
+        return Ok(vec![serde_json::from_str(&words.join(" "))?]);
+    }
+
+    match (name.as_str(), args) {
+        ("play", []) => Err(AppError::InvalidInput(
+            "`play` expects at least one URI or path.".to_owned(),
+        )),
+        ("play", paths) => Ok(request::play_uris(
+            paths
+                .iter()
+                .map(|path| uri::from_uri_or_path(path))
+                .collect::<Result<_, _>>()?,
+        )),
+        ("pause", []) => Ok(vec![Request::Pause]),
+        ("stop", []) => Ok(vec![Request::Stop]),
+        ("next", []) => Ok(vec![Request::Next]),
+        ("previous", []) => Ok(vec![Request::Previous]),
+        ("seek", [seconds]) => Ok(vec![Request::Seek {
+            position_ns: (parse_number(name, seconds)? * NANOS_PER_SECOND) as i64,
+        }]),
+        ("volume", [volume]) => Ok(vec![Request::SetVolume {
+            volume: parse_number(name, volume)?,
+        }]),
+        ("mute", []) => Ok(vec![Request::SetMute { muted: true }]),
+        ("unmute", []) => Ok(vec![Request::SetMute { muted: false }]),
+        ("status", []) => Ok(vec![Request::GetStatus]),
+        ("subscribe", []) => Ok(vec![Request::Subscribe]),
+        _ => Err(AppError::InvalidInput(format!(
+            "Unknown command or wrong arguments: `{name}`."
+        ))),
+    }
+}
+
+fn parse_number(name: &str, value: &str) -> Result<f64, AppError> {
+    value
+        .parse()
+        .map_err(|_| AppError::InvalidInput(format!("`{name}` expects a number, not `{value}`.")))
+}
+
+#[cfg(test)]
+mod tests {
+    use cappella_core::control::request::Request;
+
+    use super::{is_error, parse};
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse(&words(&["pause"])).unwrap(), vec![Request::Pause]);
+        assert_eq!(
+            parse(&words(&["seek", "1.5"])).unwrap(),
+            vec![Request::Seek {
+                position_ns: 1_500_000_000
+            }]
+        );
+        assert_eq!(
+            parse(&words(&["play", "http://radio/stream"])).unwrap(),
+            vec![
+                Request::ClearQueue,
+                Request::Enqueue {
+                    uris: words(&["http://radio/stream"])
+                },
+                Request::JumpTo { index: 0 },
+            ]
+        );
+        assert_eq!(
+            parse(&words(&[r#"{"command":"jump_to","index":2}"#])).unwrap(),
+            vec![Request::JumpTo { index: 2 }]
+        );
+    }
+
+    #[test]
+    fn test_parse_err() {
+        assert!(parse(&[]).is_err());
+        assert!(parse(&words(&["play"])).is_err());
+        assert!(parse(&words(&["volume", "loud"])).is_err());
+        assert!(parse(&words(&["{\"command\":"])).is_err());
+    }
+
+    #[test]
+    fn test_is_error() {
+        assert!(!is_error(r#"{"ok":null}"#));
+        assert!(is_error(r#"{"error":{"kind":"LockTimeout"}}"#));
+        assert!(is_error("garbage"));
+    }
+}
@@ -1,2 +1 @@
-pub mod message;
 pub mod pipe;
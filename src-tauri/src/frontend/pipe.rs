@@ -1,21 +1,13 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::Arc};
 
+use cappella_core::frontend::{message::Message, pipe::Pipe};
 use tauri::{AppHandle, Emitter, EventTarget};
 
-use crate::MAIN_WINDOW_LABEL;
-
-use super::message::Message;
-
 const PLAYER_EVENT_NAME: &str = "PLAYER_EVENT";
 
-pub trait Pipe: Debug + Send + Sync {
-    fn send(&self, frontend_message: Message);
-}
-
-pub fn new_box(app_handle_addr: usize) -> Box<dyn Pipe> {
-    let app_handle_box = unsafe { Box::from_raw(app_handle_addr as *mut AppHandle) };
-    let app_handle = *app_handle_box;
-    Box::new(Pipe_ { app_handle })
+/// Created once at startup and shared by the components sending events.
+pub fn new_arc(app_handle: AppHandle) -> Arc<dyn Pipe> {
+    Arc::new(Pipe_ { app_handle })
 }
 
 #[derive(Debug)]
@@ -23,15 +15,13 @@ struct Pipe_ {
     app_handle: AppHandle,
 }
 
-unsafe impl Send for Pipe_ {}
-unsafe impl Sync for Pipe_ {}
-
 impl Pipe for Pipe_ {
     fn send(&self, frontend_message: Message) {
+        // Sent to every window so they all stay in sync.
         if self
             .app_handle
             .emit_to(
-                EventTarget::window(MAIN_WINDOW_LABEL),
+                EventTarget::Any,
                 PLAYER_EVENT_NAME,
                 frontend_message.clone(),
             )
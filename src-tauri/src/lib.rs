@@ -1,55 +1,483 @@
+use std::{env, path::PathBuf};
+
 use ::tauri::{AppHandle, Manager, State, Window, WindowEvent};
+use cappella_core::{
+    control::{request, subscribers},
+    library, local,
+    local::{app_error::AppError, uri},
+    player, stations,
+};
 
+#[cfg(unix)]
+mod control;
 mod frontend;
-mod local;
-mod player;
-mod streamer;
 
 pub const MAIN_WINDOW_LABEL: &str = "main";
 
 #[::tauri::command]
-fn play(app_handle: AppHandle, state: State<local::state::State>, uri: &str) {
-    let app_handle_box = Box::new(app_handle);
-    let app_handle_addr = Box::into_raw(app_handle_box) as usize;
-    state.player_front().play(app_handle_addr, uri);
+fn play(state: State<local::state::State>, uri: &str) -> Result<(), AppError> {
+    state.player_front().play(uri)
+}
+
+#[::tauri::command]
+fn pause(state: State<local::state::State>) -> Result<(), AppError> {
+    state.player_front().pause()
+}
+
+#[::tauri::command]
+fn stop(state: State<local::state::State>) -> Result<(), AppError> {
+    state.player_front().stop()
+}
+
+#[::tauri::command]
+fn seek(state: State<local::state::State>, position_ns: i64) -> Result<(), AppError> {
+    state.player_front().seek(position_ns)
+}
+
+#[::tauri::command]
+fn next(state: State<local::state::State>) -> Result<(), AppError> {
+    state.player_front().next()
+}
+
+#[::tauri::command]
+fn previous(state: State<local::state::State>) -> Result<(), AppError> {
+    state.player_front().previous()
+}
+
+#[::tauri::command]
+fn jump_to(state: State<local::state::State>, index: usize) -> Result<(), AppError> {
+    state.player_front().jump_to(index)
+}
+
+#[::tauri::command]
+fn enqueue(state: State<local::state::State>, uris: Vec<String>) -> Result<(), AppError> {
+    state.player_front().enqueue(uris)
+}
+
+#[::tauri::command]
+fn remove_from_queue(state: State<local::state::State>, index: usize) -> Result<(), AppError> {
+    state.player_front().remove_from_queue(index)
+}
+
+#[::tauri::command]
+fn move_in_queue(
+    state: State<local::state::State>,
+    from: usize,
+    to: usize,
+) -> Result<(), AppError> {
+    state.player_front().move_in_queue(from, to)
+}
+
+#[::tauri::command]
+fn clear_queue(state: State<local::state::State>) -> Result<(), AppError> {
+    state.player_front().clear_queue()
+}
+
+#[::tauri::command]
+fn set_gapless(state: State<local::state::State>, gapless: bool) {
+    state.player_front().set_gapless(gapless);
+}
+
+#[::tauri::command]
+fn set_repeat(
+    state: State<local::state::State>,
+    repeat: player::order::Repeat,
+) -> Result<(), AppError> {
+    state.player_front().set_repeat(repeat)
 }
 
 #[::tauri::command]
-fn pause(state: State<local::state::State>) {
-    state.player_front().pause();
+fn set_shuffle(
+    state: State<local::state::State>,
+    shuffle: bool,
+    seed: Option<u64>,
+) -> Result<(), AppError> {
+    state.player_front().set_shuffle(shuffle, seed)
 }
 
 #[::tauri::command]
-fn stop(state: State<local::state::State>) {
-    state.player_front().stop();
+fn set_volume(
+    state: State<local::state::State>,
+    volume: f64,
+) -> Result<player::front::Volume, AppError> {
+    let volume = state.player_front().set_volume(volume)?;
+    Ok(request::send_volume_changed(&state, volume))
+}
+
+#[::tauri::command]
+fn set_mute(
+    state: State<local::state::State>,
+    muted: bool,
+) -> Result<player::front::Volume, AppError> {
+    let volume = state.player_front().set_mute(muted)?;
+    Ok(request::send_volume_changed(&state, volume))
+}
+
+#[::tauri::command]
+fn get_volume(state: State<local::state::State>) -> Result<player::front::Volume, AppError> {
+    state.player_front().volume()
+}
+
+#[::tauri::command]
+fn get_status(state: State<local::state::State>) -> Result<player::front::Status, AppError> {
+    state.player_front().status()
+}
+
+#[::tauri::command]
+fn add_library_folder(state: State<local::state::State>, folder: PathBuf) -> Result<(), AppError> {
+    state.library_front().add_library_folder(folder)
+}
+
+#[::tauri::command]
+fn rescan_library(state: State<local::state::State>) -> Result<(), AppError> {
+    state.library_front().rescan_library()
+}
+
+#[::tauri::command]
+fn list_library_tracks(
+    state: State<local::state::State>,
+) -> Result<Vec<library::track::Track>, AppError> {
+    state.library_front().tracks()
+}
+
+#[::tauri::command]
+fn list_albums(state: State<local::state::State>) -> Result<Vec<library::track::Album>, AppError> {
+    state.library_front().albums()
+}
+
+#[::tauri::command]
+fn list_artists(
+    state: State<local::state::State>,
+) -> Result<Vec<library::track::Artist>, AppError> {
+    state.library_front().artists()
+}
+
+#[::tauri::command]
+fn list_tracks_by_album(
+    state: State<local::state::State>,
+    album_id: i64,
+) -> Result<Vec<library::track::Track>, AppError> {
+    state.library_front().tracks_by_album(album_id)
+}
+
+#[::tauri::command]
+fn list_tracks_by_artist(
+    state: State<local::state::State>,
+    artist_id: i64,
+) -> Result<Vec<library::track::Track>, AppError> {
+    state.library_front().tracks_by_artist(artist_id)
+}
+
+#[::tauri::command]
+fn search(
+    state: State<local::state::State>,
+    query: &str,
+    offset: usize,
+    limit: usize,
+) -> Result<library::search::SearchPage, AppError> {
+    state.library_front().search(query, offset, limit)
+}
+
+#[::tauri::command]
+fn list_stations(
+    state: State<local::state::State>,
+) -> Result<Vec<stations::station::Station>, AppError> {
+    state.stations_front().stations()
+}
+
+#[::tauri::command]
+fn add_station(
+    state: State<local::state::State>,
+    station: stations::station::Station,
+) -> Result<(), AppError> {
+    state.stations_front().add_station(station)
+}
+
+#[::tauri::command]
+fn remove_station(state: State<local::state::State>, name: &str) -> Result<(), AppError> {
+    state.stations_front().remove_station(name)
+}
+
+#[::tauri::command]
+fn play_station(state: State<local::state::State>, name: &str) -> Result<(), AppError> {
+    let station = state.stations_front().station(name)?;
+    state.player_front().play(&station.url)
+}
+
+#[::tauri::command]
+fn import_stations(state: State<local::state::State>, path: PathBuf) -> Result<usize, AppError> {
+    state.stations_front().import_stations(&path)
+}
+
+#[::tauri::command]
+fn export_stations(state: State<local::state::State>, path: PathBuf) -> Result<(), AppError> {
+    state.stations_front().export_stations(&path)
+}
+
+#[::tauri::command]
+fn load_playlist(state: State<local::state::State>, path: PathBuf) -> Result<usize, AppError> {
+    request::load_playlist(&state, &path)
+}
+
+#[::tauri::command]
+fn save_queue_as_playlist(
+    state: State<local::state::State>,
+    path: PathBuf,
+) -> Result<(), AppError> {
+    request::save_queue_as_playlist(&state, &path)
+}
+
+#[::tauri::command]
+fn list_playlists(
+    state: State<local::state::State>,
+) -> Result<Vec<library::track::Playlist>, AppError> {
+    state.library_front().playlists()
+}
+
+#[::tauri::command]
+fn list_playlist_tracks(
+    state: State<local::state::State>,
+    playlist_id: i64,
+) -> Result<Vec<library::track::Track>, AppError> {
+    state.library_front().playlist_tracks(playlist_id)
+}
+
+#[::tauri::command]
+fn create_playlist(
+    state: State<local::state::State>,
+    name: &str,
+) -> Result<library::track::Playlist, AppError> {
+    state.library_front().create_playlist(name)
+}
+
+#[::tauri::command]
+fn rename_playlist(
+    state: State<local::state::State>,
+    playlist_id: i64,
+    name: &str,
+) -> Result<(), AppError> {
+    state.library_front().rename_playlist(playlist_id, name)
+}
+
+#[::tauri::command]
+fn delete_playlist(state: State<local::state::State>, playlist_id: i64) -> Result<(), AppError> {
+    state.library_front().delete_playlist(playlist_id)
+}
+
+#[::tauri::command]
+fn duplicate_playlist(
+    state: State<local::state::State>,
+    playlist_id: i64,
+    name: &str,
+) -> Result<library::track::Playlist, AppError> {
+    state.library_front().duplicate_playlist(playlist_id, name)
+}
+
+#[::tauri::command]
+fn add_to_playlist(
+    state: State<local::state::State>,
+    playlist_id: i64,
+    uris: Vec<String>,
+) -> Result<(), AppError> {
+    state.library_front().add_to_playlist(playlist_id, uris)
+}
+
+#[::tauri::command]
+fn remove_from_playlist(
+    state: State<local::state::State>,
+    playlist_id: i64,
+    index: usize,
+) -> Result<(), AppError> {
+    state
+        .library_front()
+        .remove_from_playlist(playlist_id, index)
+}
+
+#[::tauri::command]
+fn move_in_playlist(
+    state: State<local::state::State>,
+    playlist_id: i64,
+    from: usize,
+    to: usize,
+) -> Result<(), AppError> {
+    state
+        .library_front()
+        .move_in_playlist(playlist_id, from, to)
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    let state = init();
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    #[cfg(unix)]
+    if let Some(code) = control::run_from_args(&args) {
+        std::process::exit(code);
+    }
 
     ::tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
-        .manage(state)
-        .invoke_handler(::tauri::generate_handler![play, pause, stop,])
+        .setup(move |app| {
+            let frontend_pipe = frontend::pipe::new_arc(app.handle().clone());
+            #[cfg(target_os = "linux")]
+            let mpris_bridge = cappella_core::mpris::bridge::new_arc(frontend_pipe);
+            #[cfg(target_os = "linux")]
+            let frontend_pipe = mpris_bridge.clone();
+            #[cfg(feature = "mpd")]
+            let mpd_events = cappella_core::mpd::events::new_arc(frontend_pipe);
+            #[cfg(feature = "mpd")]
+            let frontend_pipe = mpd_events.clone();
+
+            // The control connections receive the same events as the frontend.
+            let subscribers = subscribers::new_arc(frontend_pipe);
+            let state = cappella_core::init(
+                app.path().app_config_dir()?,
+                app.path().app_data_dir()?,
+                subscribers.clone(),
+            );
+
+            #[cfg(feature = "remote")]
+            start_remote_server(&state, subscribers.clone());
+
+            #[cfg(unix)]
+            start_control_server(app.handle(), &state, subscribers);
+
+            #[cfg(target_os = "linux")]
+            if let Err(err) = mpris_bridge.start(state.clone()) {
+                eprintln!("Unable to start MPRIS: {err}");
+            }
+
+            #[cfg(feature = "mpd")]
+            start_mpd_server(&state, mpd_events);
+
+            for request in request::play_uris(startup_uris(&args)) {
+                if let Err(err) = request::execute(&state, request) {
+                    eprintln!("Unable to play the startup files: {err}");
+                    break;
+                }
+            }
+
+            app.manage(state);
+            Ok(())
+        })
+        .invoke_handler(::tauri::generate_handler![
+            play,
+            pause,
+            stop,
+            seek,
+            next,
+            previous,
+            jump_to,
+            enqueue,
+            remove_from_queue,
+            move_in_queue,
+            clear_queue,
+            set_gapless,
+            set_repeat,
+            set_shuffle,
+            set_volume,
+            set_mute,
+            get_volume,
+            get_status,
+            add_library_folder,
+            rescan_library,
+            list_library_tracks,
+            list_albums,
+            list_artists,
+            list_tracks_by_album,
+            list_tracks_by_artist,
+            search,
+            list_stations,
+            add_station,
+            remove_station,
+            play_station,
+            import_stations,
+            export_stations,
+            load_playlist,
+            save_queue_as_playlist,
+            list_playlists,
+            list_playlist_tracks,
+            create_playlist,
+            rename_playlist,
+            delete_playlist,
+            duplicate_playlist,
+            add_to_playlist,
+            remove_from_playlist,
+            move_in_playlist,
+        ])
         .on_window_event(|window, event| on_window_event(window, event))
         .run(::tauri::generate_context!())
         .expect("error while running tauri application");
 }
 
-fn init() -> local::state::State {
-    // Step 1 in alphabetical order
-    let streamer_bus = streamer::bus::new_arc();
+/// The files of the command line, played on startup.
+fn startup_uris(args: &[String]) -> Vec<String> {
+    args.iter()
+        .filter(|arg| !arg.starts_with('-'))
+        .filter_map(|arg| match uri::from_uri_or_path(arg) {
+            Ok(uri) => Some(uri),
+            Err(err) => {
+                eprintln!("Ignored argument `{arg}`: {err}");
+                None
+            }
+        })
+        .collect()
+}
 
-    // Step 2 in alphabetical order
-    let streamer_front = streamer::front::new_box(streamer_bus.clone());
-    let streamer_pipe = streamer::pipe::new_box(streamer_bus.clone());
+#[cfg(unix)]
+fn start_control_server(
+    app_handle: &AppHandle,
+    state: &local::state::State,
+    subscribers: std::sync::Arc<subscribers::Subscribers>,
+) {
+    let socket_path = cappella_core::control::server::default_socket_path();
+
+    match cappella_core::control::server::start(&socket_path, state.clone(), subscribers) {
+        Ok(()) => {
+            app_handle.manage(control::ControlSocket(socket_path));
+        }
+        Err(err) => eprintln!("Unable to start the control server: {err}"),
+    }
+}
 
-    // Step 3 in alphabetical order
-    let player_front = player::front::new_box(streamer_front, streamer_pipe);
+/// Only if enabled in the settings.
+#[cfg(feature = "mpd")]
+fn start_mpd_server(
+    state: &local::state::State,
+    events: std::sync::Arc<cappella_core::mpd::events::Events>,
+) {
+    match state.settings().get() {
+        Ok(settings) if settings.mpd.enabled => {
+            if let Err(err) =
+                cappella_core::mpd::server::start(&settings.mpd.address, state.clone(), events)
+            {
+                eprintln!("Unable to start the MPD server: {err}");
+            }
+        }
+        Ok(_) => {}
+        Err(err) => eprintln!("Unable to read the MPD settings: {err}"),
+    }
+}
 
-    // Step 4 return
-    local::state::State::new(player_front)
+/// Only if enabled in the settings, with a token.
+#[cfg(feature = "remote")]
+fn start_remote_server(
+    state: &local::state::State,
+    subscribers: std::sync::Arc<subscribers::Subscribers>,
+) {
+    match state.settings().get() {
+        Ok(settings) if settings.remote.enabled => {
+            if let Err(err) = cappella_core::remote::server::start(
+                &settings.remote.address,
+                &settings.remote.token,
+                state.clone(),
+                subscribers,
+            ) {
+                eprintln!("Unable to start the remote control: {err}");
+            }
+        }
+        Ok(_) => {}
+        Err(err) => eprintln!("Unable to read the remote control settings: {err}"),
+    }
 }
 
 fn on_window_event(window: &Window, event: &WindowEvent) {
@@ -65,7 +493,10 @@ fn on_window_event(window: &Window, event: &WindowEvent) {
 }
 
 fn end_streamer(app_handle: &AppHandle) {
-    let state = app_handle.state::<local::state::State>();
-    state.player_front().stop();
-    state.player_front().wait_until_end();
+    app_handle.state::<local::state::State>().end();
+
+    #[cfg(unix)]
+    if let Some(control_socket) = app_handle.try_state::<control::ControlSocket>() {
+        cappella_core::control::server::remove(&control_socket.0);
+    }
 }
@@ -6,28 +6,179 @@ import {
   StopOutlined
 } from '@ant-design/icons';
 import { invoke } from '@tauri-apps/api/core';
-import { Button, Col, Flex, Row, Slider } from 'antd';
+import { listen } from '@tauri-apps/api/event';
+import { Button, Col, Flex, Row, Slider, message } from 'antd';
+import { useEffect, useState } from 'react';
+
+const PLAYER_EVENT = 'PLAYER_EVENT';
+const NANOS_PER_SECOND = 1_000_000_000;
+
+type PlayerState = 'Stopped' | 'Paused' | 'Playing';
+
+type Repeat = 'Off' | 'One' | 'All';
+
+type PlayerEvent =
+  | 'None'
+  | { Position: { position_ns: number; duration_ns: number } }
+  | { StateChanged: PlayerState }
+  | { TrackStarted: string }
+  | { TrackEnded: string }
+  | { NowPlaying: NowPlaying }
+  | { VolumeChanged: Volume }
+  | { Buffering: { percent: number } }
+  | { Reconnecting: { uri: string; attempt: number; delay_ms: number } }
+  | { LibraryScanProgress: { scanned: number; total: number } }
+  | { LibraryScanFinished: { total: number } }
+  | 'QueueChanged'
+  | { PlaylistChanged: { playlist_id: number } }
+  | { Error: ErrorReport }
+  | { Warning: ErrorReport }
+  | { MissingPlugin: { uri: string; description: string } };
+
+type GstError = {
+  domain: string;
+  code: number;
+  message: string;
+  debug: string | null;
+  source: string | null;
+};
+
+type ErrorReport = {
+  message: string;
+  uri: string | null;
+  gst_error: GstError | null;
+};
+
+type AppError = {
+  kind: string;
+  message: string;
+  gst_error: GstError | null;
+};
+
+type Volume = { volume: number; muted: boolean };
+
+type Status = {
+  state: PlayerState;
+  uri: string | null;
+  position_ns: number | null;
+  duration_ns: number | null;
+  seekable: boolean;
+  volume: Volume;
+  queue_index: number | null;
+  repeat: Repeat;
+  shuffle: boolean;
+};
+
+type NowPlaying = {
+  uri: string;
+  title: string | null;
+  artist: string | null;
+  album: string | null;
+  album_artist: string | null;
+  genre: string | null;
+  cover: string | null;
+};
+
+function withUri(text: string, uri: string | null) {
+  return uri ? `${text} (${decodeURI(uri)})` : text;
+}
+
+/** Invokes a command, its error is shown and resolved as `undefined`. */
+function command<T>(cmd: string, args?: Record<string, unknown>): Promise<T | undefined> {
+  return invoke<T>(cmd, args).catch((err: AppError) => {
+    console.error(err);
+    message.error(err.message);
+    return undefined;
+  });
+}
 
 function App() {
+  const [position, setPosition] = useState(0);
+  const [duration, setDuration] = useState(0);
+  const [seekable, setSeekable] = useState(false);
+  const [buffering, setBuffering] = useState(100);
+  const [volume, setVolume] = useState(1);
+  const [nowPlaying, setNowPlaying] = useState<NowPlaying | null>(null);
+
+  useEffect(() => {
+    // The player may be running already, as after a reload.
+    command<Status>('get_status').then((status) => {
+      if (!status) {
+        return;
+      }
+      setPosition((status.position_ns ?? 0) / NANOS_PER_SECOND);
+      setDuration((status.duration_ns ?? 0) / NANOS_PER_SECOND);
+      setSeekable(status.seekable);
+      setVolume(status.volume.volume);
+    });
+  }, []);
+
+  useEffect(() => {
+    const unlisten = listen<PlayerEvent>(PLAYER_EVENT, (event) => {
+      const payload = event.payload;
+      if (typeof payload !== 'object') {
+        return;
+      }
+      if ('Position' in payload) {
+        setPosition(payload.Position.position_ns / NANOS_PER_SECOND);
+        setDuration(Math.max(payload.Position.duration_ns, 0) / NANOS_PER_SECOND);
+        // A live stream has no duration.
+        setSeekable(payload.Position.duration_ns >= 0);
+      } else if ('TrackStarted' in payload || 'TrackEnded' in payload) {
+        setPosition(0);
+      } else if ('NowPlaying' in payload) {
+        setNowPlaying(payload.NowPlaying);
+      } else if ('VolumeChanged' in payload) {
+        setVolume(payload.VolumeChanged.volume);
+      } else if ('Buffering' in payload) {
+        setBuffering(payload.Buffering.percent);
+      } else if ('Reconnecting' in payload) {
+        const { uri, attempt, delay_ms } = payload.Reconnecting;
+        message.warning(
+          withUri(`Connection lost, attempt ${attempt} in ${delay_ms / 1000} s`, uri)
+        );
+      } else if ('Error' in payload) {
+        console.error(payload.Error);
+        message.error(withUri(payload.Error.message, payload.Error.uri));
+      } else if ('Warning' in payload) {
+        console.warn(payload.Warning);
+      } else if ('MissingPlugin' in payload) {
+        message.error(
+          withUri(`Missing plugin: ${payload.MissingPlugin.description}`, payload.MissingPlugin.uri)
+        );
+      }
+    });
+
+    return () => {
+      unlisten.then((f) => f());
+    };
+  }, []);
+
   return (
     <>
       <Row gutter={[8, 8]} align="middle">
         <Col flex="none">
           <Flex gap="small" wrap="wrap">
-            <Button type="primary" shape="circle" size="large" icon={<StepBackwardOutlined />} />
+            <Button
+              type="primary"
+              shape="circle"
+              size="large"
+              icon={<StepBackwardOutlined />}
+              onClick={(_) => command('previous')}
+            />
             <Button
               type="primary"
               shape="circle"
               size="large"
               icon={<StopOutlined />}
-              onClick={(_) => invoke('stop')}
+              onClick={(_) => command('stop')}
             />
             <Button
               type="primary"
               shape="circle"
               size="large"
               icon={<PauseOutlined />}
-              onClick={(_) => invoke('pause')}
+              onClick={(_) => command('pause')}
             />
             <Button
               type="primary"
@@ -35,18 +186,58 @@ function App() {
               size="large"
               icon={<CaretRightOutlined />}
               onClick={(_) =>
-                invoke('play', {
+                command('play', {
                   uri: 'file:///home/misha/Musique/Boy Meets Girl/Boy Meets Girl - Waiting For A Star To Fall.mp3'
                 })
               }
             />
-            <Button type="primary" shape="circle" size="large" icon={<StepForwardOutlined />} />
+            <Button
+              type="primary"
+              shape="circle"
+              size="large"
+              icon={<StepForwardOutlined />}
+              onClick={(_) => command('next')}
+            />
           </Flex>
         </Col>
         <Col flex="auto">
-          <Slider />
+          <Slider
+            min={0}
+            max={duration}
+            step={0.1}
+            value={position}
+            disabled={!seekable}
+            onChange={(value) => setPosition(value)}
+            onChangeComplete={(value) =>
+              command('seek', { positionNs: Math.round(value * NANOS_PER_SECOND) })
+            }
+          />
+        </Col>
+        {buffering < 100 && <Col flex="none">Buffering {buffering}%</Col>}
+        <Col flex="120px">
+          <Slider
+            min={0}
+            max={1}
+            step={0.01}
+            value={volume}
+            onChange={(value) => setVolume(value)}
+            onChangeComplete={(value) => command('set_volume', { volume: value })}
+          />
         </Col>
       </Row>
+      {nowPlaying && (
+        <Row gutter={[8, 8]} align="middle">
+          {nowPlaying.cover && (
+            <Col flex="none">
+              <img src={nowPlaying.cover} alt="" width={64} height={64} />
+            </Col>
+          )}
+          <Col flex="auto">
+            <div>{nowPlaying.title ?? nowPlaying.uri}</div>
+            <div>{[nowPlaying.artist, nowPlaying.album].filter(Boolean).join(' — ')}</div>
+          </Col>
+        </Row>
+      )}
     </>
   );
 }